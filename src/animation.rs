@@ -0,0 +1,120 @@
+// EASING 〰️
+// How a value chases its target each update. `velocity` is per-animated-value
+// state owned by the caller (for a LERP it's just last frame's step).
+#[derive(Clone, Copy)]
+pub enum Easing {
+    Lerp(f32),                                // Move this fraction of the gap per frame
+    Spring { stiffness: f32, damping: f32 }, // Can overshoot; damping 0..1 (1 = none)
+}
+
+impl Easing {
+    // One update: returns the new value, snaps once within `snap` (and at rest)
+    pub fn step(&self, current: f32, target: f32, velocity: &mut f32, snap: f32) -> f32 {
+        let diff = target - current;
+        match *self {
+            Easing::Lerp(factor) => {
+                let next = if diff.abs() > snap { current + diff * factor } else { target };
+                *velocity = next - current;
+                next
+            }
+            Easing::Spring { stiffness, damping } => {
+                if diff.abs() <= snap && velocity.abs() <= snap {
+                    *velocity = 0.0;
+                    return target;
+                }
+                *velocity = (*velocity + diff * stiffness) * damping;
+                current + *velocity
+            }
+        }
+    }
+}
+
+// ANIMATION CONFIG 🎛️
+// Shared tuning for every LERP in the grid. Passed down the chain
+// (ColumnList -> RowList -> PosterItem) on each update.
+#[derive(Clone, Copy)]
+pub struct AnimationConfig {
+    // Scroll snaps to its target once closer than this many PHYSICAL pixels
+    pub snap_threshold: f32,
+    pub device_pixel_ratio: f32,
+
+    // Independent curves: e.g. a snappy scale pop with a gentle scroll
+    pub scroll_x: Easing,
+    pub scroll_y: Easing,
+    pub scale: Easing,
+    pub dim: Easing, // Row brightness (focus moves, focus groups)
+    pub glide: Easing, // Items moving to a new slot (filtering, removal, relayout)
+    pub fade: Easing,  // Items fading in / out (filtering)
+
+    // Settled scales (the focus pop) drawn at whole physical pixel widths,
+    // so the poster everyone looks at has crisp edges
+    pub scale_pixel_snap: bool,
+
+    // Hold the focus pop on posters until their image is in (deep links
+    // into rows that are still loading: no blown-up blank tile)
+    pub defer_focus_until_loaded: bool,
+}
+
+impl AnimationConfig {
+    pub fn new() -> Self {
+        Self {
+            snap_threshold: 0.5,
+            device_pixel_ratio: 1.0,
+            scroll_x: Easing::Lerp(0.1),
+            scroll_y: Easing::Lerp(0.1),
+            scale: Easing::Lerp(0.15),
+            dim: Easing::Lerp(0.15),
+            glide: Easing::Lerp(0.15),
+            fade: Easing::Lerp(0.15),
+            scale_pixel_snap: false,
+            defer_focus_until_loaded: false,
+        }
+    }
+
+    // Snap distance in layout pixels: the same physical distance on any DPR
+    pub fn snap_distance(&self) -> f32 {
+        self.snap_threshold / self.device_pixel_ratio.max(0.01)
+    }
+
+    // Which easing a JS-facing channel name refers to
+    pub fn easing_mut(&mut self, channel: &str) -> Option<&mut Easing> {
+        match channel {
+            "scroll_x" => Some(&mut self.scroll_x),
+            "scroll_y" => Some(&mut self.scroll_y),
+            "scale" => Some(&mut self.scale),
+            "dim" => Some(&mut self.dim),
+            "glide" => Some(&mut self.glide),
+            "fade" => Some(&mut self.fade),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Updates until `easing` lands on the target
+    fn steps_to_rest(easing: Easing, snap: f32) -> usize {
+        let (mut value, mut velocity) = (0.0, 0.0);
+        (1..1000).find(|_| {
+            value = easing.step(value, -960.0, &mut velocity, snap);
+            value == -960.0
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn a_larger_snap_threshold_lands_sooner() {
+        let lerp = Easing::Lerp(0.1);
+        assert!(steps_to_rest(lerp, 4.0) < steps_to_rest(lerp, 0.5));
+    }
+
+    #[test]
+    fn snap_distance_is_in_physical_pixels() {
+        let mut anim = AnimationConfig::new();
+        anim.snap_threshold = 1.0;
+        anim.device_pixel_ratio = 2.0;
+        assert_eq!(anim.snap_distance(), 0.5);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::render_target::RenderTarget;
+use std::rc::Rc;
+use web_sys::{WebGlBuffer, WebGlProgram, WebGlRenderingContext, WebGlUniformLocation};
+
+// BACKGROUND BLUR 🌫️
+// Two-pass separable Gaussian: grid -> target A, horizontal blur A -> B,
+// vertical blur B -> screen. Used to push the grid "behind" a modal.
+// B is half the size of A: a blurred picture loses nothing at half
+// resolution and both passes get a quarter of the fill cost.
+pub struct BlurPass {
+    pub radius: f32,
+    pub freeze: bool, // true = keep showing the last grid frame under the blur
+
+    program: Rc<WebGlProgram>,
+    quad: WebGlBuffer,
+    direction_loc: Option<WebGlUniformLocation>,
+    scene: RenderTarget,
+    half: RenderTarget,
+    has_frame: bool,
+}
+
+// Size of the intermediate target for a `width` x `height` scene
+fn half_size(width: u32, height: u32) -> (u32, u32) {
+    ((width / 2).max(1), (height / 2).max(1))
+}
+
+impl BlurPass {
+    // `program` comes from the ShaderRegistry (ProgramKey::Blur)
+    pub fn new(context: &WebGlRenderingContext, program: Rc<WebGlProgram>, width: u32, height: u32) -> Result<Self, String> {
+        let direction_loc = context.get_uniform_location(&program, "u_direction");
+
+        // Full-screen quad in clip space, same (pos, uv) layout as the posters
+        let quad = context.create_buffer().ok_or("Failed to create blur quad")?;
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&quad));
+        let vertices: [f32; 24] = [
+            -1.0, -1.0, 0.0, 0.0,
+            -1.0,  1.0, 0.0, 1.0,
+             1.0, -1.0, 1.0, 0.0,
+             1.0, -1.0, 1.0, 0.0,
+            -1.0,  1.0, 0.0, 1.0,
+             1.0,  1.0, 1.0, 1.0,
+        ];
+        let vert_array = js_sys::Float32Array::from(vertices.as_slice());
+        context.buffer_data_with_array_buffer_view(WebGlRenderingContext::ARRAY_BUFFER, &vert_array, WebGlRenderingContext::STATIC_DRAW);
+
+        Ok(Self {
+            radius: 0.0,
+            freeze: false,
+            program,
+            quad,
+            direction_loc,
+            scene: RenderTarget::new(context, width, height)?,
+            half: {
+                let (half_width, half_height) = half_size(width, height);
+                RenderTarget::new(context, half_width, half_height)?
+            },
+            has_frame: false,
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.radius > 0.0
+    }
+
+    // 1. Should the grid be re-rendered into the scene target this frame?
+    pub fn needs_scene(&self) -> bool {
+        !(self.freeze && self.has_frame)
+    }
+
+    pub fn invalidate(&mut self) {
+        self.has_frame = false;
+    }
+
+    // 2. Start capturing the grid (everything drawn next lands in target A)
+    pub fn begin_scene(&mut self, context: &WebGlRenderingContext, width: u32, height: u32) -> Result<(), String> {
+        self.scene.resize(context, width, height)?;
+        let (half_width, half_height) = half_size(width, height);
+        self.half.resize(context, half_width, half_height)?;
+        self.scene.bind(context);
+        self.has_frame = true;
+        Ok(())
+    }
+
+    // 3. Run both blur passes and present the result on screen (only inside
+    // `clip`, the viewport rect's scissor, when there is one)
+    pub fn apply(&self, context: &WebGlRenderingContext, clip: Option<[i32; 4]>) {
+        // Taps reach ~3.23 texels, so scale the step to hit the requested radius.
+        // `step` is in full-resolution pixels; both passes turn it into UV
+        // with the scene's size, so the half target doesn't halve the radius
+        let step = self.radius / 3.230_769_2;
+
+        context.use_program(Some(&self.program));
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&self.quad));
+        let pos_loc = context.get_attrib_location(&self.program, "position") as u32;
+        let tex_loc = context.get_attrib_location(&self.program, "texCoord") as u32;
+        context.vertex_attrib_pointer_with_i32(pos_loc, 2, WebGlRenderingContext::FLOAT, false, 16, 0);
+        context.vertex_attrib_pointer_with_i32(tex_loc, 2, WebGlRenderingContext::FLOAT, false, 16, 8);
+
+        // A. Horizontal: scene -> half
+        context.disable(WebGlRenderingContext::SCISSOR_TEST);
+        self.half.bind(context);
+        context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&self.scene.texture));
+        context.uniform2f(self.direction_loc.as_ref(), step / self.scene.width as f32, 0.0);
+        context.draw_arrays(WebGlRenderingContext::TRIANGLES, 0, 6);
+
+        // B. Vertical: half -> screen (upscaled back to full size)
+        context.bind_framebuffer(WebGlRenderingContext::FRAMEBUFFER, None);
+        context.viewport(0, 0, self.scene.width as i32, self.scene.height as i32);
+        context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&self.half.texture));
+        context.uniform2f(self.direction_loc.as_ref(), 0.0, step / self.scene.height as f32);
+        if let Some([x, y, w, h]) = clip {
+            context.enable(WebGlRenderingContext::SCISSOR_TEST);
+            context.scissor(x, y, w, h);
+        }
+        context.draw_arrays(WebGlRenderingContext::TRIANGLES, 0, 6);
+    }
+
+    pub fn get_vertex_shader() -> &'static str {
+        r#"
+            attribute vec2 position;
+            attribute vec2 texCoord;
+            varying vec2 v_texCoord;
+            void main() {
+                gl_Position = vec4(position, 0, 1);
+                v_texCoord = texCoord;
+            }
+        "#
+    }
+
+    // 9-tap Gaussian using linear sampling (5 fetches per pass)
+    pub fn get_fragment_shader() -> &'static str {
+        r#"
+            precision mediump float;
+            varying vec2 v_texCoord;
+            uniform sampler2D u_texture;
+            uniform vec2 u_direction;
+            void main() {
+                vec2 off1 = u_direction * 1.3846153846;
+                vec2 off2 = u_direction * 3.2307692308;
+                vec4 color = texture2D(u_texture, v_texCoord) * 0.2270270270;
+                color += texture2D(u_texture, v_texCoord + off1) * 0.3162162162;
+                color += texture2D(u_texture, v_texCoord - off1) * 0.3162162162;
+                color += texture2D(u_texture, v_texCoord + off2) * 0.0702702703;
+                color += texture2D(u_texture, v_texCoord - off2) * 0.0702702703;
+                gl_FragColor = color;
+            }
+        "#
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intermediate_target_is_half_the_scene() {
+        assert_eq!(half_size(1920, 1080), (960, 540));
+        assert_eq!(half_size(1281, 721), (640, 360));
+        assert_eq!(half_size(1, 1), (1, 1)); // Never a zero-sized texture
+    }
+}
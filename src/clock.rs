@@ -0,0 +1,76 @@
+// CLOCK ⏱️
+// Milliseconds from `performance.now()` (0.0 when there's no window, e.g. in
+// a worker without a Performance object).
+// With the `test-utils` feature a TestClock can be installed instead, so
+// everything timed (hero dwell, key repeat, frame stats...) becomes deterministic.
+pub fn now_ms() -> f64 {
+    #[cfg(feature = "test-utils")]
+    if let Some(now) = test_clock::now() {
+        return now;
+    }
+
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or(0.0)
+}
+
+#[cfg(feature = "test-utils")]
+pub use test_clock::TestClock;
+
+#[cfg(feature = "test-utils")]
+mod test_clock {
+    use std::cell::Cell;
+
+    thread_local! {
+        // None = real clock. wasm is single threaded so one slot is enough
+        static NOW: Cell<Option<f64>> = const { Cell::new(None) };
+    }
+
+    pub(super) fn now() -> Option<f64> {
+        NOW.with(|now| now.get())
+    }
+
+    // Fake time source: once installed, now_ms() only moves when advanced
+    pub struct TestClock;
+
+    impl TestClock {
+        pub fn install(start_ms: f64) {
+            NOW.with(|now| now.set(Some(start_ms)));
+        }
+
+        pub fn is_installed() -> bool {
+            now().is_some()
+        }
+
+        pub fn advance(ms: f64) {
+            NOW.with(|now| now.set(Some(now.get().unwrap_or(0.0) + ms)));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use super::*;
+    use crate::columnlist::{ColumnList, MODEL_STEP_MS};
+
+    #[test]
+    fn advancing_the_test_clock_lands_the_scroll_on_a_predicted_value() {
+        TestClock::install(1000.0);
+        let mut list = ColumnList::new();
+        assert!(list.focus(5, 0));
+        let steps = list.update_at(now_ms());
+        TestClock::advance(2.0 * MODEL_STEP_MS + 1.0);
+        let steps = steps + list.update_at(now_ms());
+        assert_eq!(now_ms(), 1000.0 + 2.0 * MODEL_STEP_MS + 1.0);
+        assert_eq!(steps, 3);
+
+        // Same easing, same number of steps, no wall clock involved
+        let (mut expected, mut velocity) = (0.0, 0.0);
+        for _ in 0..steps {
+            let anim = &list.animation;
+            expected = anim.scroll_y.step(expected, list.target_scroll_y, &mut velocity, anim.snap_distance());
+        }
+        assert_eq!(list.scroll_y, expected);
+    }
+}
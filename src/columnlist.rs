@@ -1,8 +1,62 @@
-use crate::rowlist::RowList;
+use crate::animation::AnimationConfig;
+use crate::geometry_cache::GeometryCache;
+use crate::intro::{Intro, IntroKind};
+use crate::keymap::Action;
+use crate::painter::Painter;
+use crate::posteritem::{CoverAnchor, PosterItem};
+use crate::rowlist::{RowKind, RowList, RowTemplate, ITEM_HEIGHT, ITEM_WIDTH};
+use crate::state::GridState;
 use crate::texture_manager::TextureManager;
 use wasm_bindgen::JsValue;
 use web_sys::WebGlRenderingContext;
 
+// LAYOUT 📐
+pub const ROW_START_Y: f32 = 50.0;
+pub const ROW_SPACING: f32 = 480.0; // Row Height + Gap
+pub const ROW_GAP: f32 = ROW_SPACING - ITEM_HEIGHT; // Below every poster row, whatever its height
+
+// Animations are tuned per 60fps frame; update_at steps them in these units
+pub const MODEL_STEP_MS: f64 = 1000.0 / 60.0;
+
+// Items rebuilt per frame after a context loss (see context_lost)
+const RESTORE_BUDGET: usize = 12;
+
+// is_settled needs this many model steps in a row at rest, so an animation
+// that touches its target for one frame (a spring crossing it) doesn't count
+const SETTLED_STEPS: u32 = 3;
+
+// What happens when the last "page" of rows is shorter than the screen
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LastPageBehavior {
+    Clamp, // Stop scrolling once the last row sits on the bottom edge
+    Free,  // Keep shifting one row per step (last row can float mid-screen)
+}
+
+// A move off the outside of the whole grid: Up on the first row, Down on
+// the last one, Left on the very first item (first row), Right on the very
+// last item (last row). Nothing to move to, so the surrounding UI (nav bar,
+// footer...) may want focus instead. Left/Right at the end of any other row
+// is an internal edge and isn't reported.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BlockedEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl BlockedEdge {
+    // JS-facing direction of the exit
+    pub fn direction(&self) -> &'static str {
+        match self {
+            BlockedEdge::Top => "up",
+            BlockedEdge::Bottom => "down",
+            BlockedEdge::Left => "left",
+            BlockedEdge::Right => "right",
+        }
+    }
+}
+
 pub struct ColumnList {
     pub rows: Vec<RowList>,
     pub selected_row_index: usize, // Which row is currently active?
@@ -10,23 +64,93 @@ pub struct ColumnList {
     // VERTICAL SCROLL STATE 📜
     pub scroll_y: f32,
     pub target_scroll_y: f32,
+    pub scroll_velocity_y: f32, // Pixels moved during the last update
+
+    // Visible area (for clamping the scroll at the end of the content)
+    pub viewport_width: f32,
+    pub viewport_height: f32,
+
+    // PageUp/PageDown jump size in rows (0 = rows_per_screen)
+    pub page_size: usize,
+    // Rows that fit on screen (0 = viewport height / ROW_SPACING, so it
+    // follows resizes); focus moves down this far before the list scrolls
+    pub rows_per_screen: usize,
+    pub last_page_behavior: LastPageBehavior,
+
+    // SCROLL ANCHORS ⚓ Rows kept above the focused one while moving down
+    // (top, None = rows_per_screen - 1) / up (bottom). 1 + 1 = the focused
+    // row always sits second.
+    pub top_anchor_rows: Option<usize>,
+    pub bottom_anchor_rows: usize,
+    top_row: usize,         // Row currently scrolled to the top edge
+    // PEEK 👀 Fraction (0..1) of the row below the focused one kept on screen
+    pub peek_next_row: f32,
+    anchored_focus: usize,  // selected_row_index when top_row was last worked out
+
+    pub animation: AnimationConfig,
+
+    // First-show slide / fade of the whole grid (see set_intro)
+    pub intro: Intro,
+
+    // FOCUSED GROUP: rows [start, end] stay bright, the rest use group_dim
+    pub focus_group: Option<(usize, usize)>,
+    pub group_dim: f32,
+
+    // Brightness of every row but the focused one (1.0 = no dimming)
+    pub inactive_dim: f32,
+
+    // Nudge scaled-up posters back inside the viewport (edge items at 1.2x)
+    keep_selection_onscreen: bool,
+
+    // Consecutive update_model steps with nothing moving (see is_settled)
+    rest_steps: u32,
+
+    // CONTEXT RESTORE ♻️ Items load_pending rebuilds per frame while
+    // restoring (0 = all at once), and how many needed it (0 = not restoring)
+    pub restore_budget: usize,
+    restore_total: usize,
+
+    // update_at's clock: previous timestamp + time not yet stepped
+    last_update_at: Option<f64>,
+    step_carry_ms: f64,
+    // One update_at advances at most this much, whatever the gap: after a
+    // hidden tab or a long GC the scroll eases on instead of jumping
+    pub max_frame_dt_ms: f64,
+
+    // Remembered so rows created later look like the rest
+    pub corner_radii: [f32; 4],
+    pub cover_anchor: CoverAnchor,
+
+    // Rows kept uploaded above and below the visible ones
+    pub cull_margin: usize,
+    // Rows actually drawn above and below (see set_render_overscan)
+    pub render_overscan: usize,
+
+    // Pointer drag scrolls the active row (touch / trackpad)
+    pub kinetic_scroll: bool,
+
+    // One vertex buffer per distinct poster geometry, shared by every row
+    pub geometry: GeometryCache,
+
+    // FRAME BUDGET ⏱️ Items set up (buffer + texture request) per frame, 0 = all at once
+    pub load_budget: usize,
 }
 
 impl ColumnList {
     pub fn new() -> Self {
-        let mut rows = Vec::new();
-
         // ⚠️ STRESS TEST SETTING:
-        // You can change "0..20" to "0..1000" now!
+        // You can change "20" to "1000" now!
         // Because we use TextureManager, 1000 rows (10,000 items) will only use ~20MB RAM.
-        for i in 0..20 {
-            // Calculate Y position: Start at 50, go down 220px per row
-            let y_start = 50.0 + (i as f32 * 480.0);
-
-            // Create a row at that specific Y height
-            let row = RowList::new(y_start);
+        Self::from_template(20, &RowTemplate::demo())
+    }
 
-            rows.push(row);
+    // BATCH BUILD: `row_count` rows shaped by `template`, allocated up front
+    pub fn from_template(row_count: usize, template: &RowTemplate) -> Self {
+        let mut rows = Vec::with_capacity(row_count);
+        for i in 0..row_count {
+            // Calculate Y position: Start at 50, go down one row (+ gap) per row
+            let y_start = ROW_START_Y + (i as f32 * (template.item_height + ROW_GAP));
+            rows.push(RowList::from_template(y_start, i, template));
         }
 
         let mut list = Self {
@@ -36,6 +160,36 @@ impl ColumnList {
             // Start at 0
             scroll_y: 0.0,
             target_scroll_y: 0.0,
+            scroll_velocity_y: 0.0,
+            viewport_width: 1920.0,
+            viewport_height: 1080.0,
+            page_size: 0,
+            rows_per_screen: 0,
+            last_page_behavior: LastPageBehavior::Clamp,
+            top_anchor_rows: None,
+            bottom_anchor_rows: 1,
+            top_row: 0,
+            anchored_focus: 0,
+            peek_next_row: 0.0,
+            animation: AnimationConfig::new(),
+            intro: Intro::new(),
+            focus_group: None,
+            group_dim: 0.5,
+            inactive_dim: 1.0,
+            keep_selection_onscreen: false,
+            rest_steps: 0,
+            restore_budget: RESTORE_BUDGET,
+            restore_total: 0,
+            last_update_at: None,
+            step_carry_ms: 0.0,
+            max_frame_dt_ms: 50.0,
+            corner_radii: [0.0; 4],
+            cover_anchor: CoverAnchor::Center,
+            cull_margin: 1,
+            render_overscan: 1,
+            kinetic_scroll: false,
+            geometry: GeometryCache::new(),
+            load_budget: 20, // Two demo rows per frame: first screen at once, no startup hitch
         };
 
         // Activate the first row by default
@@ -52,89 +206,838 @@ impl ColumnList {
         context: &WebGlRenderingContext,
         manager: &mut TextureManager,
     ) -> Result<(), JsValue> {
+        if self.load_budget > 0 {
+            // With a budget: the first batch now, load_pending() does the rest
+            return self.load_pending(context, manager);
+        }
         for row in &mut self.rows {
             // We pass the manager so rows can request SHARED textures
-            row.load_assets(context, manager)?;
+            row.load_assets(context, manager, &mut self.geometry)?;
         }
         Ok(())
     }
 
-    // 2. INPUT HANDLER (Up/Down Logic)
-    pub fn handle_input(&mut self, key_code: u32) {
-        match key_code {
-            38 => {
-                // UP ARROW
-                if self.selected_row_index > 0 {
-                    // A. Deactivate old row (Visuals: selected item shrinks)
-                    self.rows[self.selected_row_index].is_active = false;
-
-                    // B. Move Selection
-                    self.selected_row_index -= 1;
-
-                    // C. Activate new row (Visuals: saved item grows)
-                    self.rows[self.selected_row_index].is_active = true;
+    // Called every frame: spend this frame's budget on items still waiting,
+    // top rows first. Pass 1 only takes what each row shows before scrolling
+    // (+ its cull margin), so the first screen fills in before the rest.
+    pub fn load_pending(&mut self, context: &WebGlRenderingContext, manager: &mut TextureManager) -> Result<(), JsValue> {
+        let mut budget = self.frame_load_budget();
+        let visible = self.visible_rows();
+        if let Some((first, last)) = visible {
+            for row in &mut self.rows[first..=last] {
+                row.sharpen_srcsets(context, manager)?;
+            }
+        }
+        for first_screen in [true, false] {
+            for (i, row) in self.rows.iter_mut().enumerate() {
+                if budget == 0 {
+                    return Ok(());
                 }
+                let upto = if first_screen { row.visible_count() + row.cull_margin } else { usize::MAX };
+                let on_screen = visible.is_some_and(|(first, last)| (first..=last).contains(&i));
+                budget -= row.load_pending(context, manager, &mut self.geometry, budget, upto, on_screen)?;
             }
-            40 => {
-                // DOWN ARROW
-                if self.selected_row_index < self.rows.len() - 1 {
-                    // A. Deactivate old row
-                    self.rows[self.selected_row_index].is_active = false;
+            if first_screen {
+                manager.end_initial_loads(); // Everything on the first screen has been requested
+            }
+        }
+        Ok(())
+    }
+
+    // Items this frame's load_pending may set up: restore_budget while a
+    // context restore is going on, load_budget otherwise (0 = no limit)
+    pub fn frame_load_budget(&mut self) -> usize {
+        if self.restore_total > 0 && self.unbuilt_count() == 0 {
+            self.restore_total = 0; // Restore finished
+        }
+        let limit = if self.restore_total > 0 { self.restore_budget } else { self.load_budget };
+        if limit == 0 { usize::MAX } else { limit }
+    }
 
-                    // B. Move Selection
-                    self.selected_row_index += 1;
+    // TEXTURE BUDGET 🧮 Over budget: offscreen posters give up the least
+    // recently requested textures, then the manager deletes whatever nothing
+    // holds any more. What's on (or near) screen is pinned throughout.
+    pub fn evict_textures(&mut self, context: &WebGlRenderingContext, manager: &mut TextureManager) -> usize {
+        if !manager.is_over_budget() {
+            return 0;
+        }
+        let mut pinned = Vec::new();
+        if let Some((first, last)) = self.visible_rows() {
+            for row in &self.rows[first..=last] {
+                row.visible_textures(&mut pinned);
+            }
+        }
+        let candidates = manager.eviction_candidates(&pinned);
+        for row in &mut self.rows {
+            row.release_textures(&candidates);
+        }
+        drop(candidates);
+        manager.evict_unused(context, &pinned)
+    }
 
-                    // C. Activate new row
-                    self.rows[self.selected_row_index].is_active = true;
+    // Visible rows' posters again at the manager's new texture scale
+    pub fn reload_textures(&mut self, context: &WebGlRenderingContext, manager: &mut TextureManager) -> Result<usize, JsValue> {
+        let Some((first, last)) = self.visible_rows() else {
+            return Ok(0);
+        };
+        let mut reloaded = 0;
+        for row in &mut self.rows[first..=last] {
+            reloaded += row.reload_textures(context, manager)?;
+        }
+        Ok(reloaded)
+    }
+
+    // The GL context was lost and came back: every buffer and texture is
+    // gone. Items are rebuilt by load_pending, restore_budget a frame, first
+    // screen first, so what's visible is back within a few frames.
+    pub fn context_lost(&mut self) {
+        self.geometry = GeometryCache::new();
+        for row in &mut self.rows {
+            row.context_lost();
+        }
+        self.restore_total = self.unbuilt_count();
+    }
+
+    // 0..1 through a restore, 1.0 when there's none going on
+    pub fn restore_progress(&self) -> f32 {
+        if self.restore_total == 0 {
+            return 1.0;
+        }
+        1.0 - self.unbuilt_count() as f32 / self.restore_total as f32
+    }
+
+    // Items still without a GPU buffer
+    fn unbuilt_count(&self) -> usize {
+        self.rows.iter().flat_map(|row| &row.items).filter(|item| item.buffer.is_none() && !item.evicted).count()
+    }
+
+    // 2. INPUT HANDLER (Up/Down Logic)
+    // Returns the grid boundary the move ran into (focus doesn't move)
+    pub fn handle_input(&mut self, action: Action) -> Option<BlockedEdge> {
+        let mut blocked = None;
+        match action {
+            Action::Up => {
+                // UP ARROW (spacers are skipped)
+                match (0..self.selected_row_index).rev().find(|&i| self.rows[i].is_focusable()) {
+                    Some(up) => self.select_row(up),
+                    None => blocked = Some(BlockedEdge::Top),
                 }
             }
-            // LEFT (37) or RIGHT (39) -> Delegate to the Active Row
-            37 | 39 => {
-                if let Some(row) = self.rows.get_mut(self.selected_row_index) {
-                    row.handle_input(key_code);
+            Action::Down => {
+                // DOWN ARROW
+                match (self.selected_row_index + 1..self.rows.len()).find(|&i| self.rows[i].is_focusable()) {
+                    Some(down) => self.select_row(down),
+                    None => blocked = Some(BlockedEdge::Bottom),
                 }
             }
-            _ => {}
+            // PAGE UP / PAGE DOWN: a multi-row Up/Down, blocked the same way
+            Action::PageUp | Action::PageDown => {
+                let page = self.effective_page_size();
+                let (target, edge) = if action == Action::PageUp {
+                    (self.selected_row_index.saturating_sub(page), BlockedEdge::Top)
+                } else {
+                    (self.selected_row_index + page, BlockedEdge::Bottom)
+                };
+                let to = self.nearest_focusable(target);
+                if to == self.selected_row_index {
+                    blocked = Some(edge);
+                }
+                self.select_row(to);
+            }
+            // HOME / END: first jump to the row's first/last item;
+            // pressing again while already there jumps to the first/last ROW.
+            Action::Home | Action::End => {
+                let to_end = action == Action::End;
+                let at_edge = self.rows.get(self.selected_row_index).map_or(true, |row| {
+                    let edge = if to_end { row.items.len().saturating_sub(1) } else { 0 };
+                    row.selected_index == edge
+                });
+                if at_edge {
+                    let edge = if to_end { self.rows.len().saturating_sub(1) } else { 0 };
+                    self.select_row(self.nearest_focusable(edge));
+                } else if let Some(row) = self.rows.get_mut(self.selected_row_index) {
+                    row.handle_input(action);
+                }
+            }
+            // LEFT or RIGHT -> Delegate to the Active Row
+            Action::Left | Action::Right => {
+                let current = self.selected_row_index;
+                if let Some(row) = self.rows.get_mut(current) {
+                    let from = (row.selected_index, row.target_scroll_x);
+                    row.handle_input(action);
+                    let stuck = (row.selected_index, row.target_scroll_x) == from && !row.items.is_empty();
+                    // Only the grid's first/last row has an outer side edge
+                    let (edge, outer) = if action == Action::Left {
+                        (BlockedEdge::Left, !self.rows[..current].iter().any(|r| r.is_focusable()))
+                    } else {
+                        (BlockedEdge::Right, !self.rows[current + 1..].iter().any(|r| r.is_focusable()))
+                    };
+                    if stuck && outer {
+                        blocked = Some(edge);
+                    }
+                }
+            }
+            // Nothing moves; the engine reports it to JS (see focused_item)
+            Action::Select => {}
+        }
+
+        self.update_scroll_target();
+        blocked
+    }
+
+    // (row, item) with keyboard focus, None if the focused row is empty
+    pub fn focused_item(&self) -> Option<(usize, usize)> {
+        let row = self.rows.get(self.selected_row_index)?;
+        row.items.get(row.selected_index)?;
+        Some((self.selected_row_index, row.selected_index))
+    }
+
+    // Bounds-checked (row, item)
+    pub fn item_mut(&mut self, row: usize, item: usize) -> Option<&mut PosterItem> {
+        self.rows.get_mut(row)?.items.get_mut(item)
+    }
+
+    // Unavailable posters can be focused but not activated
+    pub fn focused_is_disabled(&self) -> bool {
+        self.focused_item()
+            .is_some_and(|(row, item)| !self.rows[row].items[item].enabled)
+    }
+
+    // Put keyboard focus on (row, item) directly, e.g. a search hit; false if
+    // it doesn't exist
+    pub fn focus(&mut self, row_index: usize, item_index: usize) -> bool {
+        if self.rows.get(row_index).map_or(true, |row| item_index >= row.items.len()) {
+            return false;
+        }
+        self.select_row(row_index);
+        let row = &mut self.rows[row_index];
+        row.selected_index = item_index;
+        row.refocus();
+        self.update_scroll_target();
+        // Whatever was at rest before the jump isn't any more
+        self.rest_steps = 0;
+        true
+    }
+
+    // Every item in each row's `items` with its (row, item) coordinates. Items a
+    // filter has finished hiding aren't there; ones still fading out are.
+    pub fn iter_items(&self) -> impl Iterator<Item = (usize, usize, &PosterItem)> {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| row.items.iter().enumerate().map(move |(i, item)| (r, i, item)))
+    }
+
+    // Same, for bulk edits (ids, elevation...)
+    pub fn iter_items_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut PosterItem)> {
+        self.rows
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(r, row)| row.items.iter_mut().enumerate().map(move |(i, item)| (r, i, item)))
+    }
+
+    // `index` (clamped) if it can take focus, else the closest row that can
+    // (ties go up). Just the clamped index if nothing is focusable.
+    fn nearest_focusable(&self, index: usize) -> usize {
+        let index = index.min(self.rows.len().saturating_sub(1));
+        let below = (index..self.rows.len()).find(|&i| self.rows[i].is_focusable());
+        let above = (0..index).rev().find(|&i| self.rows[i].is_focusable());
+        match (above, below) {
+            (Some(a), Some(b)) if index - a <= b - index => a,
+            (_, Some(b)) => b,
+            (Some(a), None) => a,
+            (None, None) => index,
+        }
+    }
+
+    // SPACER ␣ Append blank vertical space below the last row
+    pub fn push_spacer(&mut self, height: f32) {
+        self.rows.push(RowList::spacer(self.next_row_y(), height));
+    }
+
+    // A row of sprite sheet cells below the last row; returns its index
+    pub fn push_sprite_row(&mut self, src: &str, cols: usize, rows: usize, count: usize) -> usize {
+        let mut row = RowList::from_sprite_sheet(self.next_row_y(), src, cols, rows, count, ITEM_WIDTH);
+        row.viewport_width = self.viewport_width;
+        row.set_corner_radii(self.corner_radii);
+        row.set_cover_anchor(self.cover_anchor);
+        self.rows.push(row);
+        self.refocus();
+        self.rows.len() - 1
+    }
+
+    // Where a row appended now would go
+    fn next_row_y(&self) -> f32 {
+        self.rows.last().map_or(ROW_START_Y, |last| last.y + Self::slot_height(last))
+    }
+
+    // Vertical room a row takes in the stack: its tallest poster + ROW_GAP
+    // (a 200px demo row = ROW_SPACING), a spacer just its own height
+    fn slot_height(row: &RowList) -> f32 {
+        match row.kind {
+            RowKind::Spacer(height) => height,
+            RowKind::Posters => row.height() + ROW_GAP,
+        }
+    }
+
+    // Move focus to another row (clamped), updating both rows' visuals
+    fn select_row(&mut self, index: usize) {
+        let index = index.min(self.rows.len().saturating_sub(1));
+        if index == self.selected_row_index {
+            return;
+        }
+        // A. Deactivate old row (Visuals: selected item shrinks)
+        self.rows[self.selected_row_index].is_active = false;
+
+        // B. Move Selection
+        self.selected_row_index = index;
+
+        // C. Activate new row (Visuals: saved item grows)
+        self.rows[self.selected_row_index].is_active = true;
+    }
+
+    // Put focus + every scroll back exactly as captured, without animating.
+    // Rows that no longer exist are ignored; indexes are clamped.
+    pub fn restore_state(&mut self, state: &GridState) {
+        self.selected_row_index = state.selected_row_index;
+        self.refocus();
+        self.scroll_y = state.scroll_y;
+        self.target_scroll_y = state.target_scroll_y;
+        self.scroll_velocity_y = 0.0;
+        // Carry on anchoring from the restored scroll, not from before it
+        let restored_top = -state.target_scroll_y + ROW_START_Y + 0.5;
+        self.top_row = self.rows.iter().rposition(|row| row.y <= restored_top).unwrap_or(0);
+        self.anchored_focus = self.selected_row_index;
+
+        for (row, saved) in self.rows.iter_mut().zip(&state.rows) {
+            row.selected_index = saved.selected_index;
+            row.refocus();
+            row.scroll_x = saved.scroll_x;
+            row.target_scroll_x = saved.target_scroll_x;
+            row.scroll_velocity_x = 0.0;
+            let (active, selected_index) = (row.is_active, row.selected_index);
+            for (i, item) in row.items.iter_mut().enumerate() {
+                item.settle_selection(active && i == selected_index);
+            }
+        }
+    }
+
+    // POINTER 👆 Only does anything with kinetic_scroll on; x in layout pixels
+    pub fn pointer_down(&mut self, x: f32) {
+        if !self.kinetic_scroll {
+            return;
+        }
+        if let Some(row) = self.rows.get_mut(self.selected_row_index) {
+            row.begin_drag(x);
+        }
+    }
+
+    pub fn pointer_move(&mut self, x: f32) {
+        if let Some(row) = self.rows.get_mut(self.selected_row_index) {
+            row.drag_to(x);
+        }
+    }
+
+    // Every row, in case focus moved to another row mid-drag
+    pub fn pointer_up(&mut self) {
+        for row in &mut self.rows {
+            row.end_drag();
         }
+    }
 
-        // --- VERTICAL SCROLL CALCULATION ---
-        // Rule: If we go past index 1 (the 2nd row), start scrolling up.
-        // Row Height = 220.0
+    // Highlight a contiguous range of rows (inclusive); keyboard focus is unaffected
+    pub fn set_focus_group(&mut self, start: usize, end: usize) {
+        self.focus_group = Some((start.min(end), start.max(end)));
+    }
+
+    pub fn clear_focus_group(&mut self) {
+        self.focus_group = None;
+    }
+
+    pub fn set_page_size(&mut self, rows: usize) {
+        self.page_size = rows;
+    }
+
+    pub fn set_last_page_behavior(&mut self, behavior: LastPageBehavior) {
+        self.last_page_behavior = behavior;
+        self.update_scroll_target();
+    }
+
+    pub fn set_scroll_anchors(&mut self, top_rows: usize, bottom_rows: usize) {
+        self.top_anchor_rows = Some(top_rows);
+        self.bottom_anchor_rows = bottom_rows;
+        self.update_scroll_target();
+    }
 
-        if self.selected_row_index > 1 {
-            // If index is 2, shift up by 1 row height (220)
-            // If index is 3, shift up by 2 row heights (440)
-            let shift_count = (self.selected_row_index - 1) as f32;
-            self.target_scroll_y = -(shift_count * 480.0);
+    // Always show this much (0..1) of the row under the focused one, as a hint
+    // there's more below; the whole column sits a bit higher to make room
+    pub fn set_peek_next_row(&mut self, fraction: f32) {
+        self.peek_next_row = fraction.clamp(0.0, 1.0);
+        self.update_scroll_target();
+    }
+
+    pub fn set_rows_per_screen(&mut self, rows: usize) {
+        self.rows_per_screen = rows;
+        self.update_scroll_target();
+    }
+
+    pub fn effective_rows_per_screen(&self) -> usize {
+        if self.rows_per_screen > 0 {
+            self.rows_per_screen
         } else {
-            // If index is 0 or 1, stay at top
-            self.target_scroll_y = 0.0;
+            // The peek takes its share of the screen away from whole rows
+            let room = self.viewport_height - self.peek_height();
+            ((room / ROW_SPACING).floor() as usize).max(1)
         }
     }
 
-    // 3. UPDATE LOOP
-    pub fn update(&mut self, context: &WebGlRenderingContext) {
-        // 1. Vertical Lerp Logic
-        let diff = self.target_scroll_y - self.scroll_y;
-        if diff.abs() > 0.5 {
-            self.scroll_y += diff * 0.1; // Smooth scroll
+    // The focusable row under the focused one (spacers skipped), which the
+    // peek shows part of
+    fn peek_row(&self) -> Option<&RowList> {
+        self.rows.iter().skip(self.selected_row_index + 1).find(|row| row.is_focusable())
+    }
+
+    // Pixels of that row kept on screen
+    fn peek_height(&self) -> f32 {
+        self.peek_row().map_or(0.0, |next| next.height() * self.peek_next_row)
+    }
+
+    fn effective_page_size(&self) -> usize {
+        if self.page_size > 0 {
+            self.page_size
+        } else {
+            self.effective_rows_per_screen()
+        }
+    }
+
+    // --- VERTICAL SCROLL CALCULATION ---
+    fn update_scroll_target(&mut self) {
+        // Rule: moving down, scroll once more than top_anchor_rows rows are
+        // above the focus (by default: once it would drop below the screen);
+        // moving up, once fewer than bottom_anchor_rows are.
+        let selected = self.selected_row_index;
+        let down_anchor = self
+            .top_anchor_rows
+            .unwrap_or_else(|| self.effective_rows_per_screen() - 1);
+        self.top_row = if selected >= self.anchored_focus {
+            self.top_row.max(selected.saturating_sub(down_anchor))
         } else {
-            self.scroll_y = self.target_scroll_y;
+            self.top_row.min(selected.saturating_sub(self.bottom_anchor_rows))
         }
+        .min(selected);
+        self.anchored_focus = selected;
+
+        // Bring top_row to the top edge (spacers count their own height)
+        self.target_scroll_y = match self.rows.get(self.top_row) {
+            Some(top) => -(top.y - ROW_START_Y),
+            None => 0.0,
+        };
+
+        // Peek: nudge up until that much of the next row shows, but never so
+        // far the focused row's top leaves the screen
+        if let (Some(focused), Some(next)) = (self.rows.get(selected), self.peek_row()) {
+            if self.peek_next_row > 0.0 {
+                let peek_bottom = next.y + self.peek_height();
+                let needed = (self.viewport_height - peek_bottom).min(self.target_scroll_y);
+                let limit = -(focused.y - ROW_START_Y);
+                self.target_scroll_y = needed.max(limit.min(self.target_scroll_y));
+            }
+        }
+
+        // Clamp: never scroll past the end, the last row stops at the bottom edge
+        if self.last_page_behavior == LastPageBehavior::Clamp {
+            let max_scroll = (self.content_height() - self.viewport_height).max(0.0);
+            self.target_scroll_y = self.target_scroll_y.max(-max_scroll);
+        }
+    }
+
+    // Mark (row, item) as now playing, or clear it. Not exclusive: call it
+    // with false on the previous one first if only one should show
+    pub fn set_playing(&mut self, row: usize, item: usize, playing: bool) -> bool {
+        match self.item_mut(row, item) {
+            Some(item) => {
+                item.playing = playing;
+                item.mark_dirty();
+                true
+            }
+            None => false,
+        }
+    }
 
+    // VISUAL SCROLL ONLY 🖱️ Move the active row by `dx` and the column by
+    // `dy` (pixels, clamped to the content) without moving the selection, so
+    // the focused poster may end up off screen. The next focus move scrolls
+    // back to it as usual.
+    pub fn scroll_by(&mut self, dx: f32, dy: f32) {
+        if dx != 0.0 {
+            if let Some(row) = self.rows.get_mut(self.selected_row_index) {
+                row.scroll_by(dx);
+            }
+        }
+        let max_scroll = (self.content_height() - self.viewport_height).max(0.0);
+        self.target_scroll_y = (self.target_scroll_y + dy).clamp(-max_scroll, 0.0);
+    }
+
+    // Total column height: last row's bottom edge + the same margin as the top
+    pub fn content_height(&self) -> f32 {
+        match self.rows.last() {
+            Some(last) => last.y + last.height() + ROW_START_Y,
+            None => 0.0,
+        }
+    }
+
+    // (widest row's content_width, content_height): everything laid out,
+    // headers and spacers included, for scrollbars and the like
+    pub fn content_size(&self) -> (f32, f32) {
+        let width = self.rows.iter().map(|row| row.content_width()).fold(0.0, f32::max);
+        (width, self.content_height())
+    }
+
+    // Tell the list (and every row) how big the visible area is
+    pub fn set_viewport(&mut self, width: f32, height: f32) {
+        self.viewport_width = width;
+        self.viewport_height = height;
+        for row in &mut self.rows {
+            row.set_screen_width(width);
+        }
+        self.refocus();
+    }
+
+    // RELAYOUT: re-stack the rows, re-slot their items, then snap focus back
+    pub fn relayout(&mut self) {
+        // Cumulative: spacers push everything below them down by their height
+        let mut y = ROW_START_Y;
         for row in &mut self.rows {
-            // Give every row the global vertical offset
-            row.offset_y = self.scroll_y;
-            row.update(context);
+            row.y = y;
+            row.relayout();
+            y += Self::slot_height(row);
+        }
+        self.refocus();
+    }
+
+    // Clamp the focused row (off any spacer) and make sure exactly that row is active
+    pub fn refocus(&mut self) {
+        self.selected_row_index = self.nearest_focusable(self.selected_row_index);
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            row.is_active = i == self.selected_row_index;
+            row.refocus();
+        }
+        self.update_scroll_target();
+    }
+
+    // Per-item shadow depth; false when (row, item) doesn't exist
+    pub fn set_item_elevation(&mut self, row_index: usize, item_index: usize, elevation: f32) -> bool {
+        match self.rows.get_mut(row_index).and_then(|row| row.items.get_mut(item_index)) {
+            Some(item) => {
+                item.set_elevation(elevation);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // false for out-of-range indices or an image that hasn't arrived yet
+    pub fn is_item_loaded(&self, row_index: usize, item_index: usize) -> bool {
+        self.rows
+            .get(row_index)
+            .and_then(|row| row.items.get(item_index))
+            .is_some_and(|item| item.is_loaded())
+    }
+
+    // Glide one item to a new logical position (layout pixels)
+    pub fn move_item(&mut self, row_index: usize, item_index: usize, x: f32, y: f32) -> bool {
+        match self.rows.get_mut(row_index).and_then(|row| row.items.get_mut(item_index)) {
+            Some(item) => {
+                item.glide_to(x, y);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Bulk-replace one row's items (e.g. "Recommended" after personalization)
+    pub fn replace_row_items(
+        &mut self,
+        row_index: usize,
+        items: Vec<PosterItem>,
+        gl: Option<(&WebGlRenderingContext, &mut TextureManager)>,
+    ) -> Result<(), JsValue> {
+        let (corner_radii, cover_anchor) = (self.corner_radii, self.cover_anchor);
+        let row = self.rows.get_mut(row_index).ok_or("row index out of range")?;
+        row.replace_items(gl.as_ref().map(|(context, _)| *context), items);
+        row.set_corner_radii(corner_radii);
+        row.set_cover_anchor(cover_anchor);
+        if let (0, Some((context, manager))) = (self.load_budget, gl) {
+            row.load_assets(context, manager, &mut self.geometry)?;
+        } // else: load_pending() picks the new items up over the next frames
+        self.refocus();
+        Ok(())
+    }
+
+    // Remove a single poster; focus stays on the same logical item
+    pub fn remove_item(&mut self, context: Option<&WebGlRenderingContext>, row_index: usize, item_index: usize) -> bool {
+        let removed = match self.rows.get_mut(row_index) {
+            Some(row) => row.remove_item(context, item_index).is_some(),
+            None => false,
+        };
+        if removed {
+            self.relayout();
+        }
+        removed
+    }
+
+    // Starting pose of the first-show animation; false once the grid has
+    // already been stepped (it only ever plays on the first frames)
+    pub fn set_intro(&mut self, kind: IntroKind) -> bool {
+        self.intro.set(kind, self.viewport_height)
+    }
+
+    // 3. UPDATE LOOP
+    // 3a. All the animation maths, no GL context required
+    pub fn update_model(&mut self) {
+        // 1. Vertical easing (velocity doubles as the spring state)
+        let anim = self.animation;
+        self.scroll_y = anim.scroll_y.step(self.scroll_y, self.target_scroll_y, &mut self.scroll_velocity_y, anim.snap_distance());
+        self.intro.step(&anim);
+
+        for i in 0..self.rows.len() {
+            // Recomputed every frame, so quick Up/Down presses can only ever
+            // leave a row fading towards its current target
+            let target_dim = self.target_dim(i);
+            let row = &mut self.rows[i];
+            row.target_dim = target_dim;
+
+            // Give every row the global vertical offset (+ the intro's slide)
+            row.offset_y = self.scroll_y + self.intro.offset_y;
+            row.update_model(&self.animation);
+
+            // 2. Scaled past an edge? Slide inward (after the row placed its items)
+            if self.keep_selection_onscreen {
+                for item in &mut row.items {
+                    item.fit_onscreen(self.viewport_width, self.viewport_height);
+                }
+            }
+        }
+
+        // 3. Hysteresis for is_settled
+        self.rest_steps = if self.at_rest() { self.rest_steps.saturating_add(1) } else { 0 };
+    }
+
+    // Rows outside the focused group (if any) and unfocused rows are dimmed
+    fn target_dim(&self, row: usize) -> f32 {
+        let group = match self.focus_group {
+            Some((start, end)) if row < start || row > end => self.group_dim,
+            _ => 1.0,
+        };
+        let focus = if row == self.selected_row_index { 1.0 } else { self.inactive_dim };
+        group.min(focus)
+    }
+
+    // SNAP TO REST ⏭️ Every animation (column + row scroll, dims, intro,
+    // glides, fades, focus pops) jumps to its target. The next update_model
+    // then has nothing left to move.
+    pub fn settle_now(&mut self) {
+        self.scroll_y = self.target_scroll_y;
+        self.scroll_velocity_y = 0.0;
+        self.intro.finish();
+        for i in 0..self.rows.len() {
+            let target_dim = self.target_dim(i);
+            let row = &mut self.rows[i];
+            row.target_dim = target_dim;
+            row.settle_now();
+        }
+    }
+
+    // AT REST 🧘 The one "everything stopped" signal (scroll end, focus_and_wait...):
+    // column scroll, the focused row's scroll and every visible poster's
+    // glide / scale / fade have all been still for SETTLED_STEPS steps
+    pub fn is_settled(&self) -> bool {
+        self.rest_steps >= SETTLED_STEPS
+    }
+
+    // Nothing moving right now (this step only)
+    fn at_rest(&self) -> bool {
+        if self.scroll_y != self.target_scroll_y || self.intro.is_playing() {
+            return false;
+        }
+        if self.rows.get(self.selected_row_index).is_some_and(|row| row.scroll_x != row.target_scroll_x) {
+            return false;
+        }
+        let Some((first, last)) = self.visible_rows() else {
+            return true;
+        };
+        self.rows[first..=last].iter().all(|row| {
+            row.visible_items().map_or(true, |(first, last)| row.items[first..=last].iter().all(|item| item.is_settled()))
+        })
+    }
+
+    pub fn set_keep_selection_onscreen(&mut self, enabled: bool) {
+        self.keep_selection_onscreen = enabled;
+        if !enabled {
+            for (_, _, item) in self.iter_items_mut() {
+                item.clear_edge_nudge();
+            }
+        }
+    }
+
+    // EXTERNAL CLOCK ⏲️ Advance the model to `timestamp_ms` on any clock
+    // (audio, server time, a replay): the time since the previous call is run
+    // as whole MODEL_STEP_MS steps (at most max_frame_dt_ms worth), the
+    // remainder carried to the next call. The first call steps once.
+    // Returns how many steps ran.
+    pub fn update_at(&mut self, timestamp_ms: f64) -> u32 {
+        let dt = match self.last_update_at {
+            Some(last) => (timestamp_ms - last).clamp(0.0, self.max_frame_dt_ms), // A clock going backwards just waits
+            None => MODEL_STEP_MS,
+        };
+        self.last_update_at = Some(timestamp_ms);
+
+        self.step_carry_ms += dt;
+        let mut steps = 0;
+        while self.step_carry_ms >= MODEL_STEP_MS {
+            self.update_model();
+            self.step_carry_ms -= MODEL_STEP_MS;
+            steps += 1;
+        }
+        steps
+    }
+
+    // Never below one step, or update_at could never advance
+    pub fn set_max_frame_dt(&mut self, ms: f64) {
+        self.max_frame_dt_ms = if ms.is_finite() { ms.max(MODEL_STEP_MS) } else { MODEL_STEP_MS };
+    }
+
+    // DIRTY FLAGS 🚩 Something changed since the last drawn frame
+    pub fn is_dirty(&self) -> bool {
+        self.intro.dirty || self.rows.iter().any(|row| row.is_dirty())
+    }
+
+    // Items that animate on their own (see PosterItem::is_always_animating)
+    pub fn animating_count(&self) -> usize {
+        self.rows.iter().flat_map(|row| &row.items).filter(|item| item.is_always_animating()).count()
+    }
+
+    pub fn clear_dirty(&mut self) {
+        self.intro.dirty = false;
+        for row in &mut self.rows {
+            row.clear_dirty();
+        }
+    }
+
+    // Any scroll (the column or any row) still on its way to the target?
+    pub fn is_scrolling(&self) -> bool {
+        self.scroll_y != self.target_scroll_y || self.rows.iter().any(|row| row.scroll_x != row.target_scroll_x)
+    }
+
+    // CULLING ✂️ Inclusive range of rows kept on the GPU: on screen + the
+    // wider of cull_margin / render_overscan each side. Clamped to the list,
+    // None if no row is on screen.
+    pub fn visible_rows(&self) -> Option<(usize, usize)> {
+        self.rows_around_screen(self.cull_margin.max(self.render_overscan))
+    }
+
+    // Inclusive range of rows drawn: on screen + render_overscan each side
+    pub fn drawn_rows(&self) -> Option<(usize, usize)> {
+        self.rows_around_screen(self.render_overscan)
+    }
+
+    fn rows_around_screen(&self, margin: usize) -> Option<(usize, usize)> {
+        let on_screen = |row: &RowList| {
+            let top = row.y + self.scroll_y;
+            top + Self::slot_height(row) > 0.0 && top < self.viewport_height
+        };
+        let first = self.rows.iter().position(on_screen)?;
+        let last = self.rows.iter().rposition(on_screen).unwrap_or(first);
+        let max = self.rows.len() - 1;
+        Some((first.saturating_sub(margin), (last + margin).min(max)))
+    }
+
+    // How many offscreen rows / items per row stay warm around the visible set
+    pub fn set_cull_margin(&mut self, rows: usize, items: usize) {
+        self.cull_margin = rows;
+        for row in &mut self.rows {
+            row.cull_margin = items;
+        }
+    }
+
+    // How many offscreen rows / items per row are actually drawn, so fast
+    // scrolling doesn't show them popping in right at the edge. Independent
+    // of the cull margin (those are only kept uploaded); the ranges are
+    // clamped to the grid anyway, so big values just mean "everything".
+    pub fn set_render_overscan(&mut self, items: usize, rows: usize) {
+        self.render_overscan = rows;
+        for row in &mut self.rows {
+            row.render_overscan = items;
+        }
+    }
+
+    // 3b. GPU side of the update (culled rows keep their dirty flags for later)
+    pub fn upload(&mut self, context: &WebGlRenderingContext, pixel_ratio: f32) {
+        if let Some((first, last)) = self.visible_rows() {
+            for row in &mut self.rows[first..=last] {
+                row.upload(context, &mut self.geometry, pixel_ratio);
+            }
+        }
+        self.geometry.sweep(context); // Buffers of removed / resized items
+    }
+
+    // SCROLL SNAPSHOT: [active row scroll_x, column scroll_y, velocity]
+    // Velocity is the combined distance (px) both LERPs moved last frame.
+    pub fn scroll_offsets(&self) -> [f32; 3] {
+        let (scroll_x, velocity_x) = self
+            .rows
+            .get(self.selected_row_index)
+            .map(|row| (row.scroll_x, row.scroll_velocity_x))
+            .unwrap_or((0.0, 0.0));
+        let velocity = velocity_x.hypot(self.scroll_velocity_y);
+        [scroll_x, self.scroll_y, velocity]
+    }
+
+    // Rendered center of the focused poster (pixels), None if nothing is focused
+    pub fn selected_center(&self) -> Option<[f32; 2]> {
+        let row = self.rows.get(self.selected_row_index)?;
+        let [x, y, w, h] = row.items.get(row.selected_index)?.current_rect();
+        Some([x + w / 2.0, y + h / 2.0])
+    }
+
+    // Where (row, item) is drawn right now in layout pixels: (x, y, w, h) with
+    // scroll, glide and scale applied. None if it doesn't exist or is
+    // entirely off screen.
+    pub fn item_screen_rect(&self, row: usize, item: usize) -> Option<[f32; 4]> {
+        let [x, y, w, h] = self.rows.get(row)?.items.get(item)?.current_rect();
+        let onscreen = x + w > 0.0 && x < self.viewport_width && y + h > 0.0 && y < self.viewport_height;
+        onscreen.then_some([x, y, w, h])
+    }
+
+    // Which part of the image survives a cover crop, for the whole grid
+    pub fn set_cover_anchor(&mut self, anchor: CoverAnchor) {
+        self.cover_anchor = anchor;
+        for row in &mut self.rows {
+            row.set_cover_anchor(anchor);
+        }
+    }
+
+    // Rounded poster corners for the whole grid (0 = square)
+    pub fn set_corner_radius(&mut self, radius: f32) {
+        self.set_corner_radii([radius; 4]);
+    }
+
+    // Per corner: top-left, top-right, bottom-right, bottom-left
+    pub fn set_corner_radii(&mut self, radii: [f32; 4]) {
+        self.corner_radii = radii;
+        for row in &mut self.rows {
+            row.set_corner_radii(radii);
         }
     }
 
     // 4. DRAW LOOP
-    pub fn draw(&self, context: &WebGlRenderingContext) {
-        // Optimization: In a real engine, you'd only draw rows visible on screen!
-        // For now, we draw everything.
-        for row in &self.rows {
-            row.draw(context);
+    pub fn draw(&self, context: &WebGlRenderingContext, painter: &Painter) {
+        // Only rows on (or just off) screen
+        let Some((first, last)) = self.drawn_rows() else {
+            return;
+        };
+        for row in &self.rows[first..=last] {
+            row.draw(context, painter);
         }
     }
 
@@ -146,3 +1049,599 @@ impl ColumnList {
     //     None
     // }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 20 demo rows of 10 posters on a 1920x1080 screen, ids "row-item"
+    fn grid() -> ColumnList {
+        let mut list = ColumnList::new();
+        list.set_viewport(1920.0, 1080.0);
+        for (r, i, item) in list.iter_items_mut() {
+            item.id = format!("{}-{}", r, i);
+        }
+        list
+    }
+
+    fn focused_id(list: &ColumnList) -> String {
+        let (row, item) = list.focused_item().unwrap();
+        list.rows[row].items[item].id.clone()
+    }
+
+    #[test]
+    fn removing_items_before_the_focus_keeps_the_same_item_focused() {
+        let mut list = grid();
+        assert!(list.focus(0, 7));
+        assert_eq!(list.rows[0].target_scroll_x, -3.0 * crate::rowlist::ITEM_SPACING);
+
+        assert!(list.remove_item(None, 0, 0));
+        assert!(list.remove_item(None, 0, 0));
+        assert_eq!(list.rows[0].selected_index, 5);
+        assert_eq!(focused_id(&list), "0-7");
+        // Two slots further left now: scrolled two items less
+        assert_eq!(list.rows[0].target_scroll_x, -crate::rowlist::ITEM_SPACING);
+    }
+
+    #[test]
+    fn removing_the_focused_last_item_clamps_to_the_new_last() {
+        let mut list = grid();
+        list.focus(0, 9);
+        assert!(list.remove_item(None, 0, 9));
+        assert_eq!(list.focused_item(), Some((0, 8)));
+        assert!(!list.remove_item(None, 0, 42));
+    }
+
+    #[test]
+    fn scroll_offsets_follow_the_column_scroll() {
+        let mut list = grid();
+        for _ in 0..3 {
+            list.handle_input(Action::Down);
+        }
+        list.update_model();
+        let [_, scroll_y, velocity] = list.scroll_offsets();
+        assert_eq!(scroll_y, list.scroll_y);
+        assert!(scroll_y < 0.0 && velocity > 0.0);
+
+        list.settle_now();
+        list.update_model();
+        assert_eq!(list.scroll_offsets()[2], 0.0);
+    }
+
+    #[test]
+    fn scrolling_to_the_end_stops_with_the_last_row_at_the_bottom() {
+        let mut list = grid();
+        let last = list.rows.len() - 1;
+        assert!(list.focus(last, 9));
+        let row = &list.rows[last];
+        // Bottom margin = top margin, on both axes
+        assert_eq!(row.y + row.height() + list.target_scroll_y + ROW_START_Y, list.viewport_height);
+        let item = row.items.last().unwrap();
+        let right = item.target_x() + item.w + row.target_scroll_x + crate::rowlist::ITEM_START_X;
+        assert_eq!(right, list.viewport_width);
+    }
+
+    #[test]
+    fn page_down_jumps_a_page_and_counts_as_one_move() {
+        use crate::nav_sounds::Cue;
+        let mut list = grid();
+        list.set_page_size(3);
+        let before = list.focused_item();
+        assert_eq!(list.handle_input(Action::PageDown), None);
+        assert_eq!(list.focused_item(), Some((3, 0)));
+        let active: Vec<usize> = (0..list.rows.len()).filter(|&i| list.rows[i].is_active).collect();
+        assert_eq!(active, [3]);
+        // Same single focus-moved notification as a plain Down
+        assert_eq!(Cue::of(Action::PageDown, before, list.focused_item(), None), Some(Cue::Nav));
+    }
+
+    #[test]
+    fn page_keys_at_the_ends_are_blocked_like_up_and_down() {
+        let mut list = grid();
+        assert_eq!(list.handle_input(Action::PageUp), Some(BlockedEdge::Top));
+        list.focus(list.rows.len() - 2, 0);
+        assert_eq!(list.handle_input(Action::PageDown), None); // Clamped to the last row
+        assert_eq!(list.handle_input(Action::PageDown), Some(BlockedEdge::Bottom));
+    }
+
+    #[test]
+    fn end_reveals_the_last_item_then_jumps_to_the_last_row() {
+        let mut list = grid();
+        list.handle_input(Action::End);
+        assert_eq!(list.focused_item(), Some((0, 9)));
+        let content = list.rows[0].content_width();
+        assert_eq!(list.rows[0].target_scroll_x, -(content - list.viewport_width));
+
+        list.handle_input(Action::End);
+        assert_eq!(list.focused_item().map(|(row, _)| row), Some(list.rows.len() - 1));
+        list.handle_input(Action::Home);
+        list.handle_input(Action::Home);
+        assert_eq!(list.focused_item().map(|(row, _)| row), Some(0));
+    }
+
+    #[test]
+    fn rows_outside_the_focus_group_use_the_group_dim() {
+        let mut list = grid();
+        list.group_dim = 0.3;
+        list.set_focus_group(4, 2);
+        assert_eq!(list.focus_group, Some((2, 4)));
+        list.settle_now();
+        let dims: Vec<f32> = list.rows[..6].iter().map(|row| row.dim).collect();
+        assert_eq!(dims, [0.3, 0.3, 1.0, 1.0, 1.0, 0.3]);
+        assert_eq!(list.selected_row_index, 0); // Keyboard focus untouched
+    }
+
+    #[test]
+    fn replacing_a_row_swaps_items_and_clamps_the_selection() {
+        let mut list = grid();
+        list.focus(1, 8);
+        let items = (0..3).map(|i| PosterItem::new(0.0, 0.0, ITEM_WIDTH, 200.0, &format!("new-{}", i), true)).collect();
+        list.replace_row_items(1, items, None).unwrap();
+
+        let row = &list.rows[1];
+        let srcs: Vec<&str> = row.items.iter().map(|item| item.src.as_str()).collect();
+        assert_eq!(srcs, ["new-0", "new-1", "new-2"]);
+        assert_eq!(list.focused_item(), Some((1, 2)));
+        assert_eq!(row.target_scroll_x, 0.0);
+        assert_eq!(row.items[2].x, crate::rowlist::ITEM_START_X + 2.0 * crate::rowlist::ITEM_SPACING);
+    }
+
+    #[test]
+    fn visible_set_grows_with_the_cull_margin() {
+        let mut list = grid();
+        list.update_model();
+        // Rows 0-2 and items 0-5 overlap the screen
+        assert_eq!(list.visible_rows(), Some((0, 3)));
+        assert_eq!(list.rows[0].visible_items(), Some((0, 7)));
+
+        list.set_cull_margin(3, 3);
+        assert_eq!(list.visible_rows(), Some((0, 5)));
+        assert_eq!(list.rows[0].visible_items(), Some((0, 8)));
+
+        list.focus(10, 0);
+        list.settle_now();
+        let (first, last) = list.visible_rows().unwrap();
+        assert!(first <= 10 && last >= 10 && first > 0);
+    }
+
+    #[test]
+    fn selected_center_is_the_drawn_center_of_the_focus() {
+        let mut list = grid();
+        list.focus(2, 6);
+        for _ in 0..200 {
+            list.update_model();
+        }
+        let [x, y, w, h] = list.rows[2].items[6].current_rect();
+        let [cx, cy] = list.selected_center().unwrap();
+        assert!((cx - (x + w / 2.0)).abs() < 0.5 && (cy - (y + h / 2.0)).abs() < 0.5);
+        // Scrolled into view, popped around its own center
+        assert!(cx > 0.0 && cx < list.viewport_width && cy > 0.0 && cy < list.viewport_height);
+        assert_eq!(w, 300.0 * crate::posteritem::SELECTED_SCALE);
+    }
+
+    #[test]
+    fn template_rows_are_laid_out_and_share_one_geometry() {
+        let template = RowTemplate {
+            item_count: 8,
+            item_width: 200.0,
+            item_height: 300.0,
+            src: Box::new(|row, item| format!("poster-{}-{}.jpg", row, item)),
+        };
+        let list = ColumnList::from_template(100, &template);
+        assert_eq!(list.rows.len(), 100);
+        assert_eq!(list.rows[99].y, ROW_START_Y + 99.0 * (300.0 + ROW_GAP));
+        assert_eq!(list.rows[42].items[7].src, "poster-42-7.jpg");
+        assert!(list.rows.iter().all(|row| row.items.capacity() == 8));
+
+        let keys: std::collections::HashSet<_> = list.iter_items().map(|(_, _, item)| item.geometry_key()).collect();
+        assert_eq!(keys.len(), 1); // 800 posters, one vertex buffer
+    }
+
+    #[test]
+    fn fresh_and_missing_items_are_not_loaded() {
+        let list = grid();
+        assert!(!list.is_item_loaded(0, 0)); // No image yet
+        assert!(!list.is_item_loaded(0, 99));
+        assert!(!list.is_item_loaded(99, 0));
+    }
+
+    #[test]
+    fn a_spring_scroll_overshoots_while_the_scale_does_not() {
+        let mut list = grid();
+        list.animation.scroll_y = crate::animation::Easing::Spring { stiffness: 0.1, damping: 0.8 };
+        list.handle_input(Action::Down);
+        list.handle_input(Action::Down);
+        let target = list.target_scroll_y;
+        assert!(target < 0.0);
+        let (mut furthest, mut biggest) = (0.0f32, 0.0f32);
+        for _ in 0..300 {
+            list.update_model();
+            furthest = furthest.min(list.scroll_y);
+            biggest = biggest.max(list.rows[2].items[0].anim_scale);
+        }
+        assert!(furthest < target); // Past the target and back
+        assert_eq!(list.scroll_y, target);
+        assert_eq!(biggest, crate::posteritem::SELECTED_SCALE);
+    }
+
+    #[test]
+    fn free_last_page_keeps_scrolling_past_the_clamp() {
+        let mut list = grid();
+        let last = list.rows.len() - 1;
+        list.focus(last, 0);
+        let clamped = list.target_scroll_y;
+        let bottom = list.rows[last].y + list.rows[last].height() + clamped;
+        assert_eq!(bottom, list.viewport_height - ROW_START_Y);
+
+        list.set_last_page_behavior(LastPageBehavior::Free);
+        assert!(list.target_scroll_y < clamped); // Last row floats higher up
+        list.set_last_page_behavior(LastPageBehavior::Clamp);
+        assert_eq!(list.target_scroll_y, clamped);
+    }
+
+    #[test]
+    fn leaving_a_row_fades_its_dim_over_several_updates() {
+        let mut list = grid();
+        list.inactive_dim = 0.4;
+        list.settle_now();
+        list.handle_input(Action::Down);
+        let mut dims = Vec::new();
+        for _ in 0..4 {
+            list.update_model();
+            dims.push(list.rows[0].dim);
+        }
+        assert!(dims.windows(2).all(|w| w[1] < w[0]) && dims[3] > 0.4);
+
+        // Quick Up/Down/Up: everyone still heads for their current target
+        list.handle_input(Action::Up);
+        list.handle_input(Action::Down);
+        list.handle_input(Action::Up);
+        for _ in 0..200 {
+            list.update_model();
+        }
+        assert_eq!((list.rows[0].dim, list.rows[1].dim), (1.0, 0.4));
+    }
+
+    #[test]
+    fn iterating_a_3x10_grid_yields_every_coordinate() {
+        let list = ColumnList::from_template(3, &RowTemplate::demo());
+        let coords: Vec<(usize, usize)> = list.iter_items().map(|(r, i, _)| (r, i)).collect();
+        assert_eq!(coords.len(), 30);
+        assert_eq!(coords[0], (0, 0));
+        assert_eq!(coords[13], (1, 3));
+        assert_eq!(coords[29], (2, 9));
+
+        let mut list = list;
+        for (_, _, item) in list.iter_items_mut() {
+            item.elevation = 4.0;
+        }
+        assert!(list.iter_items().all(|(_, _, item)| item.elevation == 4.0));
+    }
+
+    #[test]
+    fn navigation_skips_spacers_and_layout_counts_their_height() {
+        let mut list = grid();
+        list.rows.insert(1, RowList::spacer(0.0, 100.0));
+        list.relayout();
+        assert_eq!(list.rows[2].y, ROW_START_Y + ROW_SPACING + 100.0);
+
+        list.handle_input(Action::Down);
+        assert_eq!(list.selected_row_index, 2);
+        list.handle_input(Action::Up);
+        assert_eq!(list.selected_row_index, 0);
+
+        list.push_spacer(60.0);
+        let last = list.rows.len() - 1;
+        assert_eq!(list.content_height(), list.rows[last].y + 60.0 + ROW_START_Y);
+        list.focus(last - 1, 0);
+        assert_eq!(list.handle_input(Action::Down), Some(BlockedEdge::Bottom));
+    }
+
+    #[test]
+    fn a_setter_on_one_item_makes_the_grid_dirty() {
+        let mut list = grid();
+        list.settle_now();
+        list.update_model();
+        list.clear_dirty();
+        list.update_model();
+        assert!(!list.is_dirty()); // At rest, nothing to redraw
+
+        list.rows[1].items[2].set_label("Watched");
+        assert!(list.rows[1].items[2].is_dirty());
+        assert!(list.is_dirty());
+        list.clear_dirty();
+        list.rows[1].items[2].mark_dirty();
+        assert!(list.is_dirty());
+    }
+
+    #[test]
+    fn explicit_scroll_anchors_override_the_screen_height() {
+        let mut list = grid();
+        list.set_scroll_anchors(0, 2);
+
+        // Top 0: the very first step down scrolls the focused row to the top
+        list.handle_input(Action::Down);
+        assert_eq!(list.target_scroll_y, -ROW_SPACING);
+        for _ in 0..4 {
+            list.handle_input(Action::Down);
+        }
+        assert_eq!(list.target_scroll_y, -5.0 * ROW_SPACING);
+
+        // Bottom 2: moving up keeps two rows above the focus
+        list.handle_input(Action::Up);
+        assert_eq!(list.selected_row_index, 4);
+        assert_eq!(list.target_scroll_y, -2.0 * ROW_SPACING);
+    }
+
+    #[test]
+    fn more_rows_per_screen_scroll_later() {
+        // Row index the focus reaches before the list first scrolls
+        let first_scroll = |rows: usize| {
+            let mut list = grid();
+            list.set_rows_per_screen(rows);
+            (1..10)
+                .find(|_| {
+                    list.handle_input(Action::Down);
+                    list.target_scroll_y != 0.0
+                })
+                .unwrap()
+        };
+        assert_eq!(first_scroll(2), 2);
+        assert_eq!(first_scroll(4), 4);
+    }
+
+    #[test]
+    fn a_disabled_poster_is_ghosted_and_not_activatable() {
+        let mut list = grid();
+        list.rows[0].items[1].disabled_opacity = 0.3;
+        list.rows[0].items[1].set_enabled(false);
+        assert_eq!(list.rows[0].items[1].draw_alpha(), 0.3);
+        assert!(!list.focused_is_disabled());
+
+        // Navigation still moves onto it
+        list.handle_input(Action::Right);
+        assert_eq!(focused_id(&list), "0-1");
+        assert!(list.focused_is_disabled());
+    }
+
+    #[test]
+    fn a_focus_jump_settles_only_once_the_scroll_arrives() {
+        let mut list = grid();
+        for _ in 0..200 {
+            list.update_model();
+        }
+        assert!(list.is_settled());
+
+        assert!(list.focus(6, 3));
+        assert!(!list.is_settled());
+        for _ in 0..1000 {
+            if list.is_settled() {
+                break;
+            }
+            list.update_model();
+        }
+        assert_eq!(list.scroll_y, list.target_scroll_y);
+        assert!(list.target_scroll_y < 0.0);
+    }
+
+    #[test]
+    fn up_and_down_on_a_single_row_report_the_escape_edges() {
+        let mut list = ColumnList::from_template(1, &RowTemplate::demo());
+        list.handle_input(Action::Right);
+        assert_eq!(list.handle_input(Action::Up), Some(BlockedEdge::Top));
+        assert_eq!(list.handle_input(Action::Down), Some(BlockedEdge::Bottom));
+        assert_eq!(list.focused_item(), Some((0, 1)));
+    }
+
+    #[test]
+    fn the_screen_rect_follows_scroll_and_the_focus_pop() {
+        let mut list = grid();
+        assert_eq!(list.item_screen_rect(0, 9), None); // Past the right edge
+        assert_eq!(list.item_screen_rect(0, 99), None);
+
+        for _ in 0..6 {
+            list.handle_input(Action::Right);
+        }
+        list.settle_now();
+        list.update_model();
+        let [x, y, w, h] = list.item_screen_rect(0, 6).unwrap();
+        // Slot 6 scrolled two slots left, grown 1.2x around its center
+        let expected = [1330.0 + 150.0 - 180.0, 150.0 - 120.0, 360.0, 240.0];
+        for (got, want) in [x, y, w, h].into_iter().zip(expected) {
+            assert!((got - want).abs() <= 1.0, "{} vs {}", got, want);
+        }
+    }
+
+    #[test]
+    fn update_at_steps_by_the_time_between_timestamps() {
+        let mut list = grid();
+        assert!(list.focus(5, 0));
+        assert_eq!(list.update_at(1000.0), 1); // First call: one step
+        let after_one = list.scroll_y;
+        assert_eq!(after_one, 0.1 * list.target_scroll_y);
+
+        assert_eq!(list.update_at(1040.0), 2); // 40 ms: 6.7 ms carried
+        assert_eq!(list.update_at(1060.0), 1); // 20 + 6.7 ms
+        assert_eq!(list.update_at(900.0), 0); // Backwards: waits
+        let remaining = list.target_scroll_y - list.scroll_y;
+        assert!((remaining - 0.9f32.powi(4) * list.target_scroll_y).abs() < 0.01);
+    }
+
+    #[test]
+    fn a_long_gap_between_frames_is_clamped() {
+        let mut list = grid();
+        assert!(list.focus(5, 0));
+        list.update_at(0.0);
+        let before = list.scroll_y;
+
+        // Five seconds away (hidden tab): only max_frame_dt_ms worth of steps
+        let steps = list.update_at(5000.0);
+        assert!(steps as f64 * MODEL_STEP_MS <= list.max_frame_dt_ms);
+        let mut velocity = 0.0;
+        let mut expected = before;
+        for _ in 0..steps {
+            expected = list.animation.scroll_y.step(expected, list.target_scroll_y, &mut velocity, list.animation.snap_distance());
+        }
+        assert_eq!(list.scroll_y, expected);
+        assert!(list.scroll_y > list.target_scroll_y);
+    }
+
+    #[test]
+    fn scroll_by_moves_the_view_but_not_the_focus() {
+        let mut list = grid();
+        assert!(list.focus(3, 2));
+        let target = list.target_scroll_y;
+
+        list.scroll_by(0.0, -100.0);
+        assert_eq!(list.target_scroll_y, target - 100.0);
+        assert_eq!(list.focused_item(), Some((3, 2)));
+
+        // Clamped to the content either way
+        list.scroll_by(0.0, 1e6);
+        assert_eq!(list.target_scroll_y, 0.0);
+        list.scroll_by(0.0, -1e6);
+        assert_eq!(list.target_scroll_y, -(list.content_height() - list.viewport_height));
+        assert_eq!(list.focused_item(), Some((3, 2)));
+    }
+
+    #[test]
+    fn the_playing_badge_is_set_and_cleared_per_item() {
+        let mut list = grid();
+        assert!(list.set_playing(1, 2, true));
+        assert!(list.rows[1].items[2].playing);
+        assert_eq!(list.animating_count(), 1); // The badge keeps frames coming
+
+        assert!(list.set_playing(1, 2, false));
+        assert!(!list.rows[1].items[2].playing);
+        assert_eq!(list.animating_count(), 0);
+        assert!(!list.set_playing(99, 0, true));
+    }
+
+    #[test]
+    fn item_mut_is_bounds_checked() {
+        let mut list = grid();
+        list.item_mut(1, 2).unwrap().set_label("Episode 3");
+        assert_eq!(list.rows[1].items[2].label, "Episode 3");
+        assert!(list.rows[1].items[2].is_dirty());
+        assert!(list.item_mut(99, 99).is_none());
+        assert!(list.item_mut(1, 99).is_none());
+    }
+
+    #[test]
+    fn settled_lags_the_last_animation_by_a_few_steps() {
+        let mut list = grid();
+        list.settle_now();
+        list.handle_input(Action::Right);
+        assert!(!list.is_settled());
+
+        // The step in which everything lands counts as the first still one
+        for _ in 0..1000 {
+            list.update_model();
+            if list.at_rest() {
+                break;
+            }
+        }
+        assert!(!list.is_settled());
+        let mut extra = 0;
+        while !list.is_settled() {
+            list.update_model();
+            extra += 1;
+        }
+        assert_eq!(extra, SETTLED_STEPS - 1);
+    }
+
+    #[test]
+    fn a_self_animating_item_keeps_frames_coming_at_rest() {
+        let mut list = grid();
+        assert!(list.set_playing(0, 3, true));
+        list.settle_now();
+        for _ in 0..10 {
+            list.update_model();
+        }
+        // Nothing scrolls, yet the render loop must not skip its frames
+        assert!(list.is_settled());
+        assert_eq!(list.animating_count(), 1);
+    }
+
+    #[test]
+    fn left_off_the_top_row_exits_the_grid_but_not_off_a_middle_row() {
+        let mut list = grid();
+        let exit = list.handle_input(Action::Left);
+        assert_eq!(exit, Some(BlockedEdge::Left));
+        assert_eq!(exit.unwrap().direction(), "left");
+
+        list.handle_input(Action::Down);
+        assert_eq!(list.handle_input(Action::Left), None);
+        assert_eq!(list.focused_item(), Some((1, 0)));
+    }
+
+    #[test]
+    fn content_size_spans_every_row_and_the_widest_one() {
+        let mut list = ColumnList::from_template(3, &RowTemplate::demo());
+        for (row, height) in list.rows.iter_mut().zip([300.0, 480.0, 200.0]) {
+            for item in &mut row.items {
+                item.set_size(ITEM_WIDTH, height);
+            }
+        }
+        list.rows[1].items.truncate(4);
+        list.relayout();
+
+        let (width, height) = list.content_size();
+        assert_eq!(width, list.rows[0].content_width());
+        // 300 + 480 + 200 of posters, a gap under the first two, the margins
+        assert_eq!(list.rows[2].y, ROW_START_Y + 300.0 + 480.0 + 2.0 * ROW_GAP);
+        assert_eq!(height, ROW_START_Y + 980.0 + 2.0 * ROW_GAP + ROW_START_Y);
+    }
+
+    #[test]
+    fn a_peek_lifts_the_column_to_show_part_of_the_next_row() {
+        let mut list = grid();
+        list.set_viewport(1920.0, 1040.0);
+        list.handle_input(Action::Down);
+        assert_eq!(list.target_scroll_y, 0.0);
+
+        // 20% of the 200px row below ends 10px past the bottom edge
+        list.set_peek_next_row(0.2);
+        let next = &list.rows[2];
+        assert_eq!(next.y + 0.2 * next.height() + list.target_scroll_y, list.viewport_height);
+
+        // A spacer in between: it's the poster row after it that peeks
+        list.rows.insert(2, RowList::spacer(0.0, 100.0));
+        list.relayout();
+        let next = &list.rows[3];
+        assert_eq!(next.y + 0.2 * next.height() + list.target_scroll_y, list.viewport_height);
+        // ...and the focused row stays on screen
+        assert!(list.rows[1].y + list.target_scroll_y >= 0.0);
+    }
+
+    #[test]
+    fn settle_now_lands_every_animation_on_its_target() {
+        let mut list = grid();
+        assert!(list.set_intro(crate::intro::IntroKind::SlideUp));
+        assert!(list.focus(4, 6));
+        list.update_model();
+        assert!(list.scroll_y != list.target_scroll_y);
+
+        list.settle_now();
+        assert_eq!(list.scroll_y, list.target_scroll_y);
+        assert!(!list.intro.is_playing());
+        let row = &list.rows[4];
+        assert_eq!(row.scroll_x, row.target_scroll_x);
+        assert_eq!(row.items[6].anim_scale, crate::posteritem::SELECTED_SCALE);
+
+        // One more step has nothing left to move
+        list.update_model();
+        assert!(list.at_rest());
+    }
+
+    #[test]
+    fn a_context_restore_is_spread_over_frames() {
+        let mut list = grid();
+        assert_eq!(list.frame_load_budget(), 20);
+
+        list.context_lost();
+        assert_eq!(list.restore_progress(), 0.0);
+        assert_eq!(list.frame_load_budget(), RESTORE_BUDGET);
+        assert_eq!(RESTORE_BUDGET, 12);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::columnlist::ColumnList;
+use crate::text_texture::{render_text, TextTexture};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use web_sys::{WebGlBuffer, WebGlProgram, WebGlRenderingContext, WebGlUniformLocation};
+
+const LABEL_FONT_PX: f32 = 20.0;
+
+// DEBUG OVERLAY 🐞
+// Outlines every drawn (i.e. not culled) item's current rect and labels it
+// "row:item"; each row also gets its index at the left edge. Lives outside
+// the normal draw chain and is only created the first time it's switched on.
+pub struct DebugOverlay {
+    pub enabled: bool,
+
+    program: Rc<WebGlProgram>,
+    buffer: WebGlBuffer, // Rewritten per outline / label (debug only, speed is fine)
+    color_loc: Option<WebGlUniformLocation>,
+    textured_loc: Option<WebGlUniformLocation>,
+    labels: RefCell<HashMap<String, TextTexture>>, // Rendered once per string...
+    labels_ratio: Cell<f32>, // ...at this pixel ratio (re-rendered when it changes)
+}
+
+impl DebugOverlay {
+    // `program` comes from the ShaderRegistry (ProgramKey::Debug)
+    pub fn new(context: &WebGlRenderingContext, program: Rc<WebGlProgram>, width: f32, height: f32) -> Result<Self, String> {
+        let buffer = context.create_buffer().ok_or("Failed to create debug buffer")?;
+        context.use_program(Some(&program));
+        let resolution_loc = context.get_uniform_location(&program, "u_resolution");
+        context.uniform2f(resolution_loc.as_ref(), width, height);
+
+        Ok(Self {
+            enabled: true,
+            color_loc: context.get_uniform_location(&program, "u_color"),
+            textured_loc: context.get_uniform_location(&program, "u_textured"),
+            program,
+            buffer,
+            labels: RefCell::new(HashMap::new()),
+            labels_ratio: Cell::new(1.0),
+        })
+    }
+
+    pub fn draw(&self, context: &WebGlRenderingContext, list: &ColumnList, pixel_ratio: f32) {
+        // DPR / render scale changed: labels would be blurry (or wastefully big)
+        if self.labels_ratio.get() != pixel_ratio {
+            for (_, label) in self.labels.borrow_mut().drain() {
+                context.delete_texture(Some(&label.texture));
+            }
+            self.labels_ratio.set(pixel_ratio);
+        }
+
+        context.use_program(Some(&self.program));
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&self.buffer));
+        let pos_loc = context.get_attrib_location(&self.program, "position") as u32;
+        let tex_loc = context.get_attrib_location(&self.program, "texCoord") as u32;
+        context.vertex_attrib_pointer_with_i32(pos_loc, 2, WebGlRenderingContext::FLOAT, false, 16, 0);
+        context.vertex_attrib_pointer_with_i32(tex_loc, 2, WebGlRenderingContext::FLOAT, false, 16, 8);
+
+        let Some((first_row, last_row)) = list.visible_rows() else {
+            return;
+        };
+        for (r, row) in list.rows.iter().enumerate().skip(first_row).take(last_row + 1 - first_row) {
+            // A. Row index at the left edge
+            self.label(context, &format!("row {}", r), 4.0, row.y + list.scroll_y - LABEL_FONT_PX * 1.25);
+
+            // B. Outline + "row:item" for every drawn item
+            let Some((first, last)) = row.visible_items() else {
+                continue;
+            };
+            for (i, item) in row.items.iter().enumerate().skip(first).take(last + 1 - first) {
+                let rect = item.current_rect();
+                let color = if item.is_selected { [1.0, 0.9, 0.0, 1.0] } else { [0.0, 1.0, 0.3, 1.0] };
+                self.outline(context, rect, color);
+                self.label(context, &format!("{}:{}", r, i), rect[0] + 4.0, rect[1] + 4.0);
+            }
+        }
+    }
+
+    fn outline(&self, context: &WebGlRenderingContext, [x, y, w, h]: [f32; 4], color: [f32; 4]) {
+        let vertices: [f32; 16] = [
+            x,     y,     0.0, 0.0,
+            x + w, y,     0.0, 0.0,
+            x + w, y + h, 0.0, 0.0,
+            x,     y + h, 0.0, 0.0,
+        ];
+        self.upload(context, &vertices);
+        context.uniform4f(self.color_loc.as_ref(), color[0], color[1], color[2], color[3]);
+        context.uniform1f(self.textured_loc.as_ref(), 0.0);
+        context.draw_arrays(WebGlRenderingContext::LINE_LOOP, 0, 4);
+    }
+
+    fn label(&self, context: &WebGlRenderingContext, text: &str, x: f32, y: f32) {
+        let mut labels = self.labels.borrow_mut();
+        if !labels.contains_key(text) {
+            match render_text(context, text, LABEL_FONT_PX, self.labels_ratio.get()) {
+                Ok(label) => {
+                    labels.insert(text.to_string(), label);
+                }
+                Err(e) => {
+                    web_sys::console::error_2(&"Debug label error:".into(), &e);
+                    return;
+                }
+            }
+        }
+        let label = &labels[text];
+
+        let (x2, y2) = (x + label.width, y + label.height);
+        let vertices: [f32; 24] = [
+            x,  y,  0.0, 0.0,
+            x,  y2, 0.0, 1.0,
+            x2, y,  1.0, 0.0,
+            x2, y,  1.0, 0.0,
+            x,  y2, 0.0, 1.0,
+            x2, y2, 1.0, 1.0,
+        ];
+        self.upload(context, &vertices);
+        context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&label.texture));
+        context.uniform4f(self.color_loc.as_ref(), 1.0, 1.0, 1.0, 1.0);
+        context.uniform1f(self.textured_loc.as_ref(), 1.0);
+        context.draw_arrays(WebGlRenderingContext::TRIANGLES, 0, 6);
+    }
+
+    fn upload(&self, context: &WebGlRenderingContext, vertices: &[f32]) {
+        let vert_array = js_sys::Float32Array::from(vertices);
+        context.buffer_data_with_array_buffer_view(WebGlRenderingContext::ARRAY_BUFFER, &vert_array, WebGlRenderingContext::STREAM_DRAW);
+    }
+
+    // Same (pos, uv) vertex layout as the posters
+    pub fn get_vertex_shader() -> &'static str {
+        r#"
+            attribute vec2 position;
+            attribute vec2 texCoord;
+            uniform vec2 u_resolution;
+            varying vec2 v_texCoord;
+            void main() {
+                vec2 clipSpace = (position / u_resolution) * 2.0 - 1.0;
+                gl_Position = vec4(clipSpace.x, clipSpace.y * -1.0, 0, 1);
+                v_texCoord = texCoord;
+            }
+        "#
+    }
+
+    pub fn get_fragment_shader() -> &'static str {
+        r#"
+            precision mediump float;
+            varying vec2 v_texCoord;
+            uniform sampler2D u_texture;
+            uniform vec4 u_color;
+            uniform float u_textured;
+            void main() {
+                vec4 color = u_color;
+                if (u_textured > 0.5) {
+                    color *= texture2D(u_texture, v_texCoord);
+                }
+                gl_FragColor = color;
+            }
+        "#
+    }
+}
@@ -0,0 +1,120 @@
+use crate::columnlist::ColumnList;
+use crate::posteritem::ItemKind;
+use crate::texture_manager::TextureManager;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, HtmlCanvasElement, HtmlElement, HtmlImageElement};
+
+// DOM FALLBACK 🧱
+// For boxes where getContext("webgl") returns null: the same ColumnList model
+// drives plain absolutely-positioned <img> tags inside a clipping container.
+// Positions are percentages of the logical canvas size, so the container can
+// be stretched by CSS exactly like the canvas would have been.
+pub struct DomGrid {
+    container: HtmlElement,
+    images: Vec<Vec<HtmlImageElement>>,
+    last_rects: Vec<Vec<[f32; 4]>>, // Skip style writes for items that didn't move
+    width: f32,
+    height: f32,
+}
+
+impl DomGrid {
+    pub fn new(document: &Document, canvas: &HtmlCanvasElement) -> Result<Self, JsValue> {
+        let container = document.create_element("div")?.dyn_into::<HtmlElement>()?;
+        let style = container.style();
+        style.set_property("position", "relative")?;
+        style.set_property("overflow", "hidden")?;
+        style.set_property("width", "100vw")?;
+        style.set_property("height", "100vh")?;
+        style.set_property("background", "#1a1a1a")?;
+
+        // Take the canvas' place in the page
+        if let Some(parent) = canvas.parent_node() {
+            parent.insert_before(&container, Some(canvas))?;
+        }
+        canvas.style().set_property("display", "none")?;
+
+        Ok(Self {
+            container,
+            images: Vec::new(),
+            last_rects: Vec::new(),
+            width: canvas.width() as f32,
+            height: canvas.height() as f32,
+        })
+    }
+
+    // The element standing in for the canvas
+    pub fn element(&self) -> &HtmlElement {
+        &self.container
+    }
+
+    // On-page size of the grid in CSS pixels (0 before layout)
+    pub fn client_size(&self) -> (f32, f32) {
+        (self.container.client_width() as f32, self.container.client_height() as f32)
+    }
+
+    // 1. Build one <img> per item (again whenever the grid's shape changes)
+    fn rebuild(&mut self, list: &ColumnList, manager: &TextureManager) -> Result<(), JsValue> {
+        self.container.set_inner_html("");
+        let document = web_sys::window().and_then(|w| w.document()).ok_or("no document")?;
+
+        self.images.clear();
+        self.last_rects.clear();
+        for row in &list.rows {
+            let mut row_images = Vec::with_capacity(row.items.len());
+            for item in &row.items {
+                let img = document.create_element("img")?.dyn_into::<HtmlImageElement>()?;
+                let style = img.style();
+                style.set_property("position", "absolute")?;
+                style.set_property("object-fit", "cover")?;
+                style.set_property("object-position", item.cover_anchor.css())?;
+                if let ItemKind::SeeAll(label) = &item.kind {
+                    // No image: the alt text stands in for the label
+                    img.set_alt(&format!("{} →", label));
+                    style.set_property("background", "rgba(255, 255, 255, 0.15)")?;
+                    style.set_property("color", "white")?;
+                } else {
+                    img.set_src(&manager.resolve_url(&item.src, item.w.ceil() as u32, item.h.ceil() as u32));
+                }
+                self.container.append_child(&img)?;
+                row_images.push(img);
+            }
+            self.last_rects.push(vec![[f32::NAN; 4]; row_images.len()]);
+            self.images.push(row_images);
+        }
+        Ok(())
+    }
+
+    // Content changed without the grid's shape changing: rebuild on the next sync
+    pub fn invalidate(&mut self) {
+        self.images.clear();
+    }
+
+    // 2. Mirror the model's current rects onto the <img> tags
+    pub fn sync(&mut self, list: &ColumnList, manager: &TextureManager) -> Result<(), JsValue> {
+        let shape_changed = self.images.len() != list.rows.len()
+            || self.images.iter().zip(&list.rows).any(|(imgs, row)| imgs.len() != row.items.len());
+        if shape_changed {
+            self.rebuild(list, manager)?;
+        }
+
+        for (r, row) in list.rows.iter().enumerate() {
+            for (i, item) in row.items.iter().enumerate() {
+                let rect = item.current_rect();
+                if self.last_rects[r][i] == rect {
+                    continue;
+                }
+                self.last_rects[r][i] = rect;
+
+                let style = self.images[r][i].style();
+                style.set_property("left", &format!("{}%", rect[0] / self.width * 100.0))?;
+                style.set_property("top", &format!("{}%", rect[1] / self.height * 100.0))?;
+                style.set_property("width", &format!("{}%", rect[2] / self.width * 100.0))?;
+                style.set_property("height", &format!("{}%", rect[3] / self.height * 100.0))?;
+                style.set_property("z-index", if item.is_selected { "1" } else { "0" })?;
+                style.set_property("opacity", &item.draw_alpha().to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+// Frames averaged over (about half a second at 60fps)
+const WINDOW: usize = 30;
+
+// FRAME STATS ⏲️ Rolling average of the time between render() calls
+pub struct FrameStats {
+    samples: [f64; WINDOW],
+    count: usize, // Filled samples (< WINDOW until warmed up)
+    next: usize,
+    last_frame_at: Option<f64>,
+}
+
+impl FrameStats {
+    pub fn new() -> Self {
+        Self {
+            samples: [0.0; WINDOW],
+            count: 0,
+            next: 0,
+            last_frame_at: None,
+        }
+    }
+
+    // Call once per frame with the current time (ms)
+    pub fn record(&mut self, now_ms: f64) {
+        if let Some(last) = self.last_frame_at {
+            self.push(now_ms - last);
+        }
+        self.last_frame_at = Some(now_ms);
+    }
+
+    pub fn push(&mut self, frame_ms: f64) {
+        self.samples[self.next] = frame_ms;
+        self.next = (self.next + 1) % WINDOW;
+        self.count = (self.count + 1).min(WINDOW);
+    }
+
+    // Averaged frame time; None until a full window has been seen
+    pub fn average_ms(&self) -> Option<f64> {
+        if self.count < WINDOW {
+            return None;
+        }
+        Some(self.samples.iter().sum::<f64>() / WINDOW as f64)
+    }
+
+    // Start over (e.g. after a resolution change, old frames no longer apply)
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.next = 0;
+    }
+}
+
+// ADAPTIVE RESOLUTION 🎚️
+// Steps the render scale down while frames are slower than the target and
+// back up once there's clear headroom. The gap between the two thresholds
+// plus a full fresh window after every change keeps it from see-sawing.
+pub struct AdaptiveResolution {
+    pub target_ms: f64,
+    pub min_scale: f32,
+}
+
+const SCALE_STEP: f32 = 0.1;
+const HEADROOM: f64 = 0.7; // Only go back up below 70% of the target
+
+impl AdaptiveResolution {
+    // New render scale if it should change this frame
+    pub fn update(&self, stats: &FrameStats, scale: f32) -> Option<f32> {
+        let average = stats.average_ms()?;
+        let next = if average > self.target_ms {
+            (scale - SCALE_STEP).max(self.min_scale)
+        } else if average < self.target_ms * HEADROOM {
+            (scale + SCALE_STEP).min(1.0)
+        } else {
+            scale
+        };
+        (next != scale).then_some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(frame_ms: f64) -> FrameStats {
+        let mut stats = FrameStats::new();
+        for _ in 0..WINDOW {
+            stats.push(frame_ms);
+        }
+        stats
+    }
+
+    #[test]
+    fn slow_frames_lower_the_scale_and_fast_ones_raise_it() {
+        let adaptive = AdaptiveResolution { target_ms: 20.0, min_scale: 0.5 };
+        assert_eq!(adaptive.update(&FrameStats::new(), 1.0), None); // Not warmed up
+        let down = adaptive.update(&window(30.0), 1.0).unwrap();
+        assert!(down < 1.0);
+        assert_eq!(adaptive.update(&window(30.0), 0.5), None); // Floor
+        assert!(adaptive.update(&window(8.0), down).unwrap() > down);
+        // Between 70% and 100% of the target: hold (hysteresis)
+        assert_eq!(adaptive.update(&window(17.0), down), None);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use web_sys::{WebGlBuffer, WebGlRenderingContext};
+
+// Quad geometry is position independent (centered w x h + uv, placed by
+// u_offset), so it's fully described by these: w, h, u0, v0, u1, v1 as bits
+pub type GeometryKey = [u32; 6];
+
+pub fn geometry_key(w: f32, h: f32, uv: [f32; 4]) -> GeometryKey {
+    [w.to_bits(), h.to_bits(), uv[0].to_bits(), uv[1].to_bits(), uv[2].to_bits(), uv[3].to_bits()]
+}
+
+// What replace() does with an item whose geometry changed
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GeometryChange {
+    Share,          // Another item already has the new geometry: use its buffer
+    RewriteInPlace, // Sole user: buffer_sub_data into the same buffer, no reallocation
+    Allocate,       // Still shared with others: a new buffer_data buffer
+}
+
+// SHARED QUADS ♻️
+// One vertex buffer per distinct geometry. A grid of same-sized posters
+// (100 rows of 2:3 cards...) ends up with a single buffer. Items hold an Rc;
+// the cache's own Rc is the only one left once nobody uses a buffer, and
+// sweep() deletes those (same idea as TextureManager::clear).
+pub struct GeometryCache {
+    buffers: HashMap<GeometryKey, Rc<WebGlBuffer>>,
+}
+
+impl GeometryCache {
+    pub fn new() -> Self {
+        Self {
+            buffers: HashMap::new(),
+        }
+    }
+
+    // The buffer holding `vertices` (built the first time this key is seen)
+    pub fn get(&mut self, context: &WebGlRenderingContext, key: GeometryKey, vertices: &[f32]) -> Result<Rc<WebGlBuffer>, String> {
+        if let Some(buffer) = self.buffers.get(&key) {
+            return Ok(buffer.clone());
+        }
+        let buffer = Rc::new(context.create_buffer().ok_or("Failed to create buffer")?);
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&buffer));
+        let vert_array = js_sys::Float32Array::from(vertices);
+        // DYNAMIC: a sole owner that changes size rewrites it in place (see replace)
+        context.buffer_data_with_array_buffer_view(WebGlRenderingContext::ARRAY_BUFFER, &vert_array, WebGlRenderingContext::DYNAMIC_DRAW);
+        self.buffers.insert(key, buffer.clone());
+        Ok(buffer)
+    }
+
+    // An item's geometry changed from `old_key` to `new_key`. Shares an
+    // existing buffer if there is one; if the item was the buffer's only user
+    // its vertices are rewritten in place; otherwise it gets a new buffer.
+    pub fn replace(
+        &mut self,
+        context: &WebGlRenderingContext,
+        old: Rc<WebGlBuffer>,
+        old_key: GeometryKey,
+        new_key: GeometryKey,
+        vertices: &[f32],
+    ) -> Result<Rc<WebGlBuffer>, String> {
+        match self.plan(&old, old_key, new_key) {
+            GeometryChange::Share | GeometryChange::Allocate => {
+                drop(old); // sweep() deletes it if that was the last user
+                return self.get(context, new_key, vertices);
+            }
+            GeometryChange::RewriteInPlace => {}
+        }
+        self.buffers.remove(&old_key);
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&old));
+        let vert_array = js_sys::Float32Array::from(vertices);
+        context.buffer_sub_data_with_i32_and_array_buffer_view(WebGlRenderingContext::ARRAY_BUFFER, 0, &vert_array);
+        self.buffers.insert(new_key, old.clone());
+        Ok(old)
+    }
+
+    // The replace() decision for `old` (held by the caller) going from
+    // `old_key` to `new_key`
+    pub fn plan(&self, old: &Rc<WebGlBuffer>, old_key: GeometryKey, new_key: GeometryKey) -> GeometryChange {
+        if self.buffers.contains_key(&new_key) {
+            return GeometryChange::Share;
+        }
+        let sole_user = Rc::strong_count(old) == 2 && self.buffers.get(&old_key).is_some_and(|b| Rc::ptr_eq(b, old));
+        if sole_user {
+            GeometryChange::RewriteInPlace
+        } else {
+            GeometryChange::Allocate
+        }
+    }
+
+    // Delete every buffer no item holds any more; returns how many went
+    pub fn sweep(&mut self, context: &WebGlRenderingContext) -> usize {
+        let before = self.buffers.len();
+        self.buffers.retain(|_, buffer| {
+            if Rc::strong_count(buffer) > 1 {
+                return true;
+            }
+            context.delete_buffer(Some(buffer));
+            false
+        });
+        before - self.buffers.len()
+    }
+
+    // Distinct geometries currently on the GPU
+    pub fn buffer_count(&self) -> usize {
+        self.buffers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen::{JsCast, JsValue};
+
+    // Stand-in buffer: never handed to GL, only compared by Rc
+    fn fake_buffer() -> Rc<WebGlBuffer> {
+        Rc::new(JsValue::UNDEFINED.unchecked_into())
+    }
+
+    #[test]
+    fn a_sole_user_changing_size_rewrites_its_buffer_in_place() {
+        let mut cache = GeometryCache::new();
+        let (small, big) = (geometry_key(300.0, 200.0, [0.0, 0.0, 1.0, 1.0]), geometry_key(360.0, 240.0, [0.0, 0.0, 1.0, 1.0]));
+        let held = fake_buffer();
+        cache.buffers.insert(small, held.clone());
+        assert_eq!(cache.plan(&held, small, big), GeometryChange::RewriteInPlace);
+
+        // A second poster on the same buffer: the resized one needs its own
+        let other = held.clone();
+        assert_eq!(cache.plan(&held, small, big), GeometryChange::Allocate);
+        drop(other);
+
+        // Some other poster already has the new size: share it
+        cache.buffers.insert(big, fake_buffer());
+        assert_eq!(cache.plan(&held, small, big), GeometryChange::Share);
+    }
+}
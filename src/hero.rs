@@ -0,0 +1,123 @@
+use crate::columnlist::ColumnList;
+use crate::texture_manager::TextureManager;
+use wasm_bindgen::JsValue;
+use web_sys::WebGlRenderingContext;
+
+// HERO ON FOCUS 🦸 A poster that keeps focus for `dwell_ms` grows into a
+// bigger card (HERO_SCALE) and, if the provider gives a URL for it, swaps to
+// wide hero art. Moving focus away shrinks it back and drops the art.
+pub struct HeroFocus {
+    pub enabled: bool,
+    pub dwell_ms: f64,
+    pub provider: Option<js_sys::Function>, // (src, id) -> hero art URL (or nothing)
+
+    focused: Option<(usize, usize)>,
+    since_ms: f64,
+    shown: bool,
+}
+
+impl HeroFocus {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            dwell_ms: 1000.0,
+            provider: None,
+            focused: None,
+            since_ms: 0.0,
+            shown: false,
+        }
+    }
+
+    // Once per frame. `gl` is needed to fetch hero art (DOM mode only scales).
+    pub fn update(
+        &mut self,
+        list: &mut ColumnList,
+        now_ms: f64,
+        gl: Option<(&WebGlRenderingContext, &mut TextureManager)>,
+    ) {
+        // 1. Focus moved (or the feature was switched off): collapse the old hero
+        let focused = if self.enabled { list.focused_item() } else { None };
+        if focused != self.focused {
+            self.collapse(list);
+            self.focused = focused;
+            self.since_ms = now_ms;
+        }
+
+        // 2. Dwelled long enough: expand
+        let Some((row, index)) = self.focused else {
+            return;
+        };
+        if self.shown || now_ms - self.since_ms < self.dwell_ms {
+            return;
+        }
+        self.shown = true;
+        let item = &mut list.rows[row].items[index];
+        let art = match (self.hero_src(&item.src, &item.id), gl) {
+            (Some(src), Some((context, manager))) => match manager.get_texture(context, &src, item.w.ceil() as u32, item.h.ceil() as u32) {
+                Ok(shared) => Some((shared.texture, shared.image)),
+                Err(e) => {
+                    web_sys::console::error_2(&"Hero art failed:".into(), &e);
+                    None
+                }
+            },
+            _ => None,
+        };
+        item.set_hero(art, true);
+    }
+
+    // Back to a normal poster (no-op if nothing is expanded)
+    pub fn collapse(&mut self, list: &mut ColumnList) {
+        // Whole row, not just the stored index: a removal may have shifted it
+        if let (Some((row, _)), true) = (self.focused.take(), self.shown) {
+            if let Some(row) = list.rows.get_mut(row) {
+                for item in row.items.iter_mut().filter(|item| item.hero) {
+                    item.set_hero(None, false);
+                }
+            }
+        }
+        self.shown = false;
+    }
+
+    fn hero_src(&self, src: &str, id: &str) -> Option<String> {
+        let provider = self.provider.as_ref()?;
+        match provider.call2(&JsValue::NULL, &src.into(), &id.into()) {
+            Ok(url) => url.as_string().filter(|url| !url.is_empty()),
+            Err(e) => {
+                web_sys::console::error_2(&"hero_src_provider failed:".into(), &e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::posteritem::HERO_SCALE;
+
+    #[test]
+    fn a_poster_grows_into_the_hero_after_the_dwell() {
+        let mut hero = HeroFocus::new();
+        hero.enabled = true;
+        hero.dwell_ms = 1000.0;
+        let mut list = ColumnList::new();
+        list.settle_now();
+
+        hero.update(&mut list, 0.0, None);
+        hero.update(&mut list, 900.0, None);
+        assert!(!list.rows[0].items[0].hero);
+
+        hero.update(&mut list, 1000.0, None);
+        assert!(list.rows[0].items[0].hero);
+        let before = list.rows[0].items[0].anim_scale;
+        list.update_model();
+        assert!(list.rows[0].items[0].anim_scale > before);
+        list.settle_now();
+        assert_eq!(list.rows[0].items[0].anim_scale, HERO_SCALE);
+
+        // Focus moves on: back to a normal poster
+        list.handle_input(crate::keymap::Action::Right);
+        hero.update(&mut list, 1100.0, None);
+        assert!(!list.rows[0].items[0].hero);
+    }
+}
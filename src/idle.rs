@@ -0,0 +1,67 @@
+use crate::scroll_watch::fire;
+
+// IDLE WATCH 💤
+// No key / pointer input for `timeout_ms` -> on_idle (screensaver, trailer
+// autoplay...), then on_active with the first input after that. Fires once
+// per idle stretch, checked every frame.
+pub struct IdleWatch {
+    pub timeout_ms: f64, // 0 = off
+    pub on_idle: Option<js_sys::Function>,
+    pub on_active: Option<js_sys::Function>,
+
+    last_input_ms: Option<f64>, // None until the first frame starts the timer
+    idle: bool,
+}
+
+impl IdleWatch {
+    pub fn new() -> Self {
+        Self {
+            timeout_ms: 0.0,
+            on_idle: None,
+            on_active: None,
+            last_input_ms: None,
+            idle: false,
+        }
+    }
+
+    // Any user input: restart the timer (and wake up)
+    pub fn input(&mut self, now_ms: f64) {
+        self.last_input_ms = Some(now_ms);
+        if self.idle {
+            self.idle = false;
+            fire(&self.on_active, "on_active");
+        }
+    }
+
+    pub fn update(&mut self, now_ms: f64) {
+        let last = *self.last_input_ms.get_or_insert(now_ms);
+        if !self.idle && self.timeout_ms > 0.0 && now_ms - last >= self.timeout_ms {
+            self.idle = true;
+            fire(&self.on_idle, "on_idle");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goes_idle_after_the_timeout_and_again_after_input() {
+        let mut watch = IdleWatch::new();
+        watch.timeout_ms = 500.0;
+        watch.update(0.0);
+        watch.update(499.0);
+        assert!(!watch.idle);
+        watch.update(500.0);
+        assert!(watch.idle);
+
+        // A key wakes it up and restarts the timer
+        watch.input(600.0);
+        assert!(!watch.idle);
+        watch.update(1000.0);
+        assert!(!watch.idle);
+        watch.update(1100.0);
+        assert!(watch.idle);
+    }
+}
@@ -0,0 +1,57 @@
+use web_sys::HtmlElement;
+
+// Where the latest input came from
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InputSource {
+    Keys,    // Keyboard / remote (send_key)
+    Pointer, // Mouse / touch (pointer_*)
+}
+
+impl InputSource {
+    pub fn name(self) -> &'static str {
+        match self {
+            InputSource::Keys => "keys",
+            InputSource::Pointer => "pointer",
+        }
+    }
+}
+
+// INPUT SOURCE 🎮 Remembers keys vs pointer. With auto_hide_cursor the mouse
+// cursor disappears over the grid while the remote is in use (10-foot UI)
+// and comes back as soon as the pointer moves.
+pub struct InputSourceWatch {
+    pub last: Option<InputSource>, // None until the first input
+    auto_hide_cursor: bool,
+    target: HtmlElement, // Whatever shows the grid (canvas, or the DOM fallback's container)
+}
+
+impl InputSourceWatch {
+    pub fn new(target: HtmlElement) -> Self {
+        Self {
+            last: None,
+            auto_hide_cursor: false,
+            target,
+        }
+    }
+
+    pub fn record(&mut self, source: InputSource) {
+        if self.last != Some(source) {
+            self.last = Some(source);
+            self.apply_cursor();
+        }
+    }
+
+    pub fn set_auto_hide_cursor(&mut self, enabled: bool) {
+        self.auto_hide_cursor = enabled;
+        self.apply_cursor();
+    }
+
+    fn apply_cursor(&self) {
+        let hidden = self.auto_hide_cursor && self.last == Some(InputSource::Keys);
+        let style = self.target.style();
+        let result = if hidden { style.set_property("cursor", "none") } else { style.remove_property("cursor").map(|_| ()) };
+        if let Err(e) = result {
+            web_sys::console::error_2(&"cursor style failed:".into(), &e);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::animation::AnimationConfig;
+
+// Where a SlideUp starts: this fraction of the viewport height below rest
+const SLIDE_FRACTION: f32 = 0.3;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IntroKind {
+    None,
+    FadeIn,  // Whole grid from transparent
+    SlideUp, // Whole grid rises into place from below
+}
+
+impl IntroKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(IntroKind::None),
+            "fade_in" => Some(IntroKind::FadeIn),
+            "slide_up" => Some(IntroKind::SlideUp),
+            _ => None,
+        }
+    }
+}
+
+// INTRO 🎬 Played once, on the first frames after construction: the whole
+// grid gets a vertical offset and/or alpha that eases back to rest with the
+// scroll_y / dim curves. Keys are held back until it's over.
+pub struct Intro {
+    pub offset_y: f32, // Added to every row's offset (pixels, + = lower)
+    pub alpha: f32,    // Multiplies everything the grid draws
+    velocity: [f32; 2],
+    started: bool, // A step ran: too late to set one up
+    pub dirty: bool, // Moved/faded since the last drawn frame
+}
+
+impl Intro {
+    pub fn new() -> Self {
+        Self {
+            offset_y: 0.0,
+            alpha: 1.0,
+            velocity: [0.0, 0.0],
+            started: false,
+            dirty: false,
+        }
+    }
+
+    // Set up the starting pose. Only before the first step (false after
+    // that: the intro is for first show, it never replays)
+    pub fn set(&mut self, kind: IntroKind, viewport_height: f32) -> bool {
+        if self.started {
+            return false;
+        }
+        (self.offset_y, self.alpha) = match kind {
+            IntroKind::None => (0.0, 1.0),
+            IntroKind::FadeIn => (0.0, 0.0),
+            IntroKind::SlideUp => (viewport_height * SLIDE_FRACTION, 1.0),
+        };
+        self.velocity = [0.0, 0.0];
+        self.dirty = true;
+        true
+    }
+
+    pub fn step(&mut self, anim: &AnimationConfig) {
+        self.started = true;
+        if !self.is_playing() {
+            return;
+        }
+        let [offset_velocity, alpha_velocity] = &mut self.velocity;
+        self.offset_y = anim.scroll_y.step(self.offset_y, 0.0, offset_velocity, anim.snap_distance());
+        self.alpha = anim.dim.step(self.alpha, 1.0, alpha_velocity, 0.001).clamp(0.0, 1.0);
+        self.dirty = true; // Including the step that lands at rest
+    }
+
+    // Straight to rest (a static render shouldn't catch it halfway)
+    pub fn finish(&mut self) {
+        self.started = true;
+        (self.offset_y, self.alpha) = (0.0, 1.0);
+        self.velocity = [0.0, 0.0];
+        self.dirty = true;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.offset_y != 0.0 || self.alpha != 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slide_up_rises_from_below_to_rest() {
+        let anim = AnimationConfig::new();
+        let mut intro = Intro::new();
+        assert!(intro.set(IntroKind::SlideUp, 1080.0));
+        let mut previous = intro.offset_y;
+        assert!(previous > 0.0);
+
+        for _ in 0..1000 {
+            intro.step(&anim);
+            assert!(intro.offset_y < previous || intro.offset_y == 0.0);
+            previous = intro.offset_y;
+            if !intro.is_playing() {
+                break;
+            }
+        }
+        assert_eq!(intro.offset_y, 0.0);
+        assert!(!intro.set(IntroKind::SlideUp, 1080.0)); // First show only
+    }
+}
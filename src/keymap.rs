@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+// Everything a key can do in the grid
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Select, // Fires the activate callback on the focused poster
+}
+
+impl Action {
+    // Names used by the JS config object
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "left" => Action::Left,
+            "right" => Action::Right,
+            "up" => Action::Up,
+            "down" => Action::Down,
+            "page_up" => Action::PageUp,
+            "page_down" => Action::PageDown,
+            "home" => Action::Home,
+            "end" => Action::End,
+            "select" => Action::Select,
+            _ => return None,
+        })
+    }
+
+    // ...and back (event payloads)
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Home => "home",
+            Action::End => "end",
+            Action::Select => "select",
+        }
+    }
+
+    // Which way it moves focus, None for the rest
+    pub fn axis(self) -> Option<Axis> {
+        match self {
+            Action::Left | Action::Right => Some(Axis::Horizontal),
+            Action::Up | Action::Down | Action::PageUp | Action::PageDown => Some(Axis::Vertical),
+            _ => None,
+        }
+    }
+}
+
+// Horizontal or vertical move (everything else has no axis)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+// KEYMAP ⌨️ key code -> action. Defaults are the usual TV remote / keyboard
+// codes; a JS config can rebind any action.
+pub struct KeyMap {
+    bindings: HashMap<u32, Action>,
+}
+
+impl KeyMap {
+    pub fn new() -> Self {
+        let mut map = Self {
+            bindings: HashMap::new(),
+        };
+        map.bind(Action::Left, &[37]);
+        map.bind(Action::Right, &[39]);
+        map.bind(Action::Up, &[38]);
+        map.bind(Action::Down, &[40]);
+        map.bind(Action::PageUp, &[33]);
+        map.bind(Action::PageDown, &[34]);
+        map.bind(Action::Home, &[36]);
+        map.bind(Action::End, &[35]);
+        map.bind(Action::Select, &[13]);
+        map
+    }
+
+    // Replace every key of `action` with `codes`
+    pub fn bind(&mut self, action: Action, codes: &[u32]) {
+        self.bindings.retain(|_, a| *a != action);
+        for &code in codes {
+            self.bindings.insert(code, action);
+        }
+    }
+
+    pub fn action_for(&self, key_code: u32) -> Option<Action> {
+        self.bindings.get(&key_code).copied()
+    }
+
+    // Merge `{ left: [37], select: [13, 32], ... }` over the current bindings.
+    // Unknown action names are skipped with a warning.
+    pub fn apply_js(&mut self, config: &JsValue) -> Result<(), JsValue> {
+        if config.is_undefined() || config.is_null() {
+            return Ok(());
+        }
+        let config: &js_sys::Object = config.dyn_ref().ok_or("keymap must be an object")?;
+        for entry in js_sys::Object::entries(config).iter() {
+            let entry: js_sys::Array = entry.unchecked_into();
+            let name = entry.get(0).as_string().unwrap_or_default();
+            let Some(action) = Action::from_name(&name) else {
+                web_sys::console::warn_1(&format!("keymap: unknown action \"{}\" ignored", name).into());
+                continue;
+            };
+            let codes = entry
+                .get(1)
+                .dyn_into::<js_sys::Array>()
+                .map_err(|_| format!("keymap.{} must be an array of key codes", name))?
+                .iter()
+                .map(|code| code.as_f64().map(|c| c as u32))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| format!("keymap.{} must be an array of key codes", name))?;
+            self.bind(action, &codes);
+        }
+        Ok(())
+    }
+}
+
+// REPEAT GUARD 🐢 Some TV browsers fire keydown repeats so fast that one
+// press skips several posters. The same key again within `min_interval_ms`
+// is dropped (0 = off); a different key always goes through.
+pub struct RepeatGuard {
+    pub min_interval_ms: f64,
+    last: Option<(u32, f64)>, // (key code, when it was accepted)
+}
+
+impl RepeatGuard {
+    pub fn new() -> Self {
+        Self {
+            min_interval_ms: 0.0,
+            last: None,
+        }
+    }
+
+    // true = handle this key press
+    pub fn accept(&mut self, key_code: u32, now_ms: f64) -> bool {
+        if let Some((last_code, at)) = self.last {
+            if last_code == key_code && now_ms - at < self.min_interval_ms {
+                return false;
+            }
+        }
+        self.last = Some((key_code, now_ms));
+        true
+    }
+}
+
+// MIXED AXIS INPUT 🧭 Remotes can send Down + Right in one burst (diagonal
+// rockers, mashed keys). With a priority axis set, keys are queued and each
+// frame moves along one axis only: the priority axis if it has anything
+// queued, otherwise the other one. The rest waits for the next frame.
+pub struct InputQueue {
+    pub priority: Option<Axis>, // None = apply every key immediately (default)
+    queue: Vec<Action>,
+}
+
+impl InputQueue {
+    pub fn new() -> Self {
+        Self {
+            priority: None,
+            queue: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, action: Action) {
+        self.queue.push(action);
+    }
+
+    // This frame's actions, in the order to apply them. Non-move actions
+    // (Select, Home/End) keep their place: they wait if a move before them waits.
+    pub fn drain_frame(&mut self) -> Vec<Action> {
+        let Some(priority) = self.priority else {
+            return std::mem::take(&mut self.queue);
+        };
+        let axis = if self.queue.iter().any(|a| a.axis() == Some(priority)) {
+            Some(priority)
+        } else {
+            self.queue.iter().find_map(|a| a.axis())
+        };
+
+        let mut now = Vec::new();
+        let mut later = Vec::new();
+        for action in self.queue.drain(..) {
+            let runs = match action.axis() {
+                Some(a) => Some(a) == axis,
+                None => later.is_empty(),
+            };
+            if runs {
+                now.push(action);
+            } else {
+                later.push(action);
+            }
+        }
+        self.queue = later;
+        now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_keys_queue_on_the_vertical_axis() {
+        let mut queue = InputQueue::new();
+        queue.priority = Some(Axis::Horizontal);
+        queue.push(Action::PageDown);
+        queue.push(Action::Right);
+        assert_eq!(queue.drain_frame(), [Action::Right]);
+        assert_eq!(queue.drain_frame(), [Action::PageDown]);
+    }
+
+    #[test]
+    fn binding_select_to_two_codes_makes_both_activate() {
+        let mut map = KeyMap::new();
+        map.bind(Action::Select, &[13, 32]);
+        assert_eq!(map.action_for(13), Some(Action::Select));
+        assert_eq!(map.action_for(32), Some(Action::Select));
+        map.bind(Action::Select, &[32]); // Replaces, doesn't add
+        assert_eq!(map.action_for(13), None);
+        assert_eq!(map.action_for(37), Some(Action::Left)); // Defaults kept
+        assert_eq!(Action::from_name("jump"), None);
+    }
+
+    #[test]
+    fn a_repeat_inside_the_min_interval_is_dropped() {
+        let mut guard = RepeatGuard::new();
+        guard.min_interval_ms = 120.0;
+        assert!(guard.accept(39, 0.0));
+        assert!(!guard.accept(39, 50.0));
+        assert!(guard.accept(37, 60.0)); // A different key always goes through
+        assert!(guard.accept(39, 200.0));
+    }
+
+    #[test]
+    fn a_diagonal_burst_applies_vertical_first_then_horizontal() {
+        let mut queue = InputQueue::new();
+        queue.priority = Some(Axis::Vertical);
+        queue.push(Action::Right);
+        queue.push(Action::Down);
+        assert_eq!(queue.drain_frame(), [Action::Down]);
+        assert_eq!(queue.drain_frame(), [Action::Right]);
+        assert!(queue.drain_frame().is_empty());
+    }
+}
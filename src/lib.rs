@@ -1,56 +1,1866 @@
 // src/lib.rs
-use std::rc::Rc;
-use std::cell::RefCell;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{WebGlRenderingContext, HtmlCanvasElement};
+use std::cell::Cell;
+use std::rc::Rc;
 
 mod posteritem;
 mod rowlist;
 mod columnlist;
 mod texture_manager;
+mod render_target;
+mod blur;
+mod shader_registry;
+mod dom_grid;
+mod clock;
+mod animation;
+mod painter;
+mod shadow;
+mod spotlight;
+mod scrim;
+mod text_texture;
+mod debug_overlay;
+mod scroll_watch;
+mod idle;
+mod state;
+mod frame_stats;
+mod keymap;
+mod hero;
+mod menulist;
+mod geometry_cache;
+mod nav_sounds;
+mod input_source;
+mod intro;
 
-use crate::posteritem::PosterItem;
-use crate::columnlist::ColumnList;
-use crate::texture_manager::TextureManager;
+use crate::columnlist::{BlockedEdge, ColumnList, LastPageBehavior};
+use crate::intro::IntroKind;
+use crate::posteritem::{CoverAnchor, ItemKind, PosterItem, PosterUniforms};
+use crate::rowlist::{SelectionMode, ITEM_HEIGHT, ITEM_WIDTH};
+use crate::texture_manager::{TextureFormat, TextureManager};
+use crate::blur::BlurPass;
+use crate::shader_registry::{ProgramKey, ShaderError, ShaderRegistry, ShaderStage};
+use crate::dom_grid::DomGrid;
+use crate::painter::Painter;
+use crate::shadow::ShadowPass;
+use crate::spotlight::SpotlightPass;
+use crate::scrim::ScrimPass;
+use crate::debug_overlay::DebugOverlay;
+use crate::animation::Easing;
+use crate::scroll_watch::ScrollWatch;
+use crate::state::GridState;
+use crate::frame_stats::{AdaptiveResolution, FrameStats};
+use crate::hero::HeroFocus;
+use crate::menulist::MenuList;
+use crate::idle::IdleWatch;
+use crate::nav_sounds::NavSounds;
+use crate::input_source::{InputSource, InputSourceWatch};
+use crate::keymap::{Action, Axis, InputQueue, KeyMap, RepeatGuard};
 
 
+#[allow(unused_macros)]
 macro_rules! log {
     ($($t:tt)*) => (web_sys::console::log_1(&format!($($t)*).into()))
 }
 // 1. Define the Engine Struct (Exported to JS)
 #[wasm_bindgen]
 pub struct GameEngine {
-    context: WebGlRenderingContext,
+    gl: Option<GlState>,       // None = WebGL unavailable on this device
+    dom_grid: Option<DomGrid>, // Fallback renderer, only when gl is None
+    width: u32,
+    height: u32,
     root_list: ColumnList,
     texture_manager: TextureManager,
+    blur_freeze: bool,
+    scroll_watch: ScrollWatch, // Scroll start/end callbacks
+    frame_stats: FrameStats,
+    adaptive: Option<AdaptiveResolution>, // None = render scale only changes when asked
+    keymap: KeyMap,
+    repeat_guard: RepeatGuard,
+    input_queue: InputQueue, // Only holds keys when an axis priority is set
+    idle_watch: IdleWatch,
+    nav_sounds: NavSounds,
+    input_source: InputSourceWatch,
+    menu: Option<MenuList>, // Shown instead of the grid (takes the keys too) while Some
+    on_activate: Option<js_sys::Function>, // ({ row, item, id, row_id }) when Select is pressed
+    on_escape_top: Option<js_sys::Function>,
+    on_escape_bottom: Option<js_sys::Function>,
+    on_focus_exit: Option<js_sys::Function>, // ("up" | "down" | "left" | "right") off the grid's outer edge
+    hero: HeroFocus,
+    focus_wait: Option<js_sys::Function>, // resolve() of the latest focus_and_wait promise
+    dirty_rendering: bool, // Skip GL frames in which nothing changed
+    force_redraw: bool,      // Draw the next frame even if no item is dirty
+    always_render: bool,     // Dirty rendering never skips (video behind the grid, JS animations...)
+    frame_time: Option<f64>, // Set during render / render_at: this frame's timestamp
+}
+
+// Everything that only exists when we actually got a WebGL context
+struct GlState {
+    context: WebGlRenderingContext,
+    canvas: HtmlCanvasElement, // Its width/height = drawing buffer (native_size * render_scale)
+    native_size: (u32, u32),   // Full-quality buffer: logical size, or CSS size * DPR after set_display_size
+    render_scale: f32,
+    shaders: ShaderRegistry,
+    poster_uniforms: PosterUniforms,
+    smooth_edges: bool, // OES_standard_derivatives available (anti-aliased corners)
+    blur: Option<BlurPass>, // Created on first use (most screens never blur)
+    shadow: Option<ShadowPass>, // Created once something is elevated
+    spotlight: Option<SpotlightPass>, // Created by the first set_spotlight(true, ..)
+    scrim: Option<ScrimPass>,         // Created by the first set_scrim with a height
+    debug: Option<DebugOverlay>,      // Created the first time the overlay is turned on
+    viewport_rect: Option<[f32; 4]>,  // Grid drawn into this part of the canvas (layout px), None = all of it
+    color_adjust: [f32; 3],           // Brightness, contrast, gamma (re-applied after a context restore)
+    context_watch: ContextWatch,
+}
+
+// CONTEXT LOSS 💥 Set by the canvas' webglcontextlost / webglcontextrestored
+// events, picked up by the next render()
+struct ContextWatch {
+    lost: Rc<Cell<bool>>,     // Nothing can be drawn until restored
+    restored: Rc<Cell<bool>>, // Came back: rebuild everything once
+}
+
+impl ContextWatch {
+    fn listen(canvas: &HtmlCanvasElement) -> Result<Self, JsValue> {
+        let lost = Rc::new(Cell::new(false));
+        let restored = Rc::new(Cell::new(false));
+
+        // preventDefault = "we'll handle it": without it the browser never restores
+        let lost_clone = lost.clone();
+        let on_lost = Closure::wrap(Box::new(move |event: web_sys::Event| {
+            event.prevent_default();
+            lost_clone.set(true);
+        }) as Box<dyn FnMut(web_sys::Event)>);
+        let (lost_clone, restored_clone) = (lost.clone(), restored.clone());
+        let on_restored = Closure::wrap(Box::new(move || {
+            lost_clone.set(false);
+            restored_clone.set(true);
+        }) as Box<dyn FnMut()>);
+
+        canvas.add_event_listener_with_callback("webglcontextlost", on_lost.as_ref().unchecked_ref())?;
+        canvas.add_event_listener_with_callback("webglcontextrestored", on_restored.as_ref().unchecked_ref())?;
+        on_lost.forget();
+        on_restored.forget();
+        Ok(Self { lost, restored })
+    }
+}
+
+// Where on the canvas the grid goes this frame, in drawing-buffer pixels
+#[derive(PartialEq, Debug)]
+struct GridArea {
+    viewport: [i32; 4],
+    scissor: Option<[i32; 4]>, // Only with a viewport rect: clips drawing (and clears) to it
+}
+
+impl GridArea {
+    fn apply(&self, context: &WebGlRenderingContext) {
+        let [x, y, w, h] = self.viewport;
+        context.viewport(x, y, w, h);
+        match self.scissor {
+            Some([x, y, w, h]) => {
+                context.enable(WebGlRenderingContext::SCISSOR_TEST);
+                context.scissor(x, y, w, h);
+            }
+            None => context.disable(WebGlRenderingContext::SCISSOR_TEST),
+        }
+    }
 }
 
 #[wasm_bindgen]
 impl GameEngine {
-    // 2. The Constructor (Called from JS: "new GameEngine()")
+    // 2. The Constructor (Called from JS: "new GameEngine(id, config?)")
+    // config (optional): { keymap: { left: [37], select: [13, 32], ... } }
     #[wasm_bindgen(constructor)]
-    pub fn new(canvas_id: &str) -> Result<GameEngine, JsValue> {
-        // A. Setup WebGL
+    pub fn new(canvas_id: &str, config: JsValue) -> Result<GameEngine, JsValue> {
+        // A. Find the canvas
         let window = web_sys::window().expect("no global `window` exists");
         let document = window.document().expect("should have a document on window");
         let canvas = document.get_element_by_id(canvas_id).expect("no canvas");
         let canvas: HtmlCanvasElement = canvas.dyn_into::<HtmlCanvasElement>()?;
-        
+
+        // B. Create The Game State
+        let mut keymap = KeyMap::new();
+        if !config.is_undefined() && !config.is_null() {
+            keymap.apply_js(&js_sys::Reflect::get(&config, &"keymap".into())?)?;
+        }
+        let mut texture_manager = TextureManager::new();
+        let mut root_list = ColumnList::new();
+        root_list.set_viewport(canvas.width() as f32, canvas.height() as f32);
+        root_list.animation.device_pixel_ratio = window.device_pixel_ratio() as f32;
+
+        // C. Pick a backend: WebGL if we can get it, plain DOM otherwise
         let context = canvas
-            .get_context("webgl")?
-            .unwrap()
-            .dyn_into::<WebGlRenderingContext>()?;
-
-        // B. Setup Shaders (Helper functions are at the bottom)
-        let vert_shader = compile_shader(&context, WebGlRenderingContext::VERTEX_SHADER, PosterItem::get_vertex_shader())?;
-        let frag_shader = compile_shader(&context, WebGlRenderingContext::FRAGMENT_SHADER, PosterItem::get_fragment_shader())?;
-        let program = link_program(&context, &vert_shader, &frag_shader)?;
+            .get_context("webgl")
+            .ok()
+            .flatten()
+            .and_then(|ctx| ctx.dyn_into::<WebGlRenderingContext>().ok());
+
+        let (gl, dom_grid) = match context {
+            Some(context) => {
+                let gl = GlState::new(context, &canvas)?;
+                // Load Assets
+                root_list.load_assets(&gl.context, &mut texture_manager)?;
+                (Some(gl), None)
+            }
+            None => {
+                web_sys::console::warn_1(&"WebGL unavailable, falling back to DOM grid".into());
+                (None, Some(DomGrid::new(&document, &canvas)?))
+            }
+        };
+
+        let grid_element: web_sys::HtmlElement = match &dom_grid {
+            Some(dom_grid) => dom_grid.element().clone(),
+            None => canvas.clone().into(),
+        };
+
+        // Return the Struct to JS
+        Ok(GameEngine {
+            gl,
+            dom_grid,
+            width: canvas.width(),
+            height: canvas.height(),
+            root_list,
+            texture_manager,
+            blur_freeze: false,
+            scroll_watch: ScrollWatch::new(),
+            frame_stats: FrameStats::new(),
+            adaptive: None,
+            keymap,
+            repeat_guard: RepeatGuard::new(),
+            input_queue: InputQueue::new(),
+            idle_watch: IdleWatch::new(),
+            nav_sounds: NavSounds::new(),
+            input_source: InputSourceWatch::new(grid_element),
+            menu: None,
+            on_activate: None,
+            on_escape_top: None,
+            on_escape_bottom: None,
+            on_focus_exit: None,
+            hero: HeroFocus::new(),
+            focus_wait: None,
+            dirty_rendering: false,
+            force_redraw: true,
+            always_render: false,
+            frame_time: None,
+        })
+    }
+
+    // Which renderer is active: "webgl" or "dom"
+    pub fn render_backend(&self) -> String {
+        if self.gl.is_some() { "webgl".into() } else { "dom".into() }
+    }
+
+    // 3. The Bridge: Input (Called from JS) 🌉
+    pub fn send_key(&mut self, key_code: u32) {
+        self.idle_watch.input(clock::now_ms());
+        self.input_source.record(InputSource::Keys);
+        let Some(action) = self.keymap.action_for(key_code) else {
+            return;
+        };
+        if !self.repeat_guard.accept(key_code, clock::now_ms()) {
+            return;
+        }
+        if self.input_queue.priority.is_some() || self.root_list.intro.is_playing() {
+            self.input_queue.push(action); // Applied by the next render() (after the intro)
+            return;
+        }
+        self.apply_action(action);
+    }
+
+    // Mixed Up/Down + Left/Right bursts: "vertical" or "horizontal" moves
+    // first (one axis per frame, the other queued), "none" = as they come
+    pub fn set_axis_priority(&mut self, priority: &str) -> Result<(), JsValue> {
+        self.input_queue.priority = match priority {
+            "vertical" => Some(Axis::Vertical),
+            "horizontal" => Some(Axis::Horizontal),
+            "none" => None,
+            _ => return Err("axis priority must be \"vertical\", \"horizontal\" or \"none\"".into()),
+        };
+        if self.input_queue.priority.is_none() && !self.root_list.intro.is_playing() {
+            for action in self.input_queue.drain_frame() {
+                self.apply_action(action);
+            }
+        }
+        Ok(())
+    }
+
+    // `cb` fires once no key / pointer input came for `timeout_ms` (0 = off);
+    // on_active fires with the next input after that
+    pub fn set_on_idle(&mut self, cb: Option<js_sys::Function>, timeout_ms: f64) {
+        self.idle_watch.on_idle = cb;
+        self.idle_watch.timeout_ms = timeout_ms.max(0.0);
+    }
+
+    pub fn set_on_active(&mut self, cb: Option<js_sys::Function>) {
+        self.idle_watch.on_active = cb;
+    }
+
+    // Hide the mouse cursor over the grid while keys / the remote are driving
+    // it; it reappears with the next pointer event (call pointer_move from mousemove)
+    pub fn set_auto_hide_cursor(&mut self, enabled: bool) {
+        self.input_source.set_auto_hide_cursor(enabled);
+    }
+
+    // "keys" or "pointer": where the latest input came from (null before any)
+    pub fn last_input_source(&self) -> Option<String> {
+        self.input_source.last.map(|source| source.name().to_string())
+    }
+
+    // SOUNDS 🔊 Called with { action, row, item } on key input only (never
+    // for programmatic focus): a move that moved focus...
+    pub fn set_nav_sound(&mut self, cb: Option<js_sys::Function>) {
+        self.nav_sounds.on_nav = cb;
+    }
+
+    // ...Select...
+    pub fn set_select_sound(&mut self, cb: Option<js_sys::Function>) {
+        self.nav_sounds.on_select = cb;
+    }
+
+    // ...or a move that didn't, with edge: "top" | "bottom" | "start" | "end"
+    pub fn set_edge_sound(&mut self, cb: Option<js_sys::Function>) {
+        self.nav_sounds.on_edge = cb;
+    }
+
+    // MENU 📋 Settings-style vertical list drawn instead of the grid, with
+    // the keys until hide_menu(). `entries`: strings or { label, icon? };
+    // `on_select(index, label)` fires on Select (WebGL only)
+    pub fn show_menu(&mut self, entries: js_sys::Array, on_select: Option<js_sys::Function>) -> Result<(), JsValue> {
+        let Some(gl) = &self.gl else {
+            return Ok(());
+        };
+        let entries = entries
+            .iter()
+            .map(|entry| {
+                if let Some(label) = entry.as_string() {
+                    return Ok((label, None));
+                }
+                let label = js_sys::Reflect::get(&entry, &"label".into())?
+                    .as_string()
+                    .ok_or("menu entries must be strings or { label, icon? }")?;
+                let icon = js_sys::Reflect::get(&entry, &"icon".into())?.as_string();
+                Ok((label, icon))
+            })
+            .collect::<Result<Vec<_>, JsValue>>()?;
+
+        let mut menu = MenuList::new(entries);
+        menu.set_viewport(self.width as f32, self.height as f32);
+        menu.animation = self.root_list.animation;
+        menu.on_select = on_select;
+        if let Some(mut old) = self.menu.replace(menu) {
+            old.release(&gl.context);
+        }
+        self.force_redraw = true;
+        Ok(())
+    }
+
+    // Back to the grid
+    pub fn hide_menu(&mut self) {
+        if let (Some(mut menu), Some(gl)) = (self.menu.take(), &self.gl) {
+            menu.release(&gl.context);
+        }
+        self.force_redraw = true;
+    }
+
+    // Up on the first row / Down on the last row (focus stays put)
+    pub fn set_on_escape_top(&mut self, cb: Option<js_sys::Function>) {
+        self.on_escape_top = cb;
+    }
+
+    pub fn set_on_escape_bottom(&mut self, cb: Option<js_sys::Function>) {
+        self.on_escape_bottom = cb;
+    }
+
+    // Any move off the outside of the grid, with its direction: Up on the
+    // first row, Down on the last, Left on the first row's first item, Right
+    // on the last row's last item. Side edges of the rows in between (and
+    // wrapping rows) never fire it.
+    pub fn set_on_focus_exit(&mut self, cb: Option<js_sys::Function>) {
+        self.on_focus_exit = cb;
+    }
+
+    // Drop repeats of the same key closer together than `ms` (0 = off),
+    // for TV browsers with very fast keydown auto-repeat
+    pub fn set_min_key_interval_ms(&mut self, ms: u32) {
+        self.repeat_guard.min_interval_ms = ms as f64;
+    }
+
+    // Called with ({ row, item, id, row_id }) when the focused poster is selected
+    pub fn set_on_activate(&mut self, cb: Option<js_sys::Function>) {
+        self.on_activate = cb;
+    }
+
+    // { row, item, id, row_id } of the focused poster, or null
+    pub fn focused(&self) -> Result<JsValue, JsValue> {
+        let Some((row, item)) = self.root_list.focused_item() else {
+            return Ok(JsValue::NULL);
+        };
+        let row_list = &self.root_list.rows[row];
+        let obj = js_sys::Object::new();
+        js_sys::Reflect::set(&obj, &"row".into(), &(row as u32).into())?;
+        js_sys::Reflect::set(&obj, &"item".into(), &(item as u32).into())?;
+        js_sys::Reflect::set(&obj, &"id".into(), &row_list.items[item].id.as_str().into())?;
+        js_sys::Reflect::set(&obj, &"row_id".into(), &row_list.id.as_str().into())?;
+        Ok(obj.into())
+    }
+
+    // { x, y, width, height } of a poster as currently drawn, in CSS pixels
+    // relative to the canvas' top-left (for DOM tooltips / menus beside it),
+    // or null if it doesn't exist or is off screen
+    pub fn item_screen_rect(&self, row: usize, item: usize) -> Result<JsValue, JsValue> {
+        let Some([x, y, w, h]) = self.root_list.item_screen_rect(row, item) else {
+            return Ok(JsValue::NULL);
+        };
+
+        // Layout pixels -> CSS pixels: whatever size CSS (or set_display_size,
+        // i.e. the DPR) made the canvas on the page
+        let (client_w, client_h) = match (&self.gl, &self.dom_grid) {
+            (Some(gl), _) => (gl.canvas.client_width() as f32, gl.canvas.client_height() as f32),
+            (None, Some(dom_grid)) => dom_grid.client_size(),
+            (None, None) => (0.0, 0.0),
+        };
+        let viewport_rect = self.gl.as_ref().and_then(|gl| gl.viewport_rect);
+        let [x, y, w, h] = css_rect([x, y, w, h], viewport_rect, (client_w, client_h), (self.width, self.height));
+
+        let rect = js_sys::Object::new();
+        js_sys::Reflect::set(&rect, &"x".into(), &x.into())?;
+        js_sys::Reflect::set(&rect, &"y".into(), &y.into())?;
+        js_sys::Reflect::set(&rect, &"width".into(), &w.into())?;
+        js_sys::Reflect::set(&rect, &"height".into(), &h.into())?;
+        Ok(rect.into())
+    }
+
+    // SEARCH 🔎 Every poster for which `predicate(src, id)` is truthy, as
+    // [{ row, item, src, id }] in grid order
+    pub fn find_items(&self, predicate: js_sys::Function) -> Result<js_sys::Array, JsValue> {
+        let hits = js_sys::Array::new();
+        for (row, index, item) in self.root_list.iter_items() {
+            if !predicate.call2(&JsValue::NULL, &item.src.as_str().into(), &item.id.as_str().into())?.is_truthy() {
+                continue;
+            }
+            let hit = js_sys::Object::new();
+            js_sys::Reflect::set(&hit, &"row".into(), &(row as u32).into())?;
+            js_sys::Reflect::set(&hit, &"item".into(), &(index as u32).into())?;
+            js_sys::Reflect::set(&hit, &"src".into(), &item.src.as_str().into())?;
+            js_sys::Reflect::set(&hit, &"id".into(), &item.id.as_str().into())?;
+            hits.push(&hit);
+        }
+        Ok(hits)
+    }
+
+    // Jump keyboard focus to (row, item); false if it doesn't exist
+    pub fn focus_item(&mut self, row: usize, item: usize) -> bool {
+        self.root_list.focus(row, item)
+    }
+
+    // Focus (row, item) and get a promise that resolves with true once the
+    // scroll has ended and the poster finished growing. A newer call (or a
+    // focus that doesn't exist) resolves the older promise early with false.
+    pub fn focus_and_wait(&mut self, row: usize, item: usize) -> js_sys::Promise {
+        self.settle_focus_wait(false);
+        let focused = self.root_list.focus(row, item);
+        let mut resolve_slot = None;
+        let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+            resolve_slot = Some(resolve);
+        });
+        self.focus_wait = resolve_slot;
+        if !focused {
+            self.settle_focus_wait(false);
+        }
+        promise
+    }
+
+    // Bulk-tag posters: id = mapper(src, row, item) for every one
+    // (non-string results leave the id alone)
+    pub fn assign_ids(&mut self, mapper: js_sys::Function) -> Result<(), JsValue> {
+        for (row, index, item) in self.root_list.iter_items_mut() {
+            let src: JsValue = item.src.as_str().into();
+            let id = mapper.call3(&JsValue::NULL, &src, &(row as u32).into(), &(index as u32).into())?;
+            if let Some(id) = id.as_string() {
+                item.id = id;
+            }
+        }
+        Ok(())
+    }
+
+    // Tag a row / a poster with the app's own id (returned by focused())
+    pub fn set_row_id(&mut self, row: usize, id: &str) -> bool {
+        match self.root_list.rows.get_mut(row) {
+            Some(row) => {
+                row.id = id.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_item_id(&mut self, row: usize, item: usize, id: &str) -> bool {
+        match self.root_list.rows.get_mut(row).and_then(|r| r.items.get_mut(item)) {
+            Some(item) => {
+                item.id = id.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Drag-to-scroll the focused row with momentum (off by default)
+    pub fn set_kinetic_scroll(&mut self, enabled: bool) {
+        self.root_list.kinetic_scroll = enabled;
+        if !enabled {
+            self.root_list.pointer_up();
+        }
+    }
+
+    // Scroll the focused row by `dx` / the grid by `dy` canvas pixels (wheel,
+    // peeks) without moving focus; the focused poster may go off screen until
+    // the next key press brings it back
+    pub fn scroll_by(&mut self, dx: f32, dy: f32) {
+        self.root_list.scroll_by(dx, dy);
+    }
+
+    // Pointer events, `x` in canvas pixels (map from CSS pixels on the JS side)
+    pub fn pointer_down(&mut self, x: f32) {
+        self.idle_watch.input(clock::now_ms());
+        self.input_source.record(InputSource::Pointer);
+        self.root_list.pointer_down(x);
+    }
+
+    pub fn pointer_move(&mut self, x: f32) {
+        self.idle_watch.input(clock::now_ms());
+        self.input_source.record(InputSource::Pointer);
+        self.root_list.pointer_move(x);
+    }
+
+    pub fn pointer_up(&mut self) {
+        self.idle_watch.input(clock::now_ms());
+        self.input_source.record(InputSource::Pointer);
+        self.root_list.pointer_up();
+    }
+
+    // Keep rows start..=end at full brightness, dim the others to `dim`
+    pub fn set_focus_group(&mut self, start: usize, end: usize, dim: f32) {
+        self.root_list.set_focus_group(start, end);
+        self.root_list.group_dim = dim.clamp(0.0, 1.0);
+    }
+
+    pub fn clear_focus_group(&mut self) {
+        self.root_list.clear_focus_group();
+    }
+
+    // Scroll snaps once within this many physical pixels of its target
+    pub fn set_snap_threshold(&mut self, physical_px: f32) {
+        self.root_list.animation.snap_threshold = physical_px.max(0.0);
+    }
+
+    // Round resting poster scales (the 1.2x focus pop) to whole physical
+    // pixels so they stay sharp; fractional while animating (default off)
+    pub fn set_scale_pixel_snap(&mut self, enabled: bool) {
+        self.root_list.animation.scale_pixel_snap = enabled;
+        self.force_redraw = true;
+    }
+
+    // Tinted rounded card behind `row` (WebGL only): `color` 0xRRGGBB at
+    // `opacity`, null removes it; false if the row doesn't exist
+    pub fn set_row_background(&mut self, row: usize, color: Option<u32>, opacity: f32, radius: f32) -> bool {
+        match self.root_list.rows.get_mut(row) {
+            Some(row) => {
+                row.set_background(color.map(|color| rgb(color, opacity)), radius);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Left/Right slide `row` by `step_px` a press with nothing focused
+    // (continuous banner strips); 0 = normal item-by-item. False if the row
+    // doesn't exist
+    pub fn set_row_free_scroll(&mut self, row: usize, step_px: f32) -> bool {
+        let mode = if step_px > 0.0 { SelectionMode::FreeScroll(step_px) } else { SelectionMode::Snap };
+        match self.root_list.rows.get_mut(row) {
+            Some(row) => {
+                row.set_selection_mode(mode);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Glossy reflection under `row`'s posters: `height_fraction` of each poster
+    // is mirrored, starting at `opacity` and fading to nothing (WebGL only)
+    pub fn set_row_reflection(&mut self, row: usize, enabled: bool, height_fraction: f32, opacity: f32) -> bool {
+        match self.root_list.rows.get_mut(row) {
+            Some(row) => {
+                row.set_reflection(enabled, height_fraction, opacity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Stylized hero row: selection centered, neighbours tilted in perspective (WebGL only)
+    pub fn set_row_coverflow(&mut self, row: usize, coverflow: bool) -> bool {
+        match self.root_list.rows.get_mut(row) {
+            Some(row) => {
+                row.set_coverflow(coverflow);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Left/Right wrap around inside `row` (the column itself never wraps)
+    pub fn set_row_horizontal_wrap(&mut self, row: usize, wrap: bool) -> bool {
+        match self.root_list.rows.get_mut(row) {
+            Some(row) => {
+                row.set_horizontal_wrap(wrap);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Trailing "See All →" tile on `row` (label None removes it); selecting
+    // it calls `on_activate(row)` instead of the engine's activate callback
+    pub fn set_row_see_all(&mut self, row: usize, label: Option<String>, on_activate: Option<js_sys::Function>) -> bool {
+        let context = self.gl.as_ref().map(|gl| &gl.context);
+        match self.root_list.rows.get_mut(row) {
+            Some(row) => {
+                row.set_see_all(context, label.as_deref(), on_activate);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Plain LERP for "scroll_x", "scroll_y", "scale", "dim", "glide" or
+    // "fade": `factor` of the gap per frame
+    pub fn set_lerp_easing(&mut self, channel: &str, factor: f32) -> Result<(), JsValue> {
+        let easing = self.root_list.animation.easing_mut(channel).ok_or("unknown easing channel")?;
+        *easing = Easing::Lerp(factor.clamp(0.0, 1.0));
+        Ok(())
+    }
+
+    // Spring for one channel (can overshoot); `damping` 0..1, lower settles faster
+    pub fn set_spring_easing(&mut self, channel: &str, stiffness: f32, damping: f32) -> Result<(), JsValue> {
+        let easing = self.root_list.animation.easing_mut(channel).ok_or("unknown easing channel")?;
+        *easing = Easing::Spring {
+            stiffness: stiffness.max(0.0),
+            damping: damping.clamp(0.0, 1.0),
+        };
+        Ok(())
+    }
+
+    // Brightness of rows without focus (1.0 = off); fades with the "dim" easing
+    pub fn set_inactive_row_dim(&mut self, dim: f32) {
+        self.root_list.inactive_dim = dim.clamp(0.0, 1.0);
+    }
+
+    // LERP factor of the row dim fade (1.0 = jump)
+    pub fn set_dim_smoothing(&mut self, factor: f32) {
+        self.root_list.animation.dim = Easing::Lerp(factor.clamp(0.0, 1.0));
+    }
+
+    // Shift a selected poster inward when its scale-up would poke past the
+    // canvas edge (first / last visible items), instead of clipping it
+    pub fn set_keep_selection_onscreen(&mut self, enabled: bool) {
+        self.root_list.set_keep_selection_onscreen(enabled);
+    }
+
+    // Selected posters only grow once their image has loaded (off by default)
+    pub fn set_defer_focus_until_loaded(&mut self, enabled: bool) {
+        self.root_list.animation.defer_focus_until_loaded = enabled;
+    }
+
+    // Spread item setup over frames: at most `n` per frame (0 = no limit)
+    pub fn set_load_budget_per_frame(&mut self, n: usize) {
+        self.root_list.load_budget = n;
+    }
+
+    // Rows jumped by PageUp/PageDown (0 = one screenful)
+    pub fn set_page_size(&mut self, rows: usize) {
+        self.root_list.set_page_size(rows);
+    }
+
+    // How many rows fit on screen, i.e. how far focus moves down before the
+    // list scrolls and the default page size (0 = from the canvas height)
+    pub fn set_rows_per_screen(&mut self, rows: usize) {
+        self.root_list.set_rows_per_screen(rows);
+    }
+
+    // Rows kept above the focused one when moving down (`top_rows`) and when
+    // moving up (`bottom_rows`); by default the focus moves down a whole
+    // screen (rows_per_screen) before scrolling, and 1 row up
+    pub fn set_scroll_anchors(&mut self, top_rows: usize, bottom_rows: usize) {
+        self.root_list.set_scroll_anchors(top_rows, bottom_rows);
+    }
+
+    // Keep `fraction` (0..1, default 0) of the next row visible under the
+    // focused one, so it's obvious the grid scrolls
+    pub fn set_peek_next_row(&mut self, fraction: f32) {
+        self.root_list.set_peek_next_row(fraction);
+    }
+
+    // "clamp" (default): a short last page stops at the bottom edge,
+    // "free": keep scrolling one row per step past it
+    pub fn set_last_page_behavior(&mut self, behavior: &str) -> Result<(), JsValue> {
+        let behavior = match behavior {
+            "clamp" => LastPageBehavior::Clamp,
+            "free" => LastPageBehavior::Free,
+            _ => return Err("behavior must be \"clamp\" or \"free\"".into()),
+        };
+        self.root_list.set_last_page_behavior(behavior);
+        Ok(())
+    }
+
+    // New last row of `count` posters cut from one sprite sheet image
+    // (`cols` x `rows` cells, row-major); returns the row index
+    pub fn add_sprite_row(&mut self, src: &str, cols: usize, rows: usize, count: usize) -> usize {
+        self.root_list.push_sprite_row(src, cols, rows, count)
+    }
+
+    // Blank gap of `height` px after the last row (skipped by Up/Down)
+    pub fn push_spacer(&mut self, height: f32) {
+        self.root_list.push_spacer(height);
+    }
+
+    // Remove one poster at runtime (layout + focus are fixed up automatically)
+    pub fn remove_item(&mut self, row: usize, item: usize) -> bool {
+        let context = self.gl.as_ref().map(|gl| &gl.context);
+        self.root_list.remove_item(context, row, item)
+    }
+
+    // Keep only posters whose src makes `predicate(src)` truthy in `row`;
+    // the rest animate out and the survivors slide together
+    pub fn filter_row(&mut self, row: usize, predicate: js_sys::Function) -> bool {
+        let Some(row) = self.root_list.rows.get_mut(row) else {
+            return false;
+        };
+        row.filter(|item| {
+            predicate
+                .call1(&JsValue::NULL, &item.src.as_str().into())
+                .map(|keep| keep.is_truthy())
+                .unwrap_or(true)
+        });
+        true
+    }
+
+    // Undo filter_row: hidden posters fade back in at their old places
+    pub fn clear_row_filter(&mut self, row: usize) -> bool {
+        match self.root_list.rows.get_mut(row) {
+            Some(row) => {
+                row.clear_filter();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Replace every poster in `row`. Each entry of `srcs` is an image URL
+    // or { src, id } to tag the poster with the app's own id.
+    pub fn replace_row(&mut self, row: usize, srcs: js_sys::Array) -> Result<(), JsValue> {
+        let items = srcs
+            .iter()
+            .map(|entry| {
+                let (src, id) = match entry.as_string() {
+                    Some(src) => (src, String::new()),
+                    None => {
+                        let field = |key: &str| js_sys::Reflect::get(&entry, &key.into()).ok().and_then(|v| v.as_string());
+                        let src = field("src").ok_or("srcs must be strings or { src, id } objects")?;
+                        (src, field("id").unwrap_or_default())
+                    }
+                };
+                let mut item = PosterItem::new(0.0, 0.0, ITEM_WIDTH, ITEM_HEIGHT, &src, true);
+                item.id = id;
+                Ok(item)
+            })
+            .collect::<Result<Vec<_>, JsValue>>()?;
+        let gl = self.gl.as_ref().map(|gl| (&gl.context, &mut self.texture_manager));
+        self.root_list.replace_row_items(row, items, gl)?;
+        if let Some(dom_grid) = &mut self.dom_grid {
+            dom_grid.invalidate(); // Same item count would otherwise keep the old <img> srcs
+        }
+        Ok(())
+    }
+
+    // Has this poster's own image finished loading? (false out of range)
+    pub fn is_item_loaded(&self, row: usize, item: usize) -> bool {
+        self.root_list.is_item_loaded(row, item)
+    }
+
+    // Slide one poster to (x, y) in canvas pixels; the next relayout() puts it back
+    pub fn move_item(&mut self, row: usize, item: usize, x: f32, y: f32) -> bool {
+        self.root_list.move_item(row, item, x, y)
+    }
+
+    // Offscreen rows / items per row kept uploaded around the visible ones
+    pub fn set_cull_margin(&mut self, rows: usize, items: usize) {
+        self.root_list.set_cull_margin(rows, items);
+    }
+
+    // Offscreen items per row / rows actually drawn past each visible edge
+    // (default 2 / 1): a little more GPU work for smoother scroll-in
+    pub fn set_render_overscan(&mut self, items: usize, rows: usize) {
+        self.root_list.set_render_overscan(items, rows);
+        self.force_redraw = true;
+    }
+
+    // [width, height] of the whole laid-out grid in layout pixels (widest
+    // row, all rows + gaps): size external scrollbars against the viewport
+    pub fn content_size(&self) -> Vec<f32> {
+        let (width, height) = self.root_list.content_size();
+        vec![width, height]
+    }
+
+    // [first, last] rows kept on the GPU (inclusive), empty if none is on
+    // screen; handy for prefetching
+    pub fn visible_rows(&self) -> Vec<u32> {
+        match self.root_list.visible_rows() {
+            Some((first, last)) => vec![first as u32, last as u32],
+            None => Vec::new(),
+        }
+    }
+
+    // [first, last] items of `row` kept on the GPU (inclusive), empty for a
+    // bad row or one with nothing on screen
+    pub fn visible_items(&self, row: usize) -> Vec<u32> {
+        match self.root_list.rows.get(row).and_then(|row| row.visible_items()) {
+            Some((first, last)) => vec![first as u32, last as u32],
+            None => Vec::new(),
+        }
+    }
+
+    // "rgb" (opaque posters, 25% less VRAM) or "rgba" (default); affects new loads
+    pub fn set_upload_format(&mut self, format: &str) -> Result<(), JsValue> {
+        let format = match format {
+            "rgb" => TextureFormat::Rgb,
+            "rgba" => TextureFormat::Rgba,
+            _ => return Err("format must be \"rgb\" or \"rgba\"".into()),
+        };
+        self.texture_manager.set_upload_format(format);
+        Ok(())
+    }
+
+    // True once scrolling and every visible animation have come to rest
+    // (held for a few frames, so it doesn't flicker)
+    pub fn is_settled(&self) -> bool {
+        self.root_list.is_settled()
+    }
+
+    // 0..1 while posters are being rebuilt after a lost WebGL context (1.0
+    // otherwise); `set_restore_budget` = how many per frame (0 = all at once)
+    pub fn restore_progress(&self) -> f32 {
+        self.root_list.restore_progress()
+    }
+
+    pub fn set_restore_budget(&mut self, items_per_frame: usize) {
+        self.root_list.restore_budget = items_per_frame;
+    }
+
+    // 0..1: how much of the first screen's artwork has loaded (1.0 once it
+    // all has, failures included), for a startup progress bar. The DOM
+    // fallback leaves images to the browser and always reports 1.0.
+    pub fn initial_load_progress(&self) -> f32 {
+        match self.gl {
+            Some(_) => self.texture_manager.initial_load_progress(),
+            None => 1.0,
+        }
+    }
+
+    // Vertex buffers in use by the grid: one per distinct poster geometry
+    pub fn vertex_buffer_count(&self) -> u32 {
+        self.root_list.geometry.buffer_count() as u32
+    }
+
+    // Rough texture memory in bytes (decoded pixels x bytes per pixel)
+    pub fn texture_memory_estimate(&self) -> f64 {
+        self.texture_manager.estimated_bytes() as f64
+    }
+
+    // Free every cached texture no poster uses any more; returns how many went
+    pub fn clear_texture_cache(&mut self) -> u32 {
+        match &self.gl {
+            Some(gl) => self.texture_manager.clear(&gl.context) as u32,
+            None => 0,
+        }
+    }
+
+    // Cap on texture_memory_estimate, in MB (0 = none, the default). Over it,
+    // the least recently requested textures go first; posters on screen are
+    // never evicted, offscreen ones load again when they come back into view.
+    pub fn set_texture_budget_mb(&mut self, mb: f64) {
+        self.texture_manager.budget_bytes = (mb.max(0.0) * 1024.0 * 1024.0) as u64;
+    }
+
+    // Start loading `srcs` now (for boxes of width x height) so they're cached later
+    pub fn warm_textures(&mut self, srcs: js_sys::Array, width: u32, height: u32) -> Result<(), JsValue> {
+        let Some(gl) = &self.gl else {
+            return Ok(());
+        };
+        let srcs = srcs
+            .iter()
+            .map(|src| src.as_string().ok_or_else(|| JsValue::from("srcs must be strings")))
+            .collect::<Result<Vec<_>, JsValue>>()?;
+        let srcs: Vec<&str> = srcs.iter().map(String::as_str).collect();
+        self.texture_manager.warm(&gl.context, &srcs, width, height)
+    }
+
+    // Per-shape placeholders: `provider(width, height)` returns a <canvas>
+    // drawn for a box that size, called once per aspect ratio (within 5%).
+    // Shown until the real image loads; null = back to the default (WebGL only)
+    pub fn set_placeholder_provider(&mut self, provider: Option<js_sys::Function>) {
+        self.texture_manager.set_placeholder_provider(provider);
+    }
+
+    // Upload images bottom-up (for sources that decode Y-flipped); affects new loads
+    pub fn set_flip_y(&mut self, flip_y: bool) {
+        self.texture_manager.set_flip_y(flip_y);
+    }
+
+    // Re-run layout (e.g. after the embedder changed item sizes)
+    pub fn relayout(&mut self) {
+        self.root_list.relayout();
+    }
+
+    // Snapshot of focus + every row's scroll (plain object, safe to JSON.stringify)
+    pub fn save_state(&self) -> Result<JsValue, JsValue> {
+        GridState::capture(&self.root_list).to_js()
+    }
+
+    // Back to a save_state() snapshot instantly (no scroll animation)
+    pub fn restore_state(&mut self, state: &JsValue) -> Result<(), JsValue> {
+        let state = GridState::from_js(state)?;
+        self.root_list.restore_state(&state);
+        Ok(())
+    }
+
+    // Read-only scroll state for syncing DOM effects: [scroll_x, scroll_y, velocity]
+    pub fn scroll_offsets(&self) -> Vec<f32> {
+        self.root_list.scroll_offsets().to_vec()
+    }
+
+    // Crop anchor for posters whose image doesn't match their box:
+    // "center" (default), "top", "bottom", "left" or "right"
+    pub fn set_cover_anchor(&mut self, anchor: &str) -> Result<(), JsValue> {
+        let anchor = CoverAnchor::from_name(anchor).ok_or("unknown cover anchor")?;
+        self.root_list.set_cover_anchor(anchor);
+        Ok(())
+    }
+
+    // Rounded poster corners in pixels (0 = square)
+    pub fn set_corner_radius(&mut self, radius: f32) {
+        self.root_list.set_corner_radius(radius);
+    }
+
+    // Different radius per corner (clockwise from top-left), whole grid
+    pub fn set_corner_radii(&mut self, top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) {
+        self.root_list.set_corner_radii([top_left, top_right, bottom_right, bottom_left]);
+    }
+
+    // Same for a single poster; false if it doesn't exist
+    pub fn set_item_corner_radii(&mut self, row: usize, item: usize, top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> bool {
+        match self.root_list.rows.get_mut(row).and_then(|r| r.items.get_mut(item)) {
+            Some(item) => {
+                item.set_corner_radii([top_left, top_right, bottom_right, bottom_left]);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // true when corners are anti-aliased, false = hard-edged fallback
+    pub fn has_smooth_edges(&self) -> bool {
+        self.gl.as_ref().map_or(false, |gl| gl.smooth_edges)
+    }
+
+    // Rewrite image URLs before loading: cb(src, width, height) -> url.
+    // Pass null/undefined to go back to loading `src` as-is.
+    pub fn set_url_transform(&mut self, cb: Option<js_sys::Function>) {
+        self.texture_manager.set_url_transform(cb);
+    }
+
+    // Branded placeholder shown until each poster's own image loads (WebGL only)
+    pub fn set_placeholder_image(&mut self, src: &str) {
+        if let Some(gl) = &self.gl {
+            self.texture_manager.set_placeholder_image(&gl.context, src);
+        }
+    }
+
+    // Treat an image as failed if it hasn't loaded/errored after `ms` (0 = never)
+    pub fn set_load_timeout_ms(&mut self, ms: u32) {
+        self.texture_manager.set_load_timeout_ms(ms);
+    }
+
+    // Per-image load durations: [[url, ms, ok], ...]
+    pub fn load_timings(&self) -> js_sys::Array {
+        self.texture_manager
+            .load_timings()
+            .into_iter()
+            .map(|t| {
+                let entry = js_sys::Array::new();
+                entry.push(&t.url.into());
+                entry.push(&t.ms.into());
+                entry.push(&t.ok.into());
+                entry
+            })
+            .collect()
+    }
+
+    // 4. The Loop: Render (Called from JS requestAnimationFrame) 🔄
+    // Animations advance by the time since the previous frame (at most
+    // max_frame_dt, so a resume after a hidden tab eases on instead of jumping)
+    pub fn render(&mut self) {
+        let now = *self.frame_time.get_or_insert_with(clock::now_ms);
+        self.frame_stats.record(now);
+        if !self.root_list.intro.is_playing() {
+            for action in self.input_queue.drain_frame() {
+                self.apply_action(action);
+            }
+        }
+        self.adapt_resolution();
+        let gl = self.gl.as_ref().map(|gl| (&gl.context, &mut self.texture_manager));
+        self.hero.update(&mut self.root_list, now, gl);
+        self.render_frame();
+        self.scroll_watch.observe(self.root_list.is_scrolling(), self.root_list.is_settled());
+        self.check_focus_wait();
+        self.idle_watch.update(now);
+        self.frame_time = None;
+    }
+
+    // Cap on how much animation time one render / render_at may catch up
+    // (default 50ms), so a resume after a long pause doesn't jump
+    pub fn set_max_frame_dt(&mut self, ms: f64) {
+        self.root_list.set_max_frame_dt(ms);
+    }
+
+    // STATIC RENDER 📸 One settled frame, for thumbnails / captures: every
+    // animation jumps to its target, then exactly one update + draw runs
+    // (dirty rendering can't skip it). Nothing gets scheduled; posters whose
+    // image hasn't arrived yet are still blank.
+    pub fn render_frame_static(&mut self) {
+        self.root_list.settle_now();
+        self.force_redraw = true;
+        let frame_time = self.frame_time.take(); // A single step, no catch-up
+        self.render_frame();
+        self.frame_time = frame_time;
+    }
+
+    // render() on the caller's clock (audio position, server time, replay)
+    // instead of performance.now()
+    pub fn render_at(&mut self, timestamp_ms: f64) {
+        self.frame_time = Some(timestamp_ms);
+        self.render();
+    }
+
+    // Test mode: step the fake clock by `ms` in fixed 60fps frames, rendering
+    // each one, so animations land on the same value every run. Returns the
+    // number of frames run (a partial last frame still counts as one)
+    #[cfg(feature = "test-utils")]
+    pub fn advance(&mut self, ms: f64) -> u32 {
+        if !clock::TestClock::is_installed() {
+            clock::TestClock::install(0.0);
+        }
+        let mut left = ms;
+        let mut frames = 0;
+        while left > 0.0 {
+            let step = left.min(crate::columnlist::MODEL_STEP_MS);
+            clock::TestClock::advance(step);
+            self.render();
+            left -= step;
+            frames += 1;
+        }
+        frames
+    }
+
+    // Posters focused for `dwell_ms` grow into a hero card; `hero_src_provider`
+    // (src, id) -> URL of wide art to show meanwhile (optional)
+    pub fn set_hero_on_focus(&mut self, enabled: bool, dwell_ms: f64, hero_src_provider: Option<js_sys::Function>) {
+        self.hero.enabled = enabled;
+        self.hero.dwell_ms = dwell_ms.max(0.0);
+        self.hero.provider = hero_src_provider;
+        if !enabled {
+            self.hero.collapse(&mut self.root_list);
+        }
+    }
+
+    // DIRTY-FLAG RENDERING 🚩 render() keeps the last frame on screen when no
+    // item, row or scroll changed (idle TV UIs: no GPU work at all). Off by default.
+    pub fn set_dirty_rendering(&mut self, enabled: bool) {
+        self.dirty_rendering = enabled;
+        self.force_redraw = true;
+    }
+
+    // Would the next render() draw? (always true with dirty rendering off)
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty_rendering || self.force_redraw || self.keeps_animating() || self.root_list.is_dirty()
+    }
+
+    // First-show animation of the whole grid: "none", "fade_in" or
+    // "slide_up" (from 30% of the height below), easing in with the scroll_y
+    // / dim curves. Keys pressed meanwhile wait until it's done. Call it
+    // right after construction: once the first frame ran it's an error.
+    // (The DOM fallback only slides.)
+    pub fn set_intro_animation(&mut self, kind: &str) -> Result<(), JsValue> {
+        let kind = IntroKind::from_name(kind).ok_or("intro animation must be \"none\", \"fade_in\" or \"slide_up\"")?;
+        if !self.root_list.set_intro(kind) {
+            return Err("intro animation must be set before the first frame".into());
+        }
+        self.force_redraw = true;
+        Ok(())
+    }
+
+    // Keep drawing every frame even with dirty rendering on, for content the
+    // grid can't see change. Playing badges and custom tiles already do this
+    // on their own while they exist (see animating_count).
+    pub fn set_always_render(&mut self, enabled: bool) {
+        self.always_render = enabled;
+        self.force_redraw = true;
+    }
+
+    // How many items animate by themselves (playing, custom drawn); while
+    // any do, no frame is skipped
+    pub fn animating_count(&self) -> usize {
+        self.root_list.animating_count()
+    }
+
+    fn keeps_animating(&self) -> bool {
+        self.always_render || self.root_list.animating_count() > 0
+    }
+
+    // Redraw on the next render() regardless (after changes the grid can't see)
+    pub fn request_redraw(&mut self) {
+        self.force_redraw = true;
+    }
+
+    // PER-ITEM SETTERS 🎛️ Change one tile in place (mark it watched, rename it...)
+    // without rebuilding the row. All throw for an index that doesn't exist.
+
+    // Ghost an unavailable poster (still focusable, Select ignored)
+    pub fn set_item_enabled(&mut self, row: usize, item: usize, enabled: bool) -> Result<(), JsValue> {
+        self.item_mut(row, item)?.set_enabled(enabled);
+        Ok(())
+    }
+
+    // Caption drawn under the poster ("" removes it; WebGL only)
+    pub fn set_item_label(&mut self, row: usize, item: usize, text: &str) -> Result<(), JsValue> {
+        self.item_mut(row, item)?.set_label(text);
+        Ok(())
+    }
+
+    // Several sizes of the poster as [[url, width_px], ...]: the smallest one
+    // covering its drawn size (DPR, render scale and focus pop included) is
+    // loaded, and a sharper one swapped in when it pops. Empty = src again
+    // from the next load on.
+    pub fn set_item_srcset(&mut self, row: usize, item: usize, srcset: js_sys::Array) -> Result<(), JsValue> {
+        let mut candidates = Vec::new();
+        for entry in srcset.iter() {
+            let pair = js_sys::Array::from(&entry);
+            let (url, width) = (pair.get(0).as_string(), pair.get(1).as_f64());
+            let (Some(url), Some(width)) = (url, width) else {
+                return Err("srcset entries must be [url, width]".into());
+            };
+            candidates.push((url, width.max(0.0) as u32));
+        }
+        self.item_mut(row, item)?.set_srcset(candidates);
+        Ok(())
+    }
+
+    // Freeze / resume one item's own animation (playing badge) without
+    // touching the rest of the grid, e.g. while a modal covers it
+    pub fn set_item_animation_paused(&mut self, row: usize, item: usize, paused: bool) -> Result<(), JsValue> {
+        self.item_mut(row, item)?.set_animation_paused(paused);
+        self.force_redraw = true;
+        Ok(())
+    }
+
+    // Multiply the poster's color (0..1 each); 1, 1, 1, 1 = untinted
+    pub fn set_item_tint(&mut self, row: usize, item: usize, r: f32, g: f32, b: f32, a: f32) -> Result<(), JsValue> {
+        let item = self.item_mut(row, item)?;
+        item.tint = [r, g, b, a].map(|c| c.clamp(0.0, 1.0));
+        item.mark_dirty();
+        Ok(())
+    }
+
+    fn item_mut(&mut self, row: usize, item: usize) -> Result<&mut PosterItem, JsValue> {
+        self.root_list
+            .item_mut(row, item)
+            .ok_or_else(|| JsValue::from_str(&format!("No item at ({}, {})", row, item)))
+    }
+
+    // How visible disabled posters stay (0..1, default 0.4)
+    pub fn set_disabled_opacity(&mut self, opacity: f32) {
+        for (_, _, item) in self.root_list.iter_items_mut() {
+            item.disabled_opacity = opacity.clamp(0.0, 1.0);
+            item.mark_dirty();
+        }
+    }
+
+    // Bespoke tile: `draw(gl, { x, y, width, height, alpha, pixel_ratio })`
+    // paints it into the canvas every frame instead of its image (rect in
+    // canvas pixels; GL state is restored afterwards). null = back to the
+    // poster. WebGL only.
+    pub fn set_item_custom_draw(&mut self, row: usize, item: usize, draw: Option<js_sys::Function>) -> bool {
+        let Some(target) = self.root_list.rows.get_mut(row).and_then(|r| r.items.get_mut(item)) else {
+            return false;
+        };
+        match draw {
+            Some(draw) => target.kind = ItemKind::Custom(draw),
+            None if matches!(target.kind, ItemKind::Custom(_)) => {
+                target.kind = ItemKind::Poster;
+                // Never fetched its image: let load_pending set it up from scratch
+                if target.texture.is_none() {
+                    target.buffer = None;
+                }
+            }
+            None => {}
+        }
+        target.mark_dirty();
+        true
+    }
+
+    // Animated equalizer badge on a poster ("now playing"), independent of focus
+    pub fn set_item_playing(&mut self, row: usize, item: usize, playing: bool) -> bool {
+        self.root_list.set_playing(row, item, playing)
+    }
+
+    // Mark one poster for redraw (e.g. after changing its look from outside)
+    pub fn mark_item_dirty(&mut self, row: usize, item: usize) -> bool {
+        match self.root_list.rows.get_mut(row).and_then(|r| r.items.get_mut(item)) {
+            Some(item) => {
+                item.mark_dirty();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Rolling average ms between render() calls (0 until enough frames were seen)
+    pub fn average_frame_ms(&self) -> f64 {
+        self.frame_stats.average_ms().unwrap_or(0.0)
+    }
+
+    // Let frame time drive the render scale: drops (not below `min_scale`)
+    // while frames take longer than `target_ms`, recovers when there's room.
+    // target_ms <= 0 turns it off and keeps whatever scale is current.
+    pub fn set_adaptive_resolution(&mut self, target_ms: f64, min_scale: f32) {
+        self.adaptive = (target_ms > 0.0).then(|| AdaptiveResolution {
+            target_ms,
+            min_scale: min_scale.clamp(0.1, 1.0),
+        });
+        self.frame_stats.reset();
+    }
+
+    // Fires once when scrolling begins after being at rest
+    pub fn set_on_scroll_start(&mut self, cb: Option<js_sys::Function>) {
+        self.scroll_watch.on_start = cb;
+    }
+
+    // Fires once the scroll has ended and the grid settled (posters done growing too)
+    pub fn set_on_scroll_end(&mut self, cb: Option<js_sys::Function>) {
+        self.scroll_watch.on_end = cb;
+    }
+
+    // Shadow depth for one poster (0 = flat); false if out of range
+    pub fn set_item_elevation(&mut self, row: usize, item: usize, elevation: f32) -> Result<bool, JsValue> {
+        // Nothing to lift: don't build the shadow program for it
+        if self.root_list.rows.get(row).map_or(true, |r| item >= r.items.len()) {
+            return Ok(false);
+        }
+        self.ensure_shadow_pass()?;
+        Ok(self.root_list.set_item_elevation(row, item, elevation))
+    }
+
+    // Extra elevation the selected poster gets while it's popped out
+    pub fn set_selected_elevation(&mut self, elevation: f32) -> Result<(), JsValue> {
+        if let Some(shadow) = self.ensure_shadow_pass()? {
+            shadow.selected_elevation = elevation.max(0.0);
+        }
+        Ok(())
+    }
+
+    // Darken everything but a circle around the selected poster (WebGL only).
+    // `intensity` 0..1, `radius` in canvas pixels.
+    pub fn set_spotlight(&mut self, enabled: bool, intensity: f32, radius: f32) -> Result<(), JsValue> {
+        let Some(gl) = &mut self.gl else {
+            return Ok(());
+        };
+        if gl.spotlight.is_none() {
+            if !enabled {
+                return Ok(());
+            }
+            let program = gl.shaders.get(&gl.context, ProgramKey::Spotlight)?;
+            gl.spotlight = Some(SpotlightPass::new(&gl.context, program, self.width as f32, self.height as f32)?);
+        }
+        if let Some(spotlight) = &mut gl.spotlight {
+            spotlight.enabled = enabled;
+            spotlight.intensity = intensity.clamp(0.0, 1.0);
+            spotlight.radius = radius.max(0.0);
+        }
+        self.force_redraw = true;
+        Ok(())
+    }
+
+    // Gradient bands fading the grid out under top/bottom chrome (WebGL only).
+    // Heights in canvas pixels (0 = none), `color` as 0xRRGGBB.
+    pub fn set_scrim(&mut self, top_height: f32, bottom_height: f32, color: u32) -> Result<(), JsValue> {
+        let Some(gl) = &mut self.gl else {
+            return Ok(());
+        };
+        let top_height = top_height.max(0.0);
+        let bottom_height = bottom_height.max(0.0);
+        if gl.scrim.is_none() {
+            if top_height == 0.0 && bottom_height == 0.0 {
+                return Ok(());
+            }
+            let program = gl.shaders.get(&gl.context, ProgramKey::Scrim)?;
+            gl.scrim = Some(ScrimPass::new(&gl.context, program, self.width as f32, self.height as f32)?);
+        }
+        if let Some(scrim) = &mut gl.scrim {
+            scrim.top_height = top_height;
+            scrim.bottom_height = bottom_height;
+            let [r, g, b, _] = rgb(color, 1.0);
+            scrim.color = [r, g, b];
+        }
+        self.force_redraw = true;
+        Ok(())
+    }
+
+    // Global poster color correction, 1.0 each = off (WebGL only). Brightness
+    // multiplies, contrast spreads around mid-grey, gamma > 1 lifts the shadows.
+    pub fn set_color_adjust(&mut self, brightness: f32, contrast: f32, gamma: f32) -> Result<(), JsValue> {
+        let Some(gl) = &mut self.gl else {
+            return Ok(());
+        };
+        let program = gl.shaders.get(&gl.context, ProgramKey::Textured)?;
+        gl.context.use_program(Some(&program));
+        gl.color_adjust = [brightness.max(0.0), contrast.max(0.0), gamma.max(0.01)];
+        let [brightness, contrast, gamma] = gl.color_adjust;
+        gl.poster_uniforms.set_color_adjust(&gl.context, brightness, contrast, gamma);
+        self.force_redraw = true;
+        Ok(())
+    }
+
+    // Dev aid: outline + label every drawn item (WebGL only). Off = skipped entirely.
+    pub fn set_debug_overlay(&mut self, enabled: bool) -> Result<(), JsValue> {
+        let Some(gl) = &mut self.gl else {
+            return Ok(());
+        };
+        if gl.debug.is_none() {
+            if !enabled {
+                return Ok(());
+            }
+            let program = gl.shaders.get(&gl.context, ProgramKey::Debug)?;
+            gl.debug = Some(DebugOverlay::new(&gl.context, program, self.width as f32, self.height as f32)?);
+        }
+        if let Some(debug) = &mut gl.debug {
+            debug.enabled = enabled;
+        }
+        self.force_redraw = true;
+        Ok(())
+    }
+
+    // COMPOSITING 🖼️ Draw the grid only into the x, y, width x height part
+    // of the canvas (layout pixels, WebGL only), the rest is left alone for
+    // the app's own scene. The grid lays itself out for that size, at the same
+    // scale; item_screen_rect includes the offset.
+    pub fn set_viewport_rect(&mut self, x: f32, y: f32, width: f32, height: f32) -> Result<(), JsValue> {
+        let Some(gl) = &mut self.gl else {
+            return Ok(());
+        };
+        let (width, height) = (width.max(1.0), height.max(1.0));
+        gl.viewport_rect = Some([x, y, width, height]);
+        self.set_grid_size(width, height);
+        Ok(())
+    }
+
+    // Back to the whole canvas
+    pub fn clear_viewport_rect(&mut self) {
+        let Some(gl) = &mut self.gl else {
+            return;
+        };
+        gl.viewport_rect = None;
+        self.set_grid_size(self.width as f32, self.height as f32);
+    }
+
+    // HIDPI 🔍 Show the canvas at `css_width` x `css_height` CSS pixels with a
+    // drawing buffer of exactly that many physical pixels. The physical size is
+    // rounded (not truncated) and the CSS size is set back from it, so on a
+    // fractional DPR (1.5, 2.25) buffer and page agree to the pixel.
+    // Layout stays logical, like set_render_scale (which now scales this size).
+    pub fn set_display_size(&mut self, css_width: f64, css_height: f64) -> Result<(), JsValue> {
+        let Some(gl) = &mut self.gl else {
+            return Ok(());
+        };
+        let dpr = web_sys::window().map_or(1.0, |w| w.device_pixel_ratio());
+        let (physical_w, css_w) = physical_size(css_width, dpr);
+        let (physical_h, css_h) = physical_size(css_height, dpr);
+
+        let style = gl.canvas.style();
+        style.set_property("width", &format!("{}px", css_w))?;
+        style.set_property("height", &format!("{}px", css_h))?;
+        self.root_list.animation.device_pixel_ratio = dpr as f32;
+
+        gl.native_size = (physical_w, physical_h);
+        gl.apply_buffer_size();
+        self.force_redraw = true; // Resizing the buffer cleared it
+        Ok(())
+    }
+
+    // DYNAMIC RESOLUTION 📉 Draw into a buffer of native size * `scale`
+    // (0.1..=1; native = logical size unless set_display_size was called) and
+    // let the browser upscale it; the canvas' CSS size doesn't change. Layout
+    // stays in logical pixels (u_resolution is untouched, the viewport does
+    // the mapping), so nothing moves.
+    pub fn set_render_scale(&mut self, scale: f32) -> Result<(), JsValue> {
+        let Some(gl) = &mut self.gl else {
+            return Ok(());
+        };
+        let scale = if scale.is_finite() { scale.clamp(0.1, 1.0) } else { 1.0 };
+        if scale == gl.render_scale {
+            return Ok(());
+        }
+
+        // Pin the on-page size first, or the canvas would shrink along with its buffer
+        let style = gl.canvas.style();
+        if style.get_property_value("width")?.is_empty() {
+            style.set_property("width", &format!("{}px", self.width))?;
+        }
+        if style.get_property_value("height")?.is_empty() {
+            style.set_property("height", &format!("{}px", self.height))?;
+        }
+
+        gl.render_scale = scale;
+        gl.apply_buffer_size();
+        self.force_redraw = true; // Resizing the buffer cleared it
+        Ok(())
+    }
+
+    pub fn render_scale(&self) -> f32 {
+        self.gl.as_ref().map_or(1.0, |gl| gl.render_scale)
+    }
+
+    // 5. Modal Blur: radius in pixels, 0 turns it off 🌫️ (WebGL only)
+    pub fn set_blur(&mut self, radius: f32) -> Result<(), JsValue> {
+        let Some(gl) = &mut self.gl else {
+            return Ok(());
+        };
+        if gl.blur.is_none() && radius > 0.0 {
+            let program = gl.shaders.get(&gl.context, ProgramKey::Blur)?;
+            gl.blur = Some(BlurPass::new(&gl.context, program, self.width, self.height)?);
+        }
+        if let Some(blur) = &mut gl.blur {
+            if !blur.is_active() {
+                blur.invalidate(); // Don't show a stale frozen frame from last time
+            }
+            blur.radius = radius.max(0.0);
+            blur.freeze = self.blur_freeze;
+        }
+        self.force_redraw = true;
+        Ok(())
+    }
+
+    // true = the grid stops animating under the blur (last frame is reused)
+    pub fn set_blur_freeze(&mut self, freeze: bool) {
+        self.blur_freeze = freeze;
+        if let Some(blur) = self.gl.as_mut().and_then(|gl| gl.blur.as_mut()) {
+            blur.freeze = freeze;
+        }
+    }
+}
+
+impl GameEngine {
+    fn activate(&self) {
+        // The see-all tile has its own callback
+        let row_index = self.root_list.selected_row_index;
+        if let Some(row) = self.root_list.rows.get(row_index).filter(|row| row.see_all_focused()) {
+            if let Some(cb) = &row.on_see_all {
+                if let Err(e) = cb.call1(&JsValue::NULL, &(row_index as u32).into()) {
+                    web_sys::console::error_2(&"on_see_all failed:".into(), &e);
+                }
+            }
+            return;
+        }
+
+        if self.root_list.focused_is_disabled() {
+            return;
+        }
+
+        let Some(cb) = &self.on_activate else {
+            return;
+        };
+        let result = self.focused().and_then(|focused| {
+            if focused.is_null() {
+                return Ok(JsValue::UNDEFINED);
+            }
+            cb.call1(&JsValue::NULL, &focused)
+        });
+        if let Err(e) = result {
+            web_sys::console::error_2(&"on_activate failed:".into(), &e);
+        }
+    }
+
+    // One key's worth of navigation, plus its callbacks
+    fn apply_action(&mut self, action: Action) {
+        let before = self.focus_position();
+        let blocked = match &mut self.menu {
+            Some(menu) => {
+                let blocked = menu.handle_input(action);
+                if action == Action::Select {
+                    Self::activate_menu(menu);
+                }
+                blocked
+            }
+            None => {
+                let blocked = self.root_list.handle_input(action);
+                if action == Action::Select {
+                    self.activate();
+                }
+                blocked
+            }
+        };
+
+        // Nowhere to go: let the page take focus
+        let escape = match blocked {
+            Some(BlockedEdge::Top) => &self.on_escape_top,
+            Some(BlockedEdge::Bottom) => &self.on_escape_bottom,
+            _ => &None,
+        };
+        if let Some(cb) = escape {
+            if let Err(e) = cb.call0(&JsValue::NULL) {
+                web_sys::console::error_2(&"escape callback failed:".into(), &e);
+            }
+        }
+        if let (Some(cb), Some(edge)) = (&self.on_focus_exit, blocked) {
+            if let Err(e) = cb.call1(&JsValue::NULL, &edge.direction().into()) {
+                web_sys::console::error_2(&"on_focus_exit failed:".into(), &e);
+            }
+        }
+
+        self.nav_sounds.play(action, before, self.focus_position(), blocked);
+    }
+
+    // (row, item) that has the keys: the menu's entry (row 0) while it's up
+    fn focus_position(&self) -> Option<(usize, usize)> {
+        match &self.menu {
+            Some(menu) => (!menu.items.is_empty()).then_some((0, menu.selected_index)),
+            None => self.root_list.focused_item(),
+        }
+    }
+
+    fn activate_menu(menu: &MenuList) {
+        let (Some(cb), Some(item)) = (&menu.on_select, menu.items.get(menu.selected_index)) else {
+            return;
+        };
+        if let Err(e) = cb.call2(&JsValue::NULL, &(menu.selected_index as u32).into(), &item.label.as_str().into()) {
+            web_sys::console::error_2(&"on_select failed:".into(), &e);
+        }
+    }
+
+    // focus_and_wait: resolve once scrolling ended and the focused poster settled
+    fn check_focus_wait(&mut self) {
+        let list = &self.root_list;
+        let scrolled = list.scroll_y == list.target_scroll_y;
+        if self.focus_wait.is_some() && scrolled && list.is_settled() {
+            self.settle_focus_wait(true);
+        }
+    }
+
+    fn settle_focus_wait(&mut self, completed: bool) {
+        if let Some(resolve) = self.focus_wait.take() {
+            if let Err(e) = resolve.call1(&JsValue::NULL, &completed.into()) {
+                web_sys::console::error_2(&"focus_and_wait resolve failed:".into(), &e);
+            }
+        }
+    }
+
+    fn adapt_resolution(&mut self) {
+        let Some(adaptive) = &self.adaptive else {
+            return;
+        };
+        if let Some(scale) = adaptive.update(&self.frame_stats, self.render_scale()) {
+            if let Err(e) = self.set_render_scale(scale) {
+                web_sys::console::error_2(&"Render scale error:".into(), &e);
+            }
+            self.frame_stats.reset(); // Judge the new scale on its own frames
+        }
+    }
+
+    // One frame: update the model, then draw it (GL) or mirror it (DOM)
+    fn render_frame(&mut self) {
+        if self.gl.as_ref().is_some_and(|gl| gl.context_watch.restored.replace(false)) {
+            self.restore_context();
+        }
+        let Some(gl) = &mut self.gl else {
+            // DOM fallback: same model, <img> tags instead of quads
+            step_model(&mut self.root_list, self.frame_time);
+            if let Some(dom_grid) = &mut self.dom_grid {
+                if let Err(e) = dom_grid.sync(&self.root_list, &self.texture_manager) {
+                    web_sys::console::error_2(&"DOM grid error:".into(), &e);
+                }
+            }
+            return;
+        };
+        if gl.context_watch.lost.get() {
+            return; // Nothing to draw into until it's restored
+        }
+
+        // Render scale / DPR moved a lot (and stayed there): re-request what's
+        // on screen at the new size through the URL transform
+        let pixel_ratio = gl.canvas.width() as f32 / self.width.max(1) as f32;
+        let now = self.frame_time.unwrap_or_else(clock::now_ms);
+        if self.texture_manager.observe_scale(pixel_ratio, now) {
+            if let Err(e) = self.root_list.reload_textures(&gl.context, &mut self.texture_manager) {
+                web_sys::console::error_2(&"Texture reload error:".into(), &e);
+            }
+        }
+        self.texture_manager.sweep_retired(&gl.context);
+        self.root_list.evict_textures(&gl.context, &mut self.texture_manager);
+
+        // Items still waiting for a buffer/texture (load budget, late additions)
+        if let Err(e) = self.root_list.load_pending(&gl.context, &mut self.texture_manager) {
+            web_sys::console::error_2(&"Asset load error:".into(), &e);
+        }
+
+        let program = match gl.shaders.get(&gl.context, ProgramKey::Textured) {
+            Ok(program) => program,
+            Err(e) => {
+                web_sys::console::error_1(&format!("Shader error: {}", e).into());
+                return;
+            }
+        };
+
+        // Menu up: it replaces the grid (the grid's model is paused meanwhile)
+        if let Some(menu) = &mut self.menu {
+            if let Err(e) = menu.load_pending(&gl.context, &mut self.texture_manager) {
+                web_sys::console::error_1(&format!("Menu load error: {}", e).into());
+            }
+            menu.update_model();
+            let painter = Painter {
+                program: &program,
+                uniforms: &gl.poster_uniforms,
+                shadow: None,
+                scrim: None,
+                spotlight: None,
+                debug: None,
+                pixel_ratio: gl.canvas.width() as f32 / self.width.max(1) as f32,
+                fade: 1.0,
+            };
+            let context = &gl.context;
+            gl.grid_area(self.width, self.height).apply(context);
+            context.use_program(Some(&program));
+            context.uniform1f(gl.poster_uniforms.fade.as_ref(), painter.fade);
+            context.clear_color(0.1, 0.1, 0.1, 1.0);
+            context.clear(WebGlRenderingContext::COLOR_BUFFER_BIT);
+            menu.upload(context);
+            menu.draw(context, &painter);
+            return;
+        }
+
+        // Advance the model (unless a frozen blur is standing in for the grid),
+        // then with dirty rendering skip the frame if it would look the same
+        let blurring = gl.blur.as_ref().is_some_and(|b| b.is_active());
+        if !blurring || gl.blur.as_ref().is_some_and(|b| b.needs_scene()) {
+            step_model(&mut self.root_list, self.frame_time);
+        }
+        let animating = self.always_render || self.root_list.animating_count() > 0;
+        if self.dirty_rendering && !blurring && !self.force_redraw && !animating && !self.root_list.is_dirty() {
+            return;
+        }
+        self.force_redraw = false;
+        self.root_list.clear_dirty();
+
+        let painter = Painter {
+            program: &program,
+            uniforms: &gl.poster_uniforms,
+            shadow: gl.shadow.as_ref(),
+            scrim: gl.scrim.as_ref().filter(|s| s.is_active()),
+            spotlight: gl.spotlight.as_ref().filter(|s| s.enabled),
+            debug: gl.debug.as_ref().filter(|d| d.enabled),
+            pixel_ratio: gl.canvas.width() as f32 / self.width.max(1) as f32,
+            fade: self.root_list.intro.alpha,
+        };
+
+        let area = gl.grid_area(self.width, self.height);
+
+        // A. Blurred path: grid -> offscreen target -> blur -> screen
+        if let Some(blur) = gl.blur.as_mut().filter(|b| b.is_active()) {
+            if blur.needs_scene() {
+                if let Err(e) = blur.begin_scene(&gl.context, gl.canvas.width(), gl.canvas.height()) {
+                    web_sys::console::error_1(&format!("Blur target error: {}", e).into());
+                    return;
+                }
+                area.apply(&gl.context);
+                Self::draw_grid(&gl.context, &painter, &mut self.root_list);
+            }
+            blur.apply(&gl.context, area.scissor);
+            return;
+        }
+
+        // B. Normal path: straight to the screen
+        area.apply(&gl.context);
+        Self::draw_grid(&gl.context, &painter, &mut self.root_list);
+    }
+
+    // CONTEXT RESTORED ♻️ Programs and passes are rebuilt right away; posters
+    // come back through load_pending, restore_budget a frame, visible rows
+    // first (see restore_progress)
+    fn restore_context(&mut self) {
+        let Some(gl) = &mut self.gl else {
+            return;
+        };
+        if let Err(e) = gl.restore(self.width, self.height) {
+            web_sys::console::error_2(&"Context restore failed:".into(), &e);
+            return;
+        }
+        self.texture_manager.context_lost();
+        self.root_list.context_lost();
+        if let Some(menu) = &mut self.menu {
+            menu.context_lost();
+        }
+        self.force_redraw = true;
+    }
+
+    fn set_grid_size(&mut self, width: f32, height: f32) {
+        self.root_list.set_viewport(width, height);
+        if let Some(menu) = &mut self.menu {
+            menu.set_viewport(width, height);
+        }
+        self.force_redraw = true;
+    }
+
+    // Shadows cost nothing until the first elevation is set (WebGL only)
+    fn ensure_shadow_pass(&mut self) -> Result<Option<&mut ShadowPass>, JsValue> {
+        let Some(gl) = &mut self.gl else {
+            return Ok(None);
+        };
+        if gl.shadow.is_none() {
+            let program = gl.shaders.get(&gl.context, ProgramKey::Shadow)?;
+            gl.shadow = Some(ShadowPass::new(&gl.context, program, self.width as f32, self.height as f32)?);
+        }
+        Ok(gl.shadow.as_mut())
+    }
+
+    // Clear + Upload + Draw into whatever framebuffer is currently bound
+    // (the model was already advanced by render_frame)
+    fn draw_grid(context: &WebGlRenderingContext, painter: &Painter, root_list: &mut ColumnList) {
+        context.use_program(Some(painter.program));
+        context.uniform1f(painter.uniforms.fade.as_ref(), painter.fade);
+
+        // Clear
+        context.clear_color(0.1, 0.1, 0.1, 1.0);
+        context.clear(WebGlRenderingContext::COLOR_BUFFER_BIT);
+
+        // Upload
+        root_list.upload(context, painter.pixel_ratio);
+
+        // Draw
+        root_list.draw(context, painter);
+
+        // Overlays: edge scrims, then the spotlight on whatever is focused
+        if let Some(scrim) = painter.scrim {
+            scrim.draw(context, root_list.viewport_height);
+        }
+        if let (Some(spotlight), Some(center)) = (painter.spotlight, root_list.selected_center()) {
+            spotlight.draw(context, center);
+        }
+        if let Some(debug) = painter.debug {
+            debug.draw(context, root_list, painter.pixel_ratio);
+        }
+    }
+}
+
+impl GlState {
+    // Same context object, but every GL resource died with the old one: a fresh
+    // state (programs, uniforms, attributes), with the sizes and each pass'
+    // settings carried over
+    fn restore(&mut self, width: u32, height: u32) -> Result<(), JsValue> {
+        let watch = ContextWatch {
+            lost: self.context_watch.lost.clone(),
+            restored: self.context_watch.restored.clone(),
+        };
+        let mut fresh = GlState::build(self.context.clone(), &self.canvas, watch)?;
+        let context = &fresh.context;
+        let (w, h) = (width as f32, height as f32);
+
+        // A. Poster program: logical resolution + color correction
+        let program = fresh.shaders.get(context, ProgramKey::Textured)?;
         context.use_program(Some(&program));
+        context.uniform2f(context.get_uniform_location(&program, "u_resolution").as_ref(), w, h);
+        let [brightness, contrast, gamma] = self.color_adjust;
+        fresh.poster_uniforms.set_color_adjust(context, brightness, contrast, gamma);
+
+        // B. Passes that existed before
+        if let Some(old) = &self.shadow {
+            let mut pass = ShadowPass::new(context, fresh.shaders.get(context, ProgramKey::Shadow)?, w, h)?;
+            pass.selected_elevation = old.selected_elevation;
+            fresh.shadow = Some(pass);
+        }
+        if let Some(old) = &self.spotlight {
+            let mut pass = SpotlightPass::new(context, fresh.shaders.get(context, ProgramKey::Spotlight)?, w, h)?;
+            (pass.enabled, pass.intensity, pass.radius) = (old.enabled, old.intensity, old.radius);
+            fresh.spotlight = Some(pass);
+        }
+        if let Some(old) = &self.scrim {
+            let mut pass = ScrimPass::new(context, fresh.shaders.get(context, ProgramKey::Scrim)?, w, h)?;
+            (pass.top_height, pass.bottom_height, pass.color) = (old.top_height, old.bottom_height, old.color);
+            fresh.scrim = Some(pass);
+        }
+        if let Some(old) = &self.debug {
+            let mut pass = DebugOverlay::new(context, fresh.shaders.get(context, ProgramKey::Debug)?, w, h)?;
+            pass.enabled = old.enabled;
+            fresh.debug = Some(pass);
+        }
+        if let Some(old) = &self.blur {
+            // The frozen frame is gone, it's captured again on the next frame
+            let mut pass = BlurPass::new(context, fresh.shaders.get(context, ProgramKey::Blur)?, width, height)?;
+            (pass.radius, pass.freeze) = (old.radius, old.freeze);
+            fresh.blur = Some(pass);
+        }
+
+        // C. Sizes
+        fresh.viewport_rect = self.viewport_rect;
+        fresh.color_adjust = self.color_adjust;
+        fresh.native_size = self.native_size;
+        fresh.render_scale = self.render_scale;
+        *self = fresh;
+        Ok(())
+    }
+
+    // The viewport rect keeps layout 1:1: the GL viewport stays canvas-sized
+    // (so u_resolution still maps layout pixels) and is only shifted by the
+    // rect's corner, the scissor cuts everything off at the rect's edges
+    fn grid_area(&self, logical_w: u32, logical_h: u32) -> GridArea {
+        grid_area((self.canvas.width(), self.canvas.height()), (logical_w, logical_h), self.viewport_rect)
+    }
+
+    // Drawing buffer = native_size * render_scale
+    fn apply_buffer_size(&mut self) {
+        let (w, h) = scaled_size(self.native_size, self.render_scale);
+        self.canvas.set_width(w);
+        self.canvas.set_height(h);
+        if let Some(blur) = &mut self.blur {
+            blur.invalidate(); // A frozen frame from the old size is no good
+        }
+    }
 
-        // C. Configure Global GL State
+    fn new(context: WebGlRenderingContext, canvas: &HtmlCanvasElement) -> Result<Self, JsValue> {
+        let context_watch = ContextWatch::listen(canvas)?;
+        Self::build(context, canvas, context_watch)
+    }
+
+    fn build(context: WebGlRenderingContext, canvas: &HtmlCanvasElement, context_watch: ContextWatch) -> Result<Self, JsValue> {
+        // A. Setup Shaders (compiled lazily by the registry, helpers are at the bottom)
+        // Must be enabled BEFORE compiling so the poster shader sees the macro
+        let smooth_edges = context.get_extension("OES_standard_derivatives")?.is_some();
+        let mut shaders = ShaderRegistry::new();
+        let program = shaders.get(&context, ProgramKey::Textured)?;
+        context.use_program(Some(&program));
+        let poster_uniforms = PosterUniforms::new(&context, &program);
+
+        // B. Configure Global GL State
         let res_loc = context.get_uniform_location(&program, "u_resolution").expect("u_resolution missing");
         context.uniform2f(Some(&res_loc), canvas.width() as f32, canvas.height() as f32);
+        poster_uniforms.set_color_adjust(&context, 1.0, 1.0, 1.0);
         
         context.enable(WebGlRenderingContext::BLEND);
         context.blend_func(WebGlRenderingContext::SRC_ALPHA, WebGlRenderingContext::ONE_MINUS_SRC_ALPHA);
@@ -65,52 +1875,132 @@ impl GameEngine {
         context.enable_vertex_attrib_array(tex_loc as u32);
         context.vertex_attrib_pointer_with_i32(tex_loc as u32, 2, WebGlRenderingContext::FLOAT, false, stride, 8);
 
-        // D. Create The Game State
-        let mut texture_manager = TextureManager::new();
-        let mut root_list = ColumnList::new();
-        
-        // Load Assets
-        root_list.load_assets(&context, &mut texture_manager)?;
-
-        // Return the Struct to JS
-        Ok(GameEngine {
+        Ok(Self {
             context,
-            root_list,
-            texture_manager,
+            canvas: canvas.clone(),
+            native_size: (canvas.width(), canvas.height()),
+            render_scale: 1.0,
+            shaders,
+            poster_uniforms,
+            smooth_edges,
+            blur: None,
+            shadow: None,
+            spotlight: None,
+            scrim: None,
+            debug: None,
+            viewport_rect: None,
+            color_adjust: [1.0, 1.0, 1.0],
+            context_watch,
         })
     }
+}
 
-    // 3. The Bridge: Input (Called from JS) 🌉
-    pub fn send_key(&mut self, key_code: u32) {
-        self.root_list.handle_input(key_code);
+// One frame of model time: a step per elapsed MODEL_STEP_MS since the
+// previous frame (capped), or a single step without a timestamp
+fn step_model(root_list: &mut ColumnList, frame_time: Option<f64>) {
+    match frame_time {
+        Some(timestamp_ms) => {
+            root_list.update_at(timestamp_ms);
+        }
+        None => root_list.update_model(),
     }
+}
 
-    // 4. The Loop: Render (Called from JS requestAnimationFrame) 🔄
-    pub fn render(&mut self) {
-        // Clear
-        self.context.clear_color(0.1, 0.1, 0.1, 1.0);
-        self.context.clear(WebGlRenderingContext::COLOR_BUFFER_BIT);
+// 0xRRGGBB + alpha -> RGBA 0..1
+fn rgb(color: u32, alpha: f32) -> [f32; 4] {
+    let channel = |shift: u32| ((color >> shift) & 0xff) as f32 / 255.0;
+    [channel(16), channel(8), channel(0), alpha.clamp(0.0, 1.0)]
+}
+
+// Drawing buffer size for a native size at a render scale (never 0)
+fn scaled_size((w, h): (u32, u32), scale: f32) -> (u32, u32) {
+    let scaled = |n: u32| ((n as f32 * scale).round() as u32).max(1);
+    (scaled(w), scaled(h))
+}
 
-        // Update
-        self.root_list.update(&self.context);
+// CSS pixels -> (physical pixels, CSS size that maps onto them exactly).
+// e.g. 801 CSS px at DPR 1.5 = 1201.5 -> 1202 physical, shown at 801.333 CSS px.
+fn physical_size(css: f64, dpr: f64) -> (u32, f64) {
+    let dpr = if dpr.is_finite() && dpr > 0.0 { dpr } else { 1.0 };
+    let physical = ((css * dpr).round() as u32).max(1);
+    (physical, physical as f64 / dpr)
+}
 
-        // Draw
-        self.root_list.draw(&self.context);
+// Layout rect -> CSS pixels on the page: shifted by the viewport rect's
+// corner (grid coordinates start there), then scaled by however big CSS
+// made the canvas (`client`, 0 = unknown: 1:1) versus its layout size
+fn css_rect([x, y, w, h]: [f32; 4], viewport_rect: Option<[f32; 4]>, (client_w, client_h): (f32, f32), (logical_w, logical_h): (u32, u32)) -> [f32; 4] {
+    let sx = if client_w > 0.0 { client_w / logical_w as f32 } else { 1.0 };
+    let sy = if client_h > 0.0 { client_h / logical_h as f32 } else { 1.0 };
+    let [ox, oy, _, _] = viewport_rect.unwrap_or([0.0; 4]);
+    [(x + ox) * sx, (y + oy) * sy, w * sx, h * sy]
+}
+
+// GL viewport + scissor for a drawing buffer of `buffer` pixels showing a
+// `logical`-sized layout, optionally limited to `viewport_rect` (layout px)
+fn grid_area((buffer_w, buffer_h): (u32, u32), (logical_w, logical_h): (u32, u32), viewport_rect: Option<[f32; 4]>) -> GridArea {
+    let (buffer_w, buffer_h) = (buffer_w as i32, buffer_h as i32);
+    let Some([x, y, w, h]) = viewport_rect else {
+        return GridArea { viewport: [0, 0, buffer_w, buffer_h], scissor: None };
+    };
+    let sx = buffer_w as f32 / logical_w.max(1) as f32;
+    let sy = buffer_h as f32 / logical_h.max(1) as f32;
+    // GL counts y up from the bottom
+    let (left, top, right, bottom) = ((x * sx).round(), (y * sy).round(), ((x + w) * sx).round(), ((y + h) * sy).round());
+    GridArea {
+        viewport: [left as i32, -(top as i32), buffer_w, buffer_h],
+        scissor: Some([left as i32, buffer_h - bottom as i32, (right - left) as i32, (bottom - top) as i32]),
     }
 }
 
-// ... (Keep helper functions compile_shader and link_program exactly as they were) ...
-fn compile_shader(context: &WebGlRenderingContext, shader_type: u32, source: &str) -> Result<web_sys::WebGlShader, String> {
-    let shader = context.create_shader(shader_type).ok_or("Unable to create shader object")?;
+fn compile_shader(context: &WebGlRenderingContext, stage: ShaderStage, source: &str) -> Result<web_sys::WebGlShader, ShaderError> {
+    let shader = context.create_shader(stage.gl_type()).ok_or_else(|| ShaderError::Compile { stage, log: "Unable to create shader object".into() })?;
     context.shader_source(&shader, source);
     context.compile_shader(&shader);
-    if context.get_shader_parameter(&shader, WebGlRenderingContext::COMPILE_STATUS).as_bool().unwrap_or(false) { Ok(shader) } else { Err(context.get_shader_info_log(&shader).unwrap_or_else(|| "Unknown error".into())) }
+    if context.get_shader_parameter(&shader, WebGlRenderingContext::COMPILE_STATUS).as_bool().unwrap_or(false) { Ok(shader) } else { Err(ShaderError::Compile { stage, log: context.get_shader_info_log(&shader).unwrap_or_else(|| "Unknown error".into()) }) }
 }
 
-fn link_program(context: &WebGlRenderingContext, vert: &web_sys::WebGlShader, frag: &web_sys::WebGlShader) -> Result<web_sys::WebGlProgram, String> {
-    let program = context.create_program().ok_or("Unable to create shader program")?;
+fn link_program(context: &WebGlRenderingContext, vert: &web_sys::WebGlShader, frag: &web_sys::WebGlShader) -> Result<web_sys::WebGlProgram, ShaderError> {
+    let program = context.create_program().ok_or_else(|| ShaderError::Link { log: "Unable to create shader program".into() })?;
     context.attach_shader(&program, vert);
     context.attach_shader(&program, frag);
     context.link_program(&program);
-    if context.get_program_parameter(&program, WebGlRenderingContext::LINK_STATUS).as_bool().unwrap_or(false) { Ok(program) } else { Err(context.get_program_info_log(&program).unwrap_or_else(|| "Unknown error".into())) }
-}
\ No newline at end of file
+    if context.get_program_parameter(&program, WebGlRenderingContext::LINK_STATUS).as_bool().unwrap_or(false) { Ok(program) } else { Err(ShaderError::Link { log: context.get_program_info_log(&program).unwrap_or_else(|| "Unknown error".into()) }) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_render_scale_halves_the_drawing_buffer() {
+        assert_eq!(scaled_size((1920, 1080), 0.5), (960, 540));
+        assert_eq!(scaled_size((1921, 1081), 0.75), (1441, 811));
+        assert_eq!(scaled_size((1, 1), 0.1), (1, 1));
+    }
+
+    #[test]
+    fn fractional_dpr_rounds_the_buffer_and_matches_the_css_size() {
+        let (physical, css) = physical_size(801.0, 1.5);
+        assert_eq!(physical, 1202);
+        assert_eq!(css * 1.5, 1202.0); // No fractional leftover
+        assert_eq!(physical_size(1920.0, 1.0), (1920, 1920.0));
+        assert_eq!(physical_size(100.0, f64::NAN).0, 100);
+    }
+
+    #[test]
+    fn a_100px_inset_shifts_screen_rects_and_the_gl_area() {
+        let inset = Some([100.0, 100.0, 1720.0, 880.0]);
+        let poster = [50.0, 50.0, 300.0, 200.0];
+        assert_eq!(css_rect(poster, inset, (0.0, 0.0), (1920, 1080)), [150.0, 150.0, 300.0, 200.0]);
+        // Canvas shown at half size by CSS: everything halves, offset included
+        assert_eq!(css_rect(poster, inset, (960.0, 540.0), (1920, 1080)), [75.0, 75.0, 150.0, 100.0]);
+        assert_eq!(css_rect(poster, None, (0.0, 0.0), (1920, 1080)), poster);
+
+        // 2x drawing buffer: viewport moved by the corner, scissor cut to the rect
+        let area = grid_area((3840, 2160), (1920, 1080), inset);
+        assert_eq!(area.viewport, [200, -200, 3840, 2160]);
+        assert_eq!(area.scissor, Some([200, 2160 - 1960, 3440, 1760]));
+        assert_eq!(grid_area((1920, 1080), (1920, 1080), None), GridArea { viewport: [0, 0, 1920, 1080], scissor: None });
+    }
+}
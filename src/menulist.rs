@@ -0,0 +1,281 @@
+use std::rc::Rc;
+use web_sys::WebGlRenderingContext;
+
+use crate::animation::AnimationConfig;
+use crate::columnlist::BlockedEdge;
+use crate::geometry_cache::GeometryCache;
+use crate::keymap::Action;
+use crate::painter::Painter;
+use crate::posteritem::{PosterItem, IDENTITY};
+use crate::rowlist::RowList;
+use crate::texture_manager::TextureManager;
+
+// Menu geometry (logical pixels)
+pub const MENU_MARGIN_X: f32 = 160.0;
+pub const MENU_START_Y: f32 = 120.0;
+pub const MENU_ROW_HEIGHT: f32 = 96.0;
+pub const MENU_ROW_SPACING: f32 = 112.0; // Row height + gap
+const ICON_PADDING: f32 = 18.0;
+
+// One menu row: a full-width label bar, optionally with an icon at its left
+pub struct MenuItem {
+    pub label: String,
+    pub icon_src: Option<String>,
+    pub bar: PosterItem,          // Owns its label texture (see load_pending)
+    pub icon: Option<PosterItem>, // Shared texture from the TextureManager
+    dim: f32,                     // Eased highlight: 1 = focused
+    label_stale: bool,            // Bar resized: redraw the label texture
+    dim_velocity: f32,
+}
+
+// MENU LIST 📋
+// Settings / menu screens: a single column of full-width rows, one focusable
+// item each. Same machinery as the grid: PosterItem quads, AnimationConfig
+// easing for the scroll, the focused row highlighted by brightness.
+pub struct MenuList {
+    pub items: Vec<MenuItem>,
+    pub selected_index: usize,
+
+    pub scroll_y: f32,
+    pub target_scroll_y: f32,
+    scroll_velocity_y: f32,
+
+    pub viewport_width: f32,
+    pub viewport_height: f32,
+    pub animation: AnimationConfig,
+    pub inactive_dim: f32, // Brightness of the rows without focus
+    pub on_select: Option<js_sys::Function>, // (index, label) on Select
+    geometry: GeometryCache, // Every bar is the same size: one buffer (icons another)
+}
+
+impl MenuList {
+    // `items` = (label, optional icon src), top to bottom
+    pub fn new(items: Vec<(String, Option<String>)>) -> Self {
+        let mut menu = Self {
+            items: Vec::new(),
+            selected_index: 0,
+            scroll_y: 0.0,
+            target_scroll_y: 0.0,
+            scroll_velocity_y: 0.0,
+            viewport_width: 1920.0,
+            viewport_height: 1080.0,
+            animation: AnimationConfig::new(),
+            inactive_dim: 0.55,
+            on_select: None,
+            geometry: GeometryCache::new(),
+        };
+        menu.items = items
+            .into_iter()
+            .enumerate()
+            .map(|(i, (label, icon_src))| {
+                let y = MENU_START_Y + i as f32 * MENU_ROW_SPACING;
+                let bar = PosterItem::new(MENU_MARGIN_X, y, menu.bar_width(), MENU_ROW_HEIGHT, "", false);
+                let icon = icon_src.as_deref().map(|src| {
+                    let size = MENU_ROW_HEIGHT - ICON_PADDING * 2.0;
+                    PosterItem::new(MENU_MARGIN_X + ICON_PADDING, y + ICON_PADDING, size, size, src, false)
+                });
+                MenuItem {
+                    label,
+                    icon_src,
+                    bar,
+                    icon,
+                    dim: 1.0,
+                    label_stale: false,
+                    dim_velocity: 0.0,
+                }
+            })
+            .collect();
+        menu
+    }
+
+    fn bar_width(&self) -> f32 {
+        (self.viewport_width - MENU_MARGIN_X * 2.0).max(1.0)
+    }
+
+    // Up/Down move the focus (Home/End/Page jump); reports the blocked edge
+    // like ColumnList does. Left/Right do nothing in a single column.
+    pub fn handle_input(&mut self, action: Action) -> Option<BlockedEdge> {
+        let last = self.items.len().saturating_sub(1);
+        let page = ((self.viewport_height / MENU_ROW_SPACING).floor() as usize).max(1);
+        let mut blocked = None;
+        self.selected_index = match action {
+            Action::Up if self.selected_index == 0 => {
+                blocked = Some(BlockedEdge::Top);
+                0
+            }
+            Action::Up => self.selected_index - 1,
+            Action::Down if self.selected_index >= last => {
+                blocked = Some(BlockedEdge::Bottom);
+                last
+            }
+            Action::Down => self.selected_index + 1,
+            Action::PageUp => self.selected_index.saturating_sub(page),
+            Action::PageDown => (self.selected_index + page).min(last),
+            Action::Home => 0,
+            Action::End => last,
+            Action::Left | Action::Right | Action::Select => self.selected_index,
+        };
+        self.update_scroll_target();
+        blocked
+    }
+
+    // --- SCROLL: only as far as needed to keep the focused row on screen
+    // (with one MENU_START_Y margin at either edge) ---
+    fn update_scroll_target(&mut self) {
+        let top = MENU_START_Y + self.selected_index as f32 * MENU_ROW_SPACING;
+        let bottom = top + MENU_ROW_HEIGHT;
+        if top + self.target_scroll_y < MENU_START_Y {
+            self.target_scroll_y = MENU_START_Y - top;
+        } else if bottom + self.target_scroll_y > self.viewport_height - MENU_START_Y {
+            self.target_scroll_y = self.viewport_height - MENU_START_Y - bottom;
+        }
+        self.target_scroll_y = self.target_scroll_y.min(0.0);
+    }
+
+    pub fn set_viewport(&mut self, width: f32, height: f32) {
+        self.viewport_width = width;
+        self.viewport_height = height;
+        let bar_width = self.bar_width();
+        for item in &mut self.items {
+            if item.bar.w != bar_width {
+                item.bar.set_size(bar_width, MENU_ROW_HEIGHT);
+                item.label_stale = true; // Redrawn at the new size by load_pending
+            }
+        }
+        self.update_scroll_target();
+    }
+
+    // 1. Buffers + textures for rows that don't have them yet
+    pub fn load_pending(&mut self, context: &WebGlRenderingContext, manager: &mut TextureManager) -> Result<(), String> {
+        for item in &mut self.items {
+            if item.bar.buffer.is_none() {
+                item.bar.init_buffer(context, &mut self.geometry)?;
+            }
+            if item.label_stale {
+                if let Some(texture) = item.bar.texture.take() {
+                    context.delete_texture(Some(&texture));
+                }
+                item.label_stale = false;
+            }
+            if item.bar.texture.is_none() {
+                let indent = match &item.icon {
+                    Some(icon) => ICON_PADDING * 2.0 + icon.w,
+                    None => ICON_PADDING * 2.0,
+                };
+                let (w, h) = (item.bar.w.ceil() as u32, item.bar.h.ceil() as u32);
+                match crate::text_texture::render_bar(context, &item.label, w, h, indent) {
+                    Ok(bar) => item.bar.texture = Some(Rc::new(bar.texture)),
+                    Err(e) => web_sys::console::error_2(&"Menu label failed:".into(), &e),
+                }
+            }
+            if let Some(icon) = item.icon.as_mut().filter(|icon| icon.buffer.is_none()) {
+                icon.init_buffer(context, &mut self.geometry)?;
+                let size = icon.w.ceil() as u32;
+                let shared = manager.get_texture(context, &icon.src, size, size).map_err(|e| format!("{:?}", e))?;
+                icon.set_texture(shared.texture, shared.image);
+            }
+        }
+        Ok(())
+    }
+
+    // 2. Scroll + highlight easing (no GL)
+    pub fn update_model(&mut self) {
+        let anim = self.animation;
+        self.scroll_y = anim.scroll_y.step(self.scroll_y, self.target_scroll_y, &mut self.scroll_velocity_y, anim.snap_distance());
+        for (i, item) in self.items.iter_mut().enumerate() {
+            let target_dim = if i == self.selected_index { 1.0 } else { self.inactive_dim };
+            item.dim = anim.dim.step(item.dim, target_dim, &mut item.dim_velocity, 0.001).clamp(0.0, 1.0);
+            for quad in std::iter::once(&mut item.bar).chain(item.icon.as_mut()) {
+                quad.offset_y = self.scroll_y;
+                quad.update_model(&anim);
+            }
+        }
+    }
+
+    // 3. Upload what changed, then draw the rows on screen
+    pub fn upload(&mut self, context: &WebGlRenderingContext) {
+        for item in &mut self.items {
+            item.bar.upload(context, &mut self.geometry);
+            if let Some(icon) = &mut item.icon {
+                icon.upload(context, &mut self.geometry);
+            }
+        }
+        self.geometry.sweep(context);
+    }
+
+    pub fn draw(&self, context: &WebGlRenderingContext, painter: &Painter) {
+        let uniforms = painter.uniforms;
+        for item in &self.items {
+            let [_, y, _, h] = item.bar.current_rect();
+            if y + h < 0.0 || y > self.viewport_height {
+                continue; // CULLING ✂️
+            }
+            context.uniform1f(uniforms.dim.as_ref(), item.dim);
+            for quad in std::iter::once(&item.bar).chain(item.icon.as_ref()) {
+                if let (Some(texture), Some(buffer)) = (quad.draw_texture(), &quad.buffer) {
+                    quad.apply_uniforms(context, uniforms);
+                    RowList::draw_quad(context, uniforms, &IDENTITY, texture, buffer);
+                }
+            }
+        }
+    }
+
+    // Context lost: everything is rebuilt by the next load_pending
+    pub fn context_lost(&mut self) {
+        self.geometry = GeometryCache::new();
+        for item in &mut self.items {
+            item.bar.forget_gl();
+            if let Some(icon) = &mut item.icon {
+                icon.forget_gl();
+            }
+        }
+    }
+
+    // Give back every buffer and label texture (icons belong to the cache)
+    pub fn release(&mut self, context: &WebGlRenderingContext) {
+        for item in &mut self.items {
+            for quad in std::iter::once(&mut item.bar).chain(item.icon.as_mut()) {
+                quad.buffer = None;
+            }
+            if let Some(texture) = item.bar.texture.take() {
+                context.delete_texture(Some(&texture));
+            }
+        }
+        self.geometry.sweep(context);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu(count: usize) -> MenuList {
+        MenuList::new((0..count).map(|i| (format!("Item {}", i), None)).collect())
+    }
+
+    // Focused bar's top and bottom on screen once the scroll has landed
+    fn focused_span(menu: &MenuList) -> (f32, f32) {
+        let top = MENU_START_Y + menu.selected_index as f32 * MENU_ROW_SPACING + menu.target_scroll_y;
+        (top, top + MENU_ROW_HEIGHT)
+    }
+
+    #[test]
+    fn up_and_down_keep_the_focused_row_in_view() {
+        let mut menu = menu(30);
+        assert_eq!(menu.handle_input(Action::Up), Some(BlockedEdge::Top));
+        for _ in 0..20 {
+            menu.handle_input(Action::Down);
+            let (top, bottom) = focused_span(&menu);
+            assert!(top >= 0.0 && bottom <= menu.viewport_height);
+        }
+        assert_eq!(menu.selected_index, 20);
+        assert!(menu.target_scroll_y < 0.0);
+
+        for _ in 0..20 {
+            menu.handle_input(Action::Up);
+            let (top, bottom) = focused_span(&menu);
+            assert!(top >= 0.0 && bottom <= menu.viewport_height);
+        }
+        assert_eq!(menu.target_scroll_y, 0.0);
+    }
+}
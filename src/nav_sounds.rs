@@ -0,0 +1,103 @@
+use crate::columnlist::BlockedEdge;
+use crate::keymap::Action;
+use wasm_bindgen::JsValue;
+
+// NAV SOUNDS 🔊
+// Audio feedback hooks, JS plays the actual sounds. Only fed from key input
+// (GameEngine::apply_action), so focus_item, restore_state and the like stay
+// silent. Each callback gets { action, row, item } (+ edge for on_edge).
+pub struct NavSounds {
+    pub on_nav: Option<js_sys::Function>,    // Focus moved
+    pub on_select: Option<js_sys::Function>, // Select pressed on something
+    pub on_edge: Option<js_sys::Function>,   // A move that had nowhere to go
+}
+
+// Which sound one key asks for (at most one per key)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Cue {
+    Nav,
+    Select,
+    Edge(&'static str), // "top" / "bottom" / "start" / "end"
+}
+
+impl Cue {
+    // One key's outcome: focus `before` -> `after` ((row, item); the menu
+    // reports row 0), `blocked` = the grid boundary it ran into
+    pub fn of(action: Action, before: Option<(usize, usize)>, after: Option<(usize, usize)>, blocked: Option<BlockedEdge>) -> Option<Self> {
+        after?; // Nothing focused, nothing happened
+        Some(if action == Action::Select {
+            Cue::Select
+        } else if before != after {
+            Cue::Nav
+        } else {
+            Cue::Edge(match (blocked, action) {
+                (Some(BlockedEdge::Top), _) | (None, Action::Up | Action::PageUp) => "top",
+                (Some(BlockedEdge::Bottom), _) | (None, Action::Down | Action::PageDown) => "bottom",
+                (Some(BlockedEdge::Left), _) | (None, Action::Left | Action::Home) => "start",
+                (Some(BlockedEdge::Right), _) | (None, _) => "end",
+            })
+        })
+    }
+}
+
+impl NavSounds {
+    pub fn new() -> Self {
+        Self {
+            on_nav: None,
+            on_select: None,
+            on_edge: None,
+        }
+    }
+
+    // Fire the callback for one key (see Cue::of)
+    pub fn play(&self, action: Action, before: Option<(usize, usize)>, after: Option<(usize, usize)>, blocked: Option<BlockedEdge>) {
+        let (Some(cue), Some((row, item))) = (Cue::of(action, before, after, blocked), after) else {
+            return;
+        };
+        let (cb, edge) = match cue {
+            Cue::Nav => (&self.on_nav, None),
+            Cue::Select => (&self.on_select, None),
+            Cue::Edge(edge) => (&self.on_edge, Some(edge)),
+        };
+        let Some(cb) = cb else {
+            return;
+        };
+        if let Err(e) = event(action, row, item, edge).and_then(|event| cb.call1(&JsValue::NULL, &event)) {
+            web_sys::console::error_2(&"nav sound callback failed:".into(), &e);
+        }
+    }
+}
+
+fn event(action: Action, row: usize, item: usize, edge: Option<&str>) -> Result<JsValue, JsValue> {
+    let event = js_sys::Object::new();
+    js_sys::Reflect::set(&event, &"action".into(), &action.name().into())?;
+    js_sys::Reflect::set(&event, &"row".into(), &(row as u32).into())?;
+    js_sys::Reflect::set(&event, &"item".into(), &(item as u32).into())?;
+    if let Some(edge) = edge {
+        js_sys::Reflect::set(&event, &"edge".into(), &edge.into())?;
+    }
+    Ok(event.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::columnlist::ColumnList;
+
+    // Cue for pressing `action` on `list`
+    fn press(list: &mut ColumnList, action: Action) -> Option<Cue> {
+        let before = list.focused_item();
+        let blocked = list.handle_input(action);
+        Cue::of(action, before, list.focused_item(), blocked)
+    }
+
+    #[test]
+    fn a_move_plays_nav_and_a_blocked_edge_plays_edge_instead() {
+        let mut list = ColumnList::new();
+        assert_eq!(press(&mut list, Action::Right), Some(Cue::Nav));
+        assert_eq!(press(&mut list, Action::Left), Some(Cue::Nav));
+        assert_eq!(press(&mut list, Action::Left), Some(Cue::Edge("start")));
+        assert_eq!(press(&mut list, Action::Up), Some(Cue::Edge("top")));
+        assert_eq!(press(&mut list, Action::Select), Some(Cue::Select));
+    }
+}
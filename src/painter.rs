@@ -0,0 +1,66 @@
+use crate::debug_overlay::DebugOverlay;
+use crate::posteritem::PosterUniforms;
+use crate::scrim::ScrimPass;
+use crate::shadow::ShadowPass;
+use crate::spotlight::SpotlightPass;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{WebGlFramebuffer, WebGlProgram, WebGlRenderingContext};
+
+// Everything the draw chain (ColumnList -> RowList -> PosterItem) needs to
+// paint a frame. Optional passes are None until an app turns them on.
+pub struct Painter<'a> {
+    pub program: &'a WebGlProgram, // The poster program
+    pub uniforms: &'a PosterUniforms,
+    pub shadow: Option<&'a ShadowPass>,
+    pub scrim: Option<&'a ScrimPass>,         // Drawn over the finished grid
+    pub spotlight: Option<&'a SpotlightPass>, // ...then this
+    pub debug: Option<&'a DebugOverlay>,      // Topmost, development only
+    pub pixel_ratio: f32, // Drawing buffer pixels per layout pixel (DPR x render scale)
+    pub fade: f32,        // Whole-grid opacity (intro): u_fade, and custom tiles / shadows
+}
+
+impl Painter<'_> {
+    // ESCAPE HATCH 🪝 Let JS draw a custom tile (clock, live score...) with
+    // `draw(gl, { x, y, width, height, alpha, pixel_ratio })` at its rect in
+    // layout pixels. Whatever state JS touches, the poster pipeline gets put
+    // back afterwards. Returns false if the callback was skipped (invisible).
+    pub fn draw_custom(&self, context: &WebGlRenderingContext, draw: &js_sys::Function, rect: [f32; 4], alpha: f32) -> bool {
+        let alpha = alpha * self.fade;
+        if alpha <= 0.0 {
+            return false;
+        }
+        // 1. Save what JS could reasonably change under us
+        let framebuffer = context
+            .get_parameter(WebGlRenderingContext::FRAMEBUFFER_BINDING)
+            .ok()
+            .and_then(|fb| fb.dyn_into::<WebGlFramebuffer>().ok());
+        let viewport = context
+            .get_parameter(WebGlRenderingContext::VIEWPORT)
+            .ok()
+            .and_then(|v| v.dyn_into::<js_sys::Int32Array>().ok())
+            .map(|v| v.to_vec());
+
+        // 2. Call
+        let [x, y, w, h] = rect;
+        let info = js_sys::Object::new();
+        for (key, value) in [("x", x), ("y", y), ("width", w), ("height", h), ("alpha", alpha), ("pixel_ratio", self.pixel_ratio)] {
+            let _ = js_sys::Reflect::set(&info, &key.into(), &JsValue::from(value));
+        }
+        if let Err(e) = draw.call2(&JsValue::NULL, context.as_ref(), &info) {
+            web_sys::console::error_2(&"custom item draw failed:".into(), &e);
+        }
+
+        // 3. Restore
+        context.bind_framebuffer(WebGlRenderingContext::FRAMEBUFFER, framebuffer.as_ref());
+        if let Some([vx, vy, vw, vh]) = viewport.as_deref().and_then(|v| <[i32; 4]>::try_from(v).ok()) {
+            context.viewport(vx, vy, vw, vh);
+        }
+        context.use_program(Some(self.program));
+        context.enable(WebGlRenderingContext::BLEND);
+        context.blend_func(WebGlRenderingContext::SRC_ALPHA, WebGlRenderingContext::ONE_MINUS_SRC_ALPHA);
+        context.active_texture(WebGlRenderingContext::TEXTURE0);
+        context.enable_vertex_attrib_array(0);
+        context.enable_vertex_attrib_array(1);
+        true
+    }
+}
@@ -1,162 +1,864 @@
+use crate::animation::AnimationConfig;
+use crate::geometry_cache::{geometry_key, GeometryCache, GeometryKey};
+use crate::text_texture::{render_text, TextTexture};
+use crate::texture_manager::{SharedTexture, TextureManager};
+use wasm_bindgen::JsValue;
 use std::rc::Rc;
-use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsCast;
-use web_sys::{WebGlRenderingContext, WebGlTexture, HtmlImageElement, WebGlBuffer};
+use web_sys::{WebGlRenderingContext, WebGlTexture, HtmlImageElement, WebGlBuffer, WebGlProgram, WebGlUniformLocation};
+
+// Per-item uniforms of the poster program (looked up once, reused every draw)
+pub struct PosterUniforms {
+    pub rect: Option<WebGlUniformLocation>,
+    pub radii: Option<WebGlUniformLocation>, // Corner radii: top-left, top-right, bottom-right, bottom-left
+    pub dim: Option<WebGlUniformLocation>, // Per-row brightness (set by RowList)
+    pub alpha: Option<WebGlUniformLocation>, // Per-item opacity (enter/exit fades)
+    pub transform: Option<WebGlUniformLocation>, // Pixel-space mat4 (identity unless coverflow)
+    pub reflection: Option<WebGlUniformLocation>, // (opacity, fade height px); 0 = normal draw
+    pub scale: Option<WebGlUniformLocation>, // Selection / enter / exit scale
+    pub offset: Option<WebGlUniformLocation>, // Item center incl. scroll (pixels)
+    pub color_adjust: Option<WebGlUniformLocation>, // Global (brightness, contrast, gamma)
+    pub playing: Option<WebGlUniformLocation>, // 1 = draw the "now playing" equalizer badge
+    pub tint: Option<WebGlUniformLocation>,    // Per-item RGBA multiply
+    pub time: Option<WebGlUniformLocation>,    // Seconds of the item's own clock, animates the badge
+    pub fade: Option<WebGlUniformLocation>,    // Whole-grid opacity (intro), once per frame
+}
+
+impl PosterUniforms {
+    pub fn new(context: &WebGlRenderingContext, program: &WebGlProgram) -> Self {
+        Self {
+            rect: context.get_uniform_location(program, "u_rect"),
+            radii: context.get_uniform_location(program, "u_radii"),
+            dim: context.get_uniform_location(program, "u_dim"),
+            alpha: context.get_uniform_location(program, "u_alpha"),
+            transform: context.get_uniform_location(program, "u_transform"),
+            reflection: context.get_uniform_location(program, "u_reflection"),
+            scale: context.get_uniform_location(program, "u_scale"),
+            offset: context.get_uniform_location(program, "u_offset"),
+            color_adjust: context.get_uniform_location(program, "u_color_adjust"),
+            playing: context.get_uniform_location(program, "u_playing"),
+            tint: context.get_uniform_location(program, "u_tint"),
+            time: context.get_uniform_location(program, "u_time"),
+            fade: context.get_uniform_location(program, "u_fade"),
+        }
+    }
+
+    // 1.0 each = the image as-is. Uniforms stick to the program, so this
+    // only needs calling when the values change (program must be in use).
+    pub fn set_color_adjust(&self, context: &WebGlRenderingContext, brightness: f32, contrast: f32, gamma: f32) {
+        context.uniform3f(self.color_adjust.as_ref(), brightness, contrast, gamma);
+    }
+}
+
+// Scale a selected poster grows to
+pub const SELECTED_SCALE: f32 = 1.2;
+
+// ...and a hero card (selected long enough, see hero.rs)
+pub const HERO_SCALE: f32 = 1.6;
+
+// COVERFLOW 📀 Eye distance for the perspective (pixels; smaller = stronger)
+const PERSPECTIVE_DISTANCE: f32 = 1200.0;
+
+pub const IDENTITY: [f32; 16] = [
+    1.0, 0.0, 0.0, 0.0,
+    0.0, 1.0, 0.0, 0.0,
+    0.0, 0.0, 1.0, 0.0,
+    0.0, 0.0, 0.0, 1.0,
+];
+
+// CAPTIONS 🏷️ Label text size and distance under the poster (layout pixels)
+const LABEL_FONT_PX: f32 = 22.0;
+const LABEL_GAP: f32 = 8.0;
+
+// Two triangles, w x h centered on the origin, sampling `uv`
+pub fn quad_vertices(w: f32, h: f32, [u0, v0, u1, v1]: [f32; 4]) -> Vec<f32> {
+    let (x2, y2) = (w / 2.0, h / 2.0);
+    let (x, y) = (-x2, -y2);
+    vec![
+        x,  y,   u0, v0,
+        x,  y2,  u0, v1,
+        x2, y,   u1, v0,
+        x2, y,   u1, v0,
+        x,  y2,  u0, v1,
+        x2, y2,  u1, v1,
+    ]
+}
+
+// a * b for column-major 4x4 matrices (b is applied first)
+pub fn mat4_mul(a: &[f32; 16], b: &[f32; 16]) -> [f32; 16] {
+    let mut out = [0.0; 16];
+    for col in 0..4 {
+        for row in 0..4 {
+            out[col * 4 + row] = (0..4).map(|k| a[k * 4 + row] * b[col * 4 + k]).sum();
+        }
+    }
+    out
+}
+
+// COVER CROP ✂️ When a fixed box (resize_contain off) doesn't match the
+// image's aspect, the image is cropped to fill it; this picks which part stays
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CoverAnchor {
+    Center,
+    Top, // Posters usually have the faces up here
+    Bottom,
+    Left,
+    Right,
+}
+
+impl CoverAnchor {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "center" => Some(CoverAnchor::Center),
+            "top" => Some(CoverAnchor::Top),
+            "bottom" => Some(CoverAnchor::Bottom),
+            "left" => Some(CoverAnchor::Left),
+            "right" => Some(CoverAnchor::Right),
+            _ => None,
+        }
+    }
+
+    // Same thing for the DOM fallback's object-position
+    pub fn css(self) -> &'static str {
+        match self {
+            CoverAnchor::Center => "center",
+            CoverAnchor::Top => "top",
+            CoverAnchor::Bottom => "bottom",
+            CoverAnchor::Left => "left",
+            CoverAnchor::Right => "right",
+        }
+    }
+
+    // Where the crop window sits inside the image on each axis (0 = start, 1 = end)
+    fn alignment(self) -> (f32, f32) {
+        match self {
+            CoverAnchor::Center => (0.5, 0.5),
+            CoverAnchor::Top => (0.5, 0.0),
+            CoverAnchor::Bottom => (0.5, 1.0),
+            CoverAnchor::Left => (0.0, 0.5),
+            CoverAnchor::Right => (1.0, 0.5),
+        }
+    }
+}
+
+// COVER CROP ✂️ Window [u0, v0, u1, v1] (0..1) of an image with
+// `image_aspect` that fills a box of `box_aspect`, placed at `anchor`
+fn cover_crop(image_aspect: f32, box_aspect: f32, anchor: CoverAnchor) -> [f32; 4] {
+    let (ax, ay) = anchor.alignment();
+    if image_aspect > box_aspect {
+        // Too wide: show a vertical strip
+        let visible = box_aspect / image_aspect;
+        let u0 = (1.0 - visible) * ax;
+        [u0, 0.0, u0 + visible, 1.0]
+    } else {
+        // Too tall: show a horizontal band
+        let visible = image_aspect / box_aspect;
+        let v0 = (1.0 - visible) * ay;
+        [0.0, v0, 1.0, v0 + visible]
+    }
+}
+
+// What a grid cell shows
+#[derive(Clone, PartialEq, Debug)]
+pub enum ItemKind {
+    Poster,         // Image from `src`
+    SeeAll(String), // Trailing "See All →" tile: label on a panel, no image
+    Custom(js_sys::Function), // JS draws it every frame (see Painter::draw_custom)
+}
 
 pub struct PosterItem {
-    pub x: f32,
+    pub x: f32, // Animated position (what's drawn this frame)
     pub y: f32,
+    target_x: f32, // Logical slot: x/y glide here in update_model (set via set_position / glide_to)
+    target_y: f32,
     pub w: f32,
     pub h: f32,
     pub src: String,
+    pub srcset: Vec<(String, u32)>, // Same image at several widths (url, px): picked by drawn size, src unused
+    srcset_width: u32,              // Width of the candidate requested last
+    pub id: String, // App's own key for this tile (content id, deep link...), "" = none
+    pub kind: ItemKind,
     
     // Flags
     pub resize_contain: bool,
     pub is_selected: bool,
+    pub corner_radii: [f32; 4], // Top-left, top-right, bottom-right, bottom-left (0 = square)
+    pub cover_anchor: CoverAnchor,
+    pub uv_rect: [f32; 4],  // Part of the image that is this poster (a sprite sheet cell; all of it by default)
+    uv: [f32; 4],           // Texture window [u0, v0, u1, v1] (the cover crop)
+    pub elevation: f32,     // Shadow depth (0 = flat, no shadow)
+    pub enabled: bool,      // false = unavailable (geo-blocked, expired): ghosted, Select does nothing
+    pub playing: bool,      // "Now playing" badge, independent of focus
+    animation_time: f32,    // Seconds of its own effects so far (u_time), stops while paused
+    animation_paused: bool, // Effects frozen (covered by a modal...), see set_animation_paused
+    pub tint: [f32; 4],     // RGBA multiply (e.g. greyed out once watched), white = none
+    pub label: String,      // Caption under the poster, "" = none (see set_label)
+    pub disabled_opacity: f32,
 
     // ANIMATION & SCROLL STATE 🎬
     pub anim_scale: f32,
+    scale_velocity: f32,    // Easing state for anim_scale (springs need it)
+    rest_scale: Option<f32>, // anim_scale rounded to whole physical pixels, once at rest (scale_pixel_snap)
+    focus_deferred: bool,   // Selected, but waiting for the image before popping
+    pub hero: bool,         // Expanded into a hero card (scale + hero art)
+    pub alpha: f32,         // 1 = opaque, fades to 0 while exiting
+    glide_velocity: [f32; 2], // Easing state for x / y (anim.glide)
+    fade_velocity: f32,     // Easing state for alpha (anim.fade)
+    pub exiting: bool,      // Filtered out: shrinking + fading, removed once gone
     pub offset_x: f32,      // Horizontal Scroll (From RowList)
+    edge_nudge: [f32; 2],   // Shift that keeps the scaled-up poster on screen (see fit_onscreen)
     pub offset_y: f32,      // NEW: Vertical Scroll (From ColumnList)
     
-    // Optimization State
-    prev_is_selected: bool, 
-    prev_offset_x: f32,
-    prev_offset_y: f32,     // NEW: Track changes
+    // Optimization State: the buffer only holds the w x h quad around the
+    // origin. Position, scroll and scale are uniforms, so only a size change
+    // (resize_contain) ever re-uploads vertices.
+    geometry_dirty: bool,   // Vertices must be re-uploaded on the next upload()
+    dirty: bool,            // Looks different than in the last drawn frame (dirty-flag rendering)
 
     // Assets
     pub texture: Option<Rc<WebGlTexture>>, 
     pub image_element: Option<Rc<HtmlImageElement>>,
-    pub buffer: Option<WebGlBuffer>, 
+    pub hero_art: Option<(Rc<WebGlTexture>, Rc<HtmlImageElement>)>, // Wide art while `hero`
+    next_texture: Option<(Rc<WebGlTexture>, Rc<HtmlImageElement>)>, // Replaces `texture` once loaded
+    pub evicted: bool, // Texture given up for the VRAM budget: reloaded once visible again
+    pub buffer: Option<Rc<WebGlBuffer>>, // Shared with every item of the same geometry (GeometryCache)
+    buffer_key: GeometryKey, // What `buffer` holds
+    label_art: Option<(TextTexture, Rc<WebGlBuffer>)>, // Rendered `label` + its quad
+    label_stale: bool,      // `label` changed since label_art was made
 }
 
 impl PosterItem {
     pub fn new(x: f32, y: f32, w: f32, h: f32, src: &str, resize_contain: bool) -> Self {
         Self {
             x, y, w, h,
+            target_x: x,
+            target_y: y,
             src: src.to_string(),
+            srcset: Vec::new(),
+            srcset_width: 0,
+            id: String::new(),
+            kind: ItemKind::Poster,
             resize_contain,
             is_selected: false,
+            corner_radii: [0.0; 4],
+            cover_anchor: CoverAnchor::Center,
+            uv_rect: [0.0, 0.0, 1.0, 1.0],
+            uv: [0.0, 0.0, 1.0, 1.0],
+            elevation: 0.0,
+            enabled: true,
+            playing: false,
+            animation_time: 0.0,
+            animation_paused: false,
+            tint: [1.0; 4],
+            label: String::new(),
+            disabled_opacity: 0.4,
             anim_scale: 1.0,
+            scale_velocity: 0.0,
+            rest_scale: None,
+            focus_deferred: false,
+            hero: false,
+            alpha: 1.0,
+            glide_velocity: [0.0, 0.0],
+            fade_velocity: 0.0,
+            exiting: false,
             
             offset_x: 0.0,
+            edge_nudge: [0.0, 0.0],
             offset_y: 0.0, // Start at 0
             
-            prev_offset_x: 0.0,
-            prev_offset_y: 0.0,
-            prev_is_selected: false,
+            geometry_dirty: false,
+            dirty: true,
 
             texture: None,
             image_element: None,
+            hero_art: None,
+            next_texture: None,
+            evicted: false,
             buffer: None,
+            buffer_key: [0; 6],
+            label_art: None,
+            label_stale: false,
         }
     }
 
-    // 1. Init Buffer (Standard)
-    pub fn init_buffer(&mut self, context: &WebGlRenderingContext) -> Result<(), String> {
-        let buffer = context.create_buffer().ok_or("Failed to create buffer")?;
-        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&buffer));
-        let vertices = self.create_rect();
-        unsafe {
-            let vert_array = js_sys::Float32Array::from(vertices.as_slice());
-            context.buffer_data_with_array_buffer_view(WebGlRenderingContext::ARRAY_BUFFER, &vert_array, WebGlRenderingContext::STATIC_DRAW);
-        }
-        self.buffer = Some(buffer);
+    // Key of the vertex buffer this item draws with (equal keys share one)
+    pub fn geometry_key(&self) -> GeometryKey {
+        geometry_key(self.w, self.h, self.uv)
+    }
+
+    // 1. Init Buffer (shared with any item of identical geometry)
+    pub fn init_buffer(&mut self, context: &WebGlRenderingContext, geometry: &mut GeometryCache) -> Result<(), String> {
+        let key = self.geometry_key();
+        self.buffer = Some(geometry.get(context, key, &self.create_rect())?);
+        self.buffer_key = key;
+        self.geometry_dirty = false;
         Ok(())
     }
 
+    // New box size: re-uploads the vertices on the next upload()
+    pub fn set_size(&mut self, w: f32, h: f32) {
+        if (w, h) != (self.w, self.h) {
+            self.w = w;
+            self.h = h;
+            self.geometry_dirty = true;
+            self.mark_dirty();
+        }
+    }
+
+    // Jump straight to a slot, no glide
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        self.target_x = x;
+        self.target_y = y;
+        self.x = x;
+        self.y = y;
+        self.glide_velocity = [0.0, 0.0];
+        self.mark_dirty();
+    }
+
+    // Same as set_position, but update_model slides the item there
+    pub fn glide_to(&mut self, x: f32, y: f32) {
+        if (x, y) != (self.target_x, self.target_y) {
+            self.target_x = x;
+            self.target_y = y;
+            self.mark_dirty();
+        }
+    }
+
+    // The slot it's in or gliding to (x is what's drawn this frame)
+    pub fn target_x(&self) -> f32 {
+        self.target_x
+    }
+
+    // Skip the selection pop: jump straight to the selected/unselected scale
+    pub fn settle_selection(&mut self, selected: bool) {
+        self.is_selected = selected;
+        self.anim_scale = if selected { SELECTED_SCALE } else { 1.0 };
+        self.scale_velocity = 0.0;
+        self.mark_dirty();
+    }
+
+    // "See All" tile with `label` (sized like a default poster)
+    pub fn see_all(label: &str) -> Self {
+        let mut item = Self::new(0.0, 0.0, crate::rowlist::ITEM_WIDTH, crate::rowlist::ITEM_HEIGHT, "", false);
+        item.kind = ItemKind::SeeAll(label.to_string());
+        item
+    }
+
+    // Real image decoded (false while the placeholder is showing, or after an error)
+    pub fn is_loaded(&self) -> bool {
+        self.image_element.as_deref().is_some_and(crate::texture_manager::is_loaded)
+    }
+
+    // Entrance: start invisible + tiny, update_model grows it back in
+    pub fn enter(&mut self) {
+        self.exiting = false;
+        self.alpha = 0.0;
+        self.fade_velocity = 0.0;
+        self.anim_scale = 0.0;
+        self.mark_dirty();
+    }
+
+    // Exit finished: safe to take out of the row
+    pub fn is_gone(&self) -> bool {
+        self.exiting && self.alpha <= 0.01
+    }
+
     // 2. Set Texture (Standard)
     pub fn set_texture(&mut self, texture: Rc<WebGlTexture>, image: Rc<HtmlImageElement>) {
         self.texture = Some(texture);
         self.image_element = Some(image);
+        self.evicted = false;
+        self.mark_dirty();
+    }
+
+    // Ask the manager for this poster: src, or the srcset candidate for its
+    // size at the target scale (a focused poster asks for its popped size)
+    pub fn request_texture(&mut self, context: &WebGlRenderingContext, manager: &mut TextureManager) -> Result<SharedTexture, JsValue> {
+        let (w, h) = (self.w.ceil() as u32, self.h.ceil() as u32);
+        if self.srcset.is_empty() {
+            return manager.get_texture(context, &self.src, w, h);
+        }
+        let target = (self.w * self.target_scale()).ceil() as u32;
+        let (shared, width) = manager.get_texture_srcset(context, &self.srcset, target, h)?;
+        self.srcset_width = width;
+        Ok(shared)
+    }
+
+    // The srcset has a sharper candidate than the loaded one for the size
+    // it's heading to (scaling up only: never swaps down again)
+    pub fn wants_sharper(&self, manager: &TextureManager) -> bool {
+        let target = (self.w * self.target_scale()).ceil() as u32;
+        manager.pick_srcset(&self.srcset, target).is_some_and(|(_, width)| *width > self.srcset_width)
+    }
+
+    // New srcset: swapped in (at the right candidate) once on screen, the
+    // current texture stays up meanwhile
+    pub fn set_srcset(&mut self, srcset: Vec<(String, u32)>) {
+        self.srcset = srcset;
+        self.srcset_width = 0;
+    }
+
+    // Every texture this item may draw (poster, incoming replacement, hero art)
+    pub fn textures(&self) -> impl Iterator<Item = &Rc<WebGlTexture>> {
+        self.texture.iter().chain(self.next_texture.iter().map(|(t, _)| t)).chain(self.hero_art.iter().map(|(t, _)| t))
+    }
+
+    // Let go of the poster texture so the manager can delete it. Like a fresh
+    // item again (no buffer), but load_pending leaves it alone until it's
+    // back on screen.
+    pub fn evict_texture(&mut self) {
+        self.texture = None;
+        self.image_element = None;
+        self.buffer = None;
+        self.evicted = true;
+        self.mark_dirty();
+    }
+
+    // Same poster at another size (render scale change): the current texture
+    // stays up until this one has loaded, no placeholder flash
+    pub fn replace_texture(&mut self, texture: Rc<WebGlTexture>, image: Rc<HtmlImageElement>) {
+        self.next_texture = Some((texture, image));
+    }
+
+    // The GL context was lost: every handle here is dead (not deleted, there's
+    // nothing left to delete). load_pending / upload_label build them again.
+    pub fn forget_gl(&mut self) {
+        self.buffer = None;
+        self.geometry_dirty = false;
+        self.texture = None;
+        self.next_texture = None;
+        self.hero_art = None;
+        self.label_art = None;
+        self.label_stale = !self.label.is_empty();
+        self.mark_dirty();
+    }
+
+    // REDRAW FLAG 🚩 Set by anything that changes how the item looks (setters,
+    // running animations, the image arriving); cleared once a frame is drawn
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    // Changes every frame without anything being set: the playing badge, and
+    // custom tiles (can't tell what JS will draw). Keeps dirty rendering drawing.
+    pub fn is_always_animating(&self) -> bool {
+        !self.animation_paused && (self.playing || matches!(self.kind, ItemKind::Custom(_)))
+    }
+
+    // Freeze this item's self-running effects (playing badge) where they
+    // are, and stop it keeping dirty rendering awake; the rest of the grid
+    // carries on. Glides / fades / focus pops still run.
+    pub fn set_animation_paused(&mut self, paused: bool) {
+        if self.animation_paused != paused {
+            self.animation_paused = paused;
+            self.dirty = true;
+        }
+    }
+
+    pub fn clear_dirty(&mut self) {
+        self.dirty = false;
+    }
+
+    // Everything update_model can change on screen
+    fn visual_state(&self) -> ([f32; 4], f32, bool, bool) {
+        (self.current_rect(), self.alpha, self.is_loaded(), self.hero_art_ready())
+    }
+
+    // Hero art in, or back out (drops the texture so the cache can free it)
+    pub fn set_hero(&mut self, hero: Option<(Rc<WebGlTexture>, Rc<HtmlImageElement>)>, expanded: bool) {
+        self.hero = expanded;
+        self.hero_art = hero;
+        self.mark_dirty();
+    }
+
+    fn hero_art_ready(&self) -> bool {
+        self.hero && self.hero_art.as_ref().is_some_and(|(_, img)| crate::texture_manager::is_loaded(img))
+    }
+
+    // What to bind this frame: hero art once it has arrived, else the poster
+    pub fn draw_texture(&self) -> Option<&WebGlTexture> {
+        if self.hero_art_ready() {
+            return self.hero_art.as_ref().map(|(texture, _)| &**texture);
+        }
+        self.texture.as_deref()
     }
 
     // 3. UPDATE LOOP 🔄
-    pub fn update(&mut self, context: &WebGlRenderingContext) {
-        let mut needs_upload = false;
+    // 3a. Animation + layout only (no GL needed; also drives the DOM fallback)
+    pub fn update_model(&mut self, anim: &AnimationConfig) {
+        let before = self.visual_state();
+
+        // A0. Swap in a replacement texture once it has arrived
+        if self.next_texture.as_ref().is_some_and(|(_, img)| crate::texture_manager::is_loaded(img)) {
+            if let Some((texture, image)) = self.next_texture.take() {
+                self.set_texture(texture, image);
+            }
+        }
 
         // A. Resize Logic
         if self.resize_contain {
-            if let Some(img) = &self.image_element {
-                let img_w = img.natural_width() as f32;
-                let img_h = img.natural_height() as f32;
+            if let Some((img_w, img_h)) = self.source_size() {
                 if img_w > 0.0 && img_h > 0.0 {
                     let ratio = img_h / img_w;
                     if (self.h - (self.w * ratio)).abs() > 0.01 {
                          self.h = self.w * ratio;
                          self.resize_contain = false;
-                         needs_upload = true;
+                         self.geometry_dirty = true; // The only change that touches vertices
                     }
                 }
             }
         }
 
-        // B. SCROLL CHECK (X and Y) 📜
-        if (self.offset_x - self.prev_offset_x).abs() > 0.1 {
-            needs_upload = true;
-            self.prev_offset_x = self.offset_x;
+        // A2. COVER CROP (only re-uploads when the window actually moves)
+        let uv = self.cover_uv();
+        if uv != self.uv {
+            self.uv = uv;
+            self.geometry_dirty = true;
         }
-        // NEW: Check Vertical Scroll
-        if (self.offset_y - self.prev_offset_y).abs() > 0.1 {
-            needs_upload = true;
-            self.prev_offset_y = self.offset_y;
+
+        // B. SCROLL: nothing to do, offset_x/offset_y go straight into u_offset 📜
+
+        // C. POSITION GLIDE (reflow: filtering, removal, relayout; anim.glide)
+        let [vx, vy] = &mut self.glide_velocity;
+        self.x = anim.glide.step(self.x, self.target_x, vx, anim.snap_distance());
+        self.y = anim.glide.step(self.y, self.target_y, vy, anim.snap_distance());
+
+        // D. FADE (in after enter(), out while exiting; anim.fade)
+        let target_alpha = self.target_alpha();
+        self.alpha = anim.fade.step(self.alpha, target_alpha, &mut self.fade_velocity, 0.01).clamp(0.0, 1.0);
+
+        // D2. Own effects clock: a model step's worth, unless paused
+        if !self.animation_paused {
+            self.animation_time += (crate::columnlist::MODEL_STEP_MS / 1000.0) as f32;
         }
 
-        // C. ANIMATION LOGIC (LERP)
-        let target_scale = if self.is_selected { 1.2 } else { 1.0 };
-        let diff = target_scale - self.anim_scale;
+        // E. ANIMATION LOGIC (scale easing, see AnimationConfig; drawn via u_scale)
+        self.focus_deferred = anim.defer_focus_until_loaded
+            && self.is_selected
+            && matches!(self.kind, ItemKind::Poster)
+            && !self.is_loaded();
+        let target_scale = self.target_scale();
+        self.anim_scale = anim.scale.step(self.anim_scale, target_scale, &mut self.scale_velocity, 0.001);
+        if self.exiting {
+            self.anim_scale = self.anim_scale.max(0.0); // A spring must not flip it inside out
+        }
+        // E2. PIXEL SNAP: once the pop has landed, draw it a whole number of
+        // physical pixels wide (fractional while moving, or it would judder)
+        let at_rest = self.anim_scale == target_scale && self.scale_velocity == 0.0;
+        self.rest_scale = (anim.scale_pixel_snap && at_rest && self.w > 0.0).then(|| {
+            let dpr = anim.device_pixel_ratio.max(0.01);
+            (self.w * self.anim_scale * dpr).round() / (self.w * dpr)
+        });
 
-        if diff.abs() > 0.001 {
-            self.anim_scale += diff * 0.15;
-            needs_upload = true; 
+        // F. Anything moved, faded or finished loading -> redraw
+        if self.geometry_dirty || self.visual_state() != before {
+            self.dirty = true;
+        }
+    }
+
+    // Jump straight to where the animations are heading (glide, fade, scale)
+    pub fn settle_now(&mut self) {
+        self.x = self.target_x;
+        self.y = self.target_y;
+        self.glide_velocity = [0.0, 0.0];
+        self.alpha = self.target_alpha();
+        self.fade_velocity = 0.0;
+        self.anim_scale = self.target_scale();
+        self.scale_velocity = 0.0;
+        self.dirty = true;
+    }
+
+    fn target_alpha(&self) -> f32 {
+        if self.exiting { 0.0 } else { 1.0 }
+    }
+
+    fn target_scale(&self) -> f32 {
+        if self.exiting {
+            0.0
+        } else if self.focus_deferred {
+            1.0
+        } else if self.is_selected && self.hero {
+            HERO_SCALE
+        } else if self.is_selected {
+            SELECTED_SCALE
         } else {
-            if self.anim_scale != target_scale {
-                self.anim_scale = target_scale;
-                needs_upload = true;
+            1.0
+        }
+    }
+
+    // Every animation (glide, fade, scale) has arrived
+    pub fn is_settled(&self) -> bool {
+        self.x == self.target_x
+            && self.y == self.target_y
+            && self.glide_velocity == [0.0, 0.0]
+            && self.alpha == self.target_alpha()
+            && self.fade_velocity == 0.0
+            && self.anim_scale == self.target_scale()
+            && self.scale_velocity == 0.0
+    }
+
+    // 3b. UPLOAD (only when the size changed)
+    pub fn upload(&mut self, context: &WebGlRenderingContext, geometry: &mut GeometryCache) {
+        if !self.geometry_dirty {
+            return;
+        }
+        if let Some(buffer) = self.buffer.take() {
+            let key = self.geometry_key();
+            match geometry.replace(context, buffer, self.buffer_key, key, &self.create_rect()) {
+                Ok(buffer) => {
+                    self.buffer = Some(buffer);
+                    self.buffer_key = key;
+                }
+                Err(e) => web_sys::console::error_1(&format!("Buffer error: {}", e).into()),
             }
+            self.geometry_dirty = false;
         }
+    }
 
-        // D. UPLOAD
-        if needs_upload {
-             if let Some(buffer) = &self.buffer {
-                 context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(buffer));
-                 let vertices = self.create_rect();
-                 unsafe {
-                     let vert_array = js_sys::Float32Array::from(vertices.as_slice());
-                     context.buffer_data_with_array_buffer_view(WebGlRenderingContext::ARRAY_BUFFER, &vert_array, WebGlRenderingContext::DYNAMIC_DRAW);
-                 }
-             }
+    // Caption text: rebuilt by upload_label on the next upload
+    pub fn set_label(&mut self, label: &str) {
+        if self.label != label {
+            self.label = label.to_string();
+            self.label_stale = true;
+            self.mark_dirty();
         }
     }
 
-    // 4. Geometry Generator (Uses offset_x AND offset_y!)
-    pub fn create_rect(&self) -> Vec<f32> {
-        let scale = self.anim_scale;
-        let center_x = self.x + (self.w / 2.0);
-        let center_y = self.y + (self.h / 2.0);
+    // 3c. (Re)render the caption if the text or the pixel ratio changed
+    pub fn upload_label(&mut self, context: &WebGlRenderingContext, geometry: &mut GeometryCache, pixel_ratio: f32) {
+        let wrong_ratio = self.label_art.as_ref().is_some_and(|(text, _)| text.pixel_ratio != pixel_ratio);
+        if !self.label_stale && !wrong_ratio {
+            return;
+        }
+        self.label_stale = false;
+        self.release_label(context);
+        if self.label.is_empty() {
+            return;
+        }
+        let text = match render_text(context, &self.label, LABEL_FONT_PX, pixel_ratio) {
+            Ok(text) => text,
+            Err(e) => {
+                web_sys::console::error_2(&"Label failed:".into(), &e);
+                return;
+            }
+        };
+        let full = [0.0, 0.0, 1.0, 1.0];
+        let key = geometry_key(text.width, text.height, full);
+        match geometry.get(context, key, &quad_vertices(text.width, text.height, full)) {
+            Ok(buffer) => self.label_art = Some((text, buffer)),
+            Err(e) => {
+                context.delete_texture(Some(&text.texture));
+                web_sys::console::error_1(&format!("Buffer error: {}", e).into());
+            }
+        }
+    }
+
+    // Free the caption texture (its buffer belongs to the GeometryCache)
+    pub fn release_label(&mut self, context: &WebGlRenderingContext) {
+        if let Some((text, _)) = self.label_art.take() {
+            context.delete_texture(Some(&text.texture));
+        }
+    }
+
+    // Point the uniforms at the caption (centered, LABEL_GAP under the
+    // poster, scaling with it) and hand back what to draw, if there is one
+    pub fn apply_label_uniforms(&self, context: &WebGlRenderingContext, uniforms: &PosterUniforms) -> Option<(&WebGlTexture, &WebGlBuffer)> {
+        let (text, buffer) = self.label_art.as_ref()?;
+        let scale = self.drawn_scale();
+        let (cx, poster_cy) = self.current_center();
+        let cy = poster_cy + (self.h / 2.0 + LABEL_GAP + text.height / 2.0) * scale;
+        let (w, h) = (text.width * scale, text.height * scale);
+        context.uniform4f(uniforms.rect.as_ref(), cx - w / 2.0, cy - h / 2.0, w, h);
+        context.uniform4f(uniforms.radii.as_ref(), 0.0, 0.0, 0.0, 0.0);
+        context.uniform4f(uniforms.tint.as_ref(), 1.0, 1.0, 1.0, 1.0);
+        context.uniform1f(uniforms.playing.as_ref(), 0.0);
+        context.uniform1f(uniforms.alpha.as_ref(), self.draw_alpha());
+        context.uniform1f(uniforms.scale.as_ref(), scale);
+        context.uniform2f(uniforms.offset.as_ref(), cx, cy);
+        Some((&text.texture, buffer))
+    }
+
+    // anim_scale as drawn (see rest_scale)
+    pub fn drawn_scale(&self) -> f32 {
+        self.rest_scale.unwrap_or(self.anim_scale)
+    }
+
+    // 4. Where the item is drawn right now: [x, y, w, h] incl. scroll + scale
+    pub fn current_rect(&self) -> [f32; 4] {
+        let scale = self.drawn_scale();
         let new_w = self.w * scale;
         let new_h = self.h * scale;
 
-        // APPLY SCROLL OFFSETS HERE! 
-        let final_center_x = center_x + self.offset_x;
-        let final_center_y = center_y + self.offset_y; // NEW!
-
-        let x = final_center_x - (new_w / 2.0);
-        let y = final_center_y - (new_h / 2.0);
-        let x2 = x + new_w;
-        let y2 = y + new_h;
-        
-        vec![
-            x,  y,   0.0, 0.0,
-            x,  y2,  0.0, 1.0,
-            x2, y,   1.0, 0.0,
-            x2, y,   1.0, 0.0,
-            x,  y2,  0.0, 1.0,
-            x2, y2,  1.0, 1.0,
+        // Scroll offsets (and the edge nudge) are in the center already
+        let (final_center_x, final_center_y) = self.current_center();
+
+        [final_center_x - (new_w / 2.0), final_center_y - (new_h / 2.0), new_w, new_h]
+    }
+
+    // Center of current_rect (what u_offset gets)
+    pub fn current_center(&self) -> (f32, f32) {
+        (
+            self.x + self.w / 2.0 + self.offset_x + self.edge_nudge[0],
+            self.y + self.h / 2.0 + self.offset_y + self.edge_nudge[1],
+        )
+    }
+
+    // KEEP ON SCREEN 📐 Slide the poster inward by however much its scale-up
+    // pushed it past a screen edge (width x height). Only the growth counts:
+    // a poster that's half scrolled off stays where it is, it just grows
+    // away from the edge. Scales with anim_scale, so nothing jumps.
+    pub fn fit_onscreen(&mut self, width: f32, height: f32) {
+        let growth_overflow = |center: f32, size: f32, bound: f32| {
+            let (half, half_scaled) = (size / 2.0, size * self.anim_scale / 2.0);
+            let past_start = |half: f32| (half - center).max(0.0);
+            let past_end = |half: f32| (center + half - bound).max(0.0);
+            let push_in = (past_start(half_scaled) - past_start(half)).max(0.0);
+            let push_back = (past_end(half_scaled) - past_end(half)).max(0.0);
+            push_in - push_back
+        };
+        let center_x = self.x + self.w / 2.0 + self.offset_x;
+        let center_y = self.y + self.h / 2.0 + self.offset_y;
+        let nudge = [growth_overflow(center_x, self.w, width), growth_overflow(center_y, self.h, height)];
+        if nudge != self.edge_nudge {
+            self.edge_nudge = nudge;
+            self.dirty = true;
+        }
+    }
+
+    pub fn clear_edge_nudge(&mut self) {
+        if self.edge_nudge != [0.0, 0.0] {
+            self.edge_nudge = [0.0, 0.0];
+            self.dirty = true;
+        }
+    }
+
+    // Pixel size of this poster's part of the image (its sprite cell), once
+    // the image has dimensions
+    fn source_size(&self) -> Option<(f32, f32)> {
+        let img = self.image_element.as_deref()?;
+        let [u0, v0, u1, v1] = self.uv_rect;
+        Some((img.natural_width() as f32 * (u1 - u0), img.natural_height() as f32 * (v1 - v0)))
+    }
+
+    // Part of the texture that fills the box: all of uv_rect, unless the box
+    // is fixed (not resizing to the image) and the aspects differ -> crop at the anchor
+    fn cover_uv(&self) -> [f32; 4] {
+        let [u0, v0, u1, v1] = self.uv_rect;
+        let loaded = self.image_element.as_deref().is_some_and(crate::texture_manager::is_loaded);
+        let (w, h) = match self.source_size() {
+            Some((w, h)) if loaded && w > 0.0 && h > 0.0 => (w, h),
+            _ => return self.uv_rect,
+        };
+        if self.resize_contain || self.w <= 0.0 || self.h <= 0.0 {
+            return self.uv_rect;
+        }
+        // Crop window in 0..1 of the cell, then mapped into uv_rect
+        let [cu0, cv0, cu1, cv1] = cover_crop(w / h, self.w / self.h, self.cover_anchor);
+        let (du, dv) = (u1 - u0, v1 - v0);
+        [u0 + cu0 * du, v0 + cv0 * dv, u0 + cu1 * du, v0 + cv1 * dv]
+    }
+
+    // 5. Geometry Generator: unscaled quad centered on the origin, the vertex
+    // shader places it with u_scale + u_offset
+    pub fn create_rect(&self) -> Vec<f32> {
+        quad_vertices(self.w, self.h, self.uv)
+    }
+
+    // e.g. [12, 12, 0, 0] rounds only the top (cards with a label bar underneath)
+    pub fn set_corner_radii(&mut self, radii: [f32; 4]) {
+        self.corner_radii = radii.map(|r| r.max(0.0));
+        self.mark_dirty();
+    }
+
+    // Still focusable when disabled, just drawn at disabled_opacity
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.mark_dirty();
+    }
+
+    // Opacity this frame: the enter/exit fade times the ghosting of a disabled item
+    pub fn draw_alpha(&self) -> f32 {
+        if self.enabled { self.alpha } else { self.alpha * self.disabled_opacity }
+    }
+
+    pub fn set_elevation(&mut self, elevation: f32) {
+        self.elevation = elevation.max(0.0);
+        self.mark_dirty();
+    }
+
+    // 0.0 at rest, 1.0 once the selection pop has fully grown
+    pub fn selection_progress(&self) -> f32 {
+        ((self.anim_scale - 1.0) / (SELECTED_SCALE - 1.0)).clamp(0.0, 1.0)
+    }
+
+    // Shadow quad for the current frame: (rect, softness, alpha), None = flat.
+    // Selection lifts the item by up to `selected_elevation` extra.
+    pub fn shadow(&self, selected_elevation: f32) -> Option<([f32; 4], f32, f32)> {
+        let elevation = self.elevation + selected_elevation * self.selection_progress();
+        if elevation <= 0.0 {
+            return None;
+        }
+        let [x, y, w, h] = self.current_rect();
+        let softness = elevation * 1.5;
+        let drop = elevation * 0.6;
+        let rect = [x - softness, y - softness + drop, w + softness * 2.0, h + softness * 2.0];
+        let alpha = (0.2 + elevation * 0.02).min(0.6) * self.draw_alpha();
+        Some((rect, softness, alpha))
+    }
+
+    // Rotation about the item's vertical center line + perspective, as a
+    // column-major mat4 working on pixel positions. The result keeps its
+    // w, so the GPU does the divide (and textures stay perspective-correct).
+    // Positive `angle` brings the right edge towards the viewer.
+    pub fn rotation_y_transform(&self, angle: f32) -> [f32; 16] {
+        let [x, y, w, h] = self.current_rect();
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+        let (sin, cos) = angle.sin_cos();
+        let k = sin / PERSPECTIVE_DISTANCE; // w = 1 - (px - cx) * k
+        let a = cos - cx * k;
+        [
+            a,           -cy * k,     0.0, -k,
+            0.0,         1.0,         0.0, 0.0,
+            0.0,         0.0,         1.0, 0.0,
+            cx - cx * a, cy * cx * k, 0.0, 1.0 + cx * k,
+        ]
+    }
+
+    // Mirror across a line `gap` px under the item's bottom edge (reflections).
+    // Top of the texture ends up furthest down, i.e. flipped texCoords.
+    pub fn mirror_transform(&self, gap: f32) -> [f32; 16] {
+        let [_, y, _, h] = self.current_rect();
+        let line = y + h + gap / 2.0;
+        [
+            1.0, 0.0,        0.0, 0.0,
+            0.0, -1.0,       0.0, 0.0,
+            0.0, 0.0,        1.0, 0.0,
+            0.0, 2.0 * line, 0.0, 1.0,
         ]
     }
 
+    // 6. Push this item's uniforms before its draw call
+    pub fn apply_uniforms(&self, context: &WebGlRenderingContext, uniforms: &PosterUniforms) {
+        let [x, y, w, h] = self.current_rect();
+        context.uniform4f(uniforms.rect.as_ref(), x, y, w, h);
+        // Never let a radius exceed half the short side
+        let [tl, tr, br, bl] = self.corner_radii.map(|r| r.min(w.min(h) / 2.0).max(0.0));
+        context.uniform4f(uniforms.radii.as_ref(), tl, tr, br, bl);
+        context.uniform1f(uniforms.alpha.as_ref(), self.draw_alpha());
+        let (cx, cy) = self.current_center();
+        context.uniform1f(uniforms.scale.as_ref(), self.drawn_scale());
+        context.uniform2f(uniforms.offset.as_ref(), cx, cy);
+        context.uniform1f(uniforms.playing.as_ref(), if self.playing { 1.0 } else { 0.0 });
+        context.uniform1f(uniforms.time.as_ref(), self.animation_time);
+        let [r, g, b, a] = self.tint;
+        context.uniform4f(uniforms.tint.as_ref(), r, g, b, a);
+    }
+
     // ... (rest of file: change_image, shaders - same as before) ...
+    #[allow(dead_code)]
     pub fn change_image(&mut self, new_src: &str) {
         self.src = new_src.to_string();
         self.resize_contain = true;
@@ -168,24 +870,241 @@ impl PosterItem {
             attribute vec2 position;
             attribute vec2 texCoord;
             uniform vec2 u_resolution;
+            uniform mat4 u_transform;
+            uniform float u_scale;
+            uniform vec2 u_offset;
             varying vec2 v_texCoord;
+            varying vec2 v_pos;
             void main() {
-                vec2 zeroToOne = position / u_resolution;
-                vec2 zeroToTwo = zeroToOne * 2.0;
-                vec2 clipSpace = zeroToTwo - 1.0;
-                gl_Position = vec4(clipSpace.x, clipSpace.y * -1.0, 0, 1);
+                // Centered quad -> its pixel rect (scale + scroll are uniforms, no re-uploads)
+                vec2 placed = position * u_scale + u_offset;
+                // Pixel space in, homogeneous pixels out (w != 1 only for coverflow)
+                vec4 p = u_transform * vec4(placed, 0.0, 1.0);
+                vec2 zeroToTwo = (p.xy / u_resolution) * 2.0;
+                vec2 clipSpace = zeroToTwo - p.w;
+                gl_Position = vec4(clipSpace.x, clipSpace.y * -1.0, 0, p.w);
                 v_texCoord = texCoord;
+                v_pos = placed;
             }
         "#
     }
+    // Rounded-rect mask: feathered by ~1px when OES_standard_derivatives is
+    // enabled (the macro only exists then), hard-edged otherwise.
     pub fn get_fragment_shader() -> &'static str {
         r#"
+            #extension GL_OES_standard_derivatives : enable
             precision mediump float;
             varying vec2 v_texCoord;
+            varying vec2 v_pos;
             uniform sampler2D u_texture;
+            uniform vec4 u_rect;
+            uniform vec4 u_radii; // tl, tr, br, bl
+            uniform float u_dim;
+            uniform float u_alpha;
+            uniform vec2 u_reflection;
+            uniform vec3 u_color_adjust; // brightness, contrast, gamma
+            uniform float u_playing;
+            uniform float u_time;
+            uniform vec4 u_tint;
+            uniform float u_fade;
             void main() {
-                gl_FragColor = texture2D(u_texture, v_texCoord);
+                vec4 color = texture2D(u_texture, v_texCoord);
+                // Color adjust (dim TVs, accessibility): scale, spread around mid-grey, gamma
+                color.rgb *= u_color_adjust.x;
+                color.rgb = (color.rgb - 0.5) * u_color_adjust.y + 0.5;
+                color.rgb = pow(clamp(color.rgb, 0.0, 1.0), vec3(1.0 / u_color_adjust.z));
+                color *= u_tint;
+                color.rgb *= u_dim;
+                color.a *= u_alpha * u_fade;
+                if (u_playing > 0.5) {
+                    // "Now playing": three bars bouncing on u_time, bottom-left on a dark square
+                    vec2 local = v_pos - u_rect.xy;
+                    vec2 badge = vec2(local.x - 12.0, (u_rect.w - 12.0) - local.y); // y up from the badge's bottom
+                    if (badge.x >= -6.0 && badge.x < 36.0 && badge.y >= -6.0 && badge.y < 32.0) {
+                        float bar = floor(badge.x / 10.0);
+                        float height = 8.0 + 18.0 * (0.5 + 0.5 * sin(u_time * 6.0 + bar * 1.7));
+                        bool lit = badge.x >= 0.0 && bar < 3.0 && mod(badge.x, 10.0) < 6.0 && badge.y >= 0.0 && badge.y < height;
+                        color.rgb = lit ? vec3(u_dim) : color.rgb * 0.4;
+                    }
+                }
+                if (u_reflection.y > 0.0) {
+                    // v_pos is pre-mirror: distance from the bottom edge = distance below it on screen
+                    float below = (u_rect.y + u_rect.w) - v_pos.y;
+                    color.a *= u_reflection.x * clamp(1.0 - below / u_reflection.y, 0.0, 1.0);
+                }
+                if (max(max(u_radii.x, u_radii.y), max(u_radii.z, u_radii.w)) > 0.0) {
+                    vec2 halfSize = u_rect.zw * 0.5;
+                    vec2 p = v_pos - (u_rect.xy + halfSize);
+                    // Radius of the corner this quadrant belongs to (y grows downwards)
+                    vec2 side = p.x < 0.0 ? u_radii.xw : u_radii.yz;
+                    float radius = p.y < 0.0 ? side.x : side.y;
+                    vec2 q = abs(p) - (halfSize - vec2(radius));
+                    float dist = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - radius;
+            #ifdef GL_OES_standard_derivatives
+                    float aa = fwidth(dist);
+                    color.a *= 1.0 - smoothstep(-0.5 * aa, 0.5 * aa, dist);
+            #else
+                    if (dist > 0.0) discard;
+            #endif
+                }
+                gl_FragColor = color;
             }
         "#
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poster() -> PosterItem {
+        PosterItem::new(50.0, 50.0, 300.0, 200.0, "poster.jpg", false)
+    }
+
+    #[test]
+    fn higher_elevation_draws_a_larger_shadow() {
+        let mut low = poster();
+        let mut high = poster();
+        assert!(low.shadow(0.0).is_none()); // Flat by default
+        low.set_elevation(2.0);
+        high.set_elevation(8.0);
+        let ([_, _, low_w, low_h], _, low_alpha) = low.shadow(0.0).unwrap();
+        let ([_, _, high_w, high_h], _, high_alpha) = high.shadow(0.0).unwrap();
+        assert!(high_w > low_w && high_h > low_h && high_alpha > low_alpha);
+    }
+
+    #[test]
+    fn a_new_target_glides_the_drawn_position() {
+        let mut item = poster();
+        let anim = AnimationConfig::new();
+        item.clear_dirty();
+        item.glide_to(370.0, 50.0);
+        assert!(item.is_dirty());
+        assert_eq!(item.target_x(), 370.0);
+
+        let mut xs = Vec::new();
+        for _ in 0..3 {
+            item.update_model(&anim);
+            xs.push(item.current_rect()[0]);
+        }
+        assert!(xs[0] > 50.0 && xs[0] < xs[1] && xs[1] < xs[2] && xs[2] < 370.0);
+        for _ in 0..100 {
+            item.update_model(&anim);
+        }
+        assert_eq!(item.x, 370.0);
+        assert!(item.is_settled());
+    }
+
+    #[test]
+    fn the_reflection_is_flipped_below_the_poster() {
+        let item = poster();
+        let m = item.mirror_transform(6.0);
+        let [_, y, _, h] = item.current_rect();
+        let mirrored_y = |py: f32| m[5] * py + m[13]; // Column-major: y' = m11*y + m31
+        // Top edge (v = 0) lands furthest down, bottom edge just under the gap
+        assert_eq!(mirrored_y(y + h), y + h + 6.0);
+        assert_eq!(mirrored_y(y), y + 2.0 * h + 6.0);
+        assert_eq!(m[0], 1.0); // x untouched
+    }
+
+    #[test]
+    fn the_selection_pop_never_touches_the_vertices() {
+        let mut item = poster();
+        let anim = AnimationConfig::new();
+        item.is_selected = true;
+        item.offset_x = -320.0; // Scrolling at the same time
+        for _ in 0..60 {
+            item.update_model(&anim);
+            assert!(!item.geometry_dirty);
+            item.offset_x += 5.0;
+        }
+        assert_eq!(item.anim_scale, SELECTED_SCALE);
+        item.set_size(320.0, 200.0); // Only a size change does
+        assert!(item.geometry_dirty);
+    }
+
+    #[test]
+    fn top_anchor_keeps_the_top_of_a_tall_image() {
+        // 1:2 image in a 1:1 box: half of it shows
+        assert_eq!(cover_crop(0.5, 1.0, CoverAnchor::Top), [0.0, 0.0, 1.0, 0.5]);
+        assert_eq!(cover_crop(0.5, 1.0, CoverAnchor::Center), [0.0, 0.25, 1.0, 0.75]);
+        assert_eq!(cover_crop(0.5, 1.0, CoverAnchor::Bottom), [0.0, 0.5, 1.0, 1.0]);
+        // Wide image: the anchor picks the horizontal strip instead
+        assert_eq!(cover_crop(2.0, 1.0, CoverAnchor::Left), [0.0, 0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn top_only_radii_leave_the_bottom_corners_square() {
+        let mut item = poster();
+        item.set_corner_radii([12.0, 12.0, -3.0, 0.0]);
+        assert_eq!(item.corner_radii, [12.0, 12.0, 0.0, 0.0]);
+        assert!(item.is_dirty());
+    }
+
+    #[test]
+    fn a_deferred_focus_waits_for_the_image() {
+        let mut anim = AnimationConfig::new();
+        anim.defer_focus_until_loaded = true;
+        let mut item = poster();
+        item.is_selected = true;
+        for _ in 0..60 {
+            item.update_model(&anim);
+        }
+        assert_eq!(item.anim_scale, 1.0);
+
+        // Without the option the same unloaded poster pops right away
+        anim.defer_focus_until_loaded = false;
+        item.update_model(&anim);
+        assert!(item.anim_scale > 1.0);
+    }
+
+    #[test]
+    fn a_popped_poster_at_the_left_edge_is_nudged_back_on_screen() {
+        let mut item = PosterItem::new(10.0, 50.0, 300.0, 200.0, "poster.jpg", false);
+        item.anim_scale = SELECTED_SCALE;
+        assert!(item.current_rect()[0] < 0.0);
+
+        item.fit_onscreen(1920.0, 1080.0);
+        assert_eq!(item.current_rect()[0], 0.0);
+        assert_eq!(item.current_rect()[2], 360.0); // Moved, not shrunk
+    }
+
+    #[test]
+    fn a_settled_pop_is_a_whole_number_of_physical_pixels() {
+        let mut anim = AnimationConfig::new();
+        anim.scale_pixel_snap = true;
+        anim.device_pixel_ratio = 1.5;
+        // 301 * 1.2 * 1.5 = 541.8 physical pixels unsnapped
+        let mut item = PosterItem::new(50.0, 50.0, 301.0, 200.0, "poster.jpg", false);
+        item.is_selected = true;
+        for _ in 0..200 {
+            item.update_model(&anim);
+        }
+        let physical = item.current_rect()[2] * anim.device_pixel_ratio;
+        assert!((physical - physical.round()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn a_paused_item_stops_its_own_clock() {
+        let anim = AnimationConfig::new();
+        let (mut paused, mut running) = (poster(), poster());
+        paused.playing = true;
+        running.playing = true;
+        paused.clear_dirty();
+        paused.set_animation_paused(true);
+        assert!(paused.is_dirty());
+        assert!(!paused.is_always_animating());
+
+        for _ in 0..10 {
+            paused.update_model(&anim);
+            running.update_model(&anim);
+        }
+        assert_eq!(paused.animation_time, 0.0);
+        assert!(running.animation_time > 0.0);
+
+        paused.clear_dirty();
+        paused.set_animation_paused(false);
+        paused.update_model(&anim);
+        assert!(paused.is_dirty() && paused.animation_time > 0.0);
+    }
+}
@@ -0,0 +1,84 @@
+use web_sys::{WebGlFramebuffer, WebGlRenderingContext, WebGlTexture};
+
+// OFFSCREEN RENDER TARGET 🖼️
+// A framebuffer with a colour texture attached, so a whole frame can be drawn
+// "into a picture" and then sampled again by a post-processing pass.
+pub struct RenderTarget {
+    pub framebuffer: WebGlFramebuffer,
+    pub texture: WebGlTexture,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl RenderTarget {
+    pub fn new(context: &WebGlRenderingContext, width: u32, height: u32) -> Result<Self, String> {
+        let texture = context.create_texture().ok_or("Failed to create target texture")?;
+        let framebuffer = context
+            .create_framebuffer()
+            .ok_or("Failed to create framebuffer")?;
+
+        let target = Self {
+            framebuffer,
+            texture,
+            width,
+            height,
+        };
+        target.allocate(context)?;
+        Ok(target)
+    }
+
+    // 1. (Re)allocate the colour texture at the current size
+    fn allocate(&self, context: &WebGlRenderingContext) -> Result<(), String> {
+        context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&self.texture));
+        context
+            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                WebGlRenderingContext::TEXTURE_2D,
+                0,
+                WebGlRenderingContext::RGBA as i32,
+                self.width as i32,
+                self.height as i32,
+                0,
+                WebGlRenderingContext::RGBA,
+                WebGlRenderingContext::UNSIGNED_BYTE,
+                None,
+            )
+            .map_err(|_| "Failed to allocate target texture")?;
+
+        // NPOT-safe parameters (the canvas is almost never a power of two)
+        for (param, value) in [
+            (WebGlRenderingContext::TEXTURE_WRAP_S, WebGlRenderingContext::CLAMP_TO_EDGE),
+            (WebGlRenderingContext::TEXTURE_WRAP_T, WebGlRenderingContext::CLAMP_TO_EDGE),
+            (WebGlRenderingContext::TEXTURE_MIN_FILTER, WebGlRenderingContext::LINEAR),
+            (WebGlRenderingContext::TEXTURE_MAG_FILTER, WebGlRenderingContext::LINEAR),
+        ] {
+            context.tex_parameteri(WebGlRenderingContext::TEXTURE_2D, param, value as i32);
+        }
+
+        context.bind_framebuffer(WebGlRenderingContext::FRAMEBUFFER, Some(&self.framebuffer));
+        context.framebuffer_texture_2d(
+            WebGlRenderingContext::FRAMEBUFFER,
+            WebGlRenderingContext::COLOR_ATTACHMENT0,
+            WebGlRenderingContext::TEXTURE_2D,
+            Some(&self.texture),
+            0,
+        );
+        context.bind_framebuffer(WebGlRenderingContext::FRAMEBUFFER, None);
+        Ok(())
+    }
+
+    // 2. Resize (only touches the GPU when the size really changed)
+    pub fn resize(&mut self, context: &WebGlRenderingContext, width: u32, height: u32) -> Result<(), String> {
+        if self.width == width && self.height == height {
+            return Ok(());
+        }
+        self.width = width;
+        self.height = height;
+        self.allocate(context)
+    }
+
+    // 3. Redirect every following draw call into this target
+    pub fn bind(&self, context: &WebGlRenderingContext) {
+        context.bind_framebuffer(WebGlRenderingContext::FRAMEBUFFER, Some(&self.framebuffer));
+        context.viewport(0, 0, self.width as i32, self.height as i32);
+    }
+}
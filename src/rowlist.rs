@@ -1,9 +1,84 @@
-use crate::posteritem::PosterItem;
+use crate::animation::AnimationConfig;
+use crate::keymap::Action;
+use crate::geometry_cache::GeometryCache;
+use crate::painter::Painter;
+use crate::posteritem::{mat4_mul, CoverAnchor, ItemKind, PosterItem, PosterUniforms, IDENTITY};
 use crate::texture_manager::TextureManager;
 use wasm_bindgen::JsValue;
-use web_sys::WebGlRenderingContext;
+use std::rc::Rc;
+use web_sys::{WebGlBuffer, WebGlRenderingContext, WebGlTexture};
+
+// LAYOUT 📐
+pub const ITEM_START_X: f32 = 50.0;
+pub const ITEM_SPACING: f32 = 320.0; // Item Width + Gap
+pub const ITEM_WIDTH: f32 = 300.0;
+pub const ITEM_HEIGHT: f32 = 200.0; // Until the image tells us its real aspect
+
+// Kinetic scroll: momentum kept per frame after release, and where it stops
+const KINETIC_FRICTION: f32 = 0.95;
+const KINETIC_STOP_SPEED: f32 = 0.5; // px/frame
+
+// Space between a poster and its reflection
+const REFLECTION_GAP: f32 = 6.0;
+
+// How far a row card reaches above / below the row's posters
+const BACKGROUND_PAD: f32 = 30.0;
+
+// Glossy floor under a hero row's posters
+#[derive(Clone, Copy)]
+pub struct Reflection {
+    pub height_fraction: f32, // How much of the poster's height is reflected
+    pub opacity: f32,         // At the poster's edge, fading to 0
+}
+
+// Coverflow: tilt of an item half a screen (or more) away from the center
+const COVERFLOW_MAX_ANGLE: f32 = std::f32::consts::FRAC_PI_3;
+
+// Demo content until the embedder supplies real rows
+const DEMO_POSTERS: [&str; 2] = [
+    "https://m.media-amazon.com/images/M/MV5BNGI0MDI4NjEtOWU3ZS00ODQyLWFhYTgtNGYxM2ZkM2Q2YjE3XkEyXkFqcGc@._V1_.jpg",
+    "https://m.media-amazon.com/images/M/MV5BNDE0MGFkYzktYTMyNS00Mjk1LWI3YzEtYWYxMzAxNTI2YmUyXkEyXkFqcGc@._V1_QL75_UX480_.jpg",
+];
+
+// ROW TEMPLATE 🧩 What every generated row looks like: item count, item size
+// and which image goes where (`src` is called with (row, item)).
+pub struct RowTemplate {
+    pub item_count: usize,
+    pub item_width: f32,
+    pub item_height: f32,
+    pub src: Box<dyn Fn(usize, usize) -> String>,
+}
+
+impl RowTemplate {
+    // 10 posters per row, alternating between the two demo images
+    pub fn demo() -> Self {
+        Self {
+            item_count: 10,
+            item_width: ITEM_WIDTH,
+            item_height: ITEM_HEIGHT,
+            src: Box::new(|_, i| DEMO_POSTERS[i % 2].to_string()),
+        }
+    }
+}
+
+// What a row in the column is
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RowKind {
+    Posters,     // Normal focusable row of items
+    Spacer(f32), // Blank vertical gap of this height: never focused, draws nothing
+}
+
+// How Left/Right move through a row
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SelectionMode {
+    Snap,            // Item by item, the focused one pops (default)
+    FreeScroll(f32), // Banner strips: slide this many pixels a press, nothing is focused
+}
 
 pub struct RowList {
+    pub id: String, // App's own key for this row, "" = none
+    pub kind: RowKind,
+    pub y: f32,
     pub items: Vec<PosterItem>,
     pub selected_index: usize,
     pub is_active: bool,
@@ -11,78 +86,591 @@ pub struct RowList {
     // SCROLL STATE 📜
     pub scroll_x: f32,        // Current visual position (Lerped)
     pub target_scroll_x: f32, // Where we want to go
+    pub scroll_velocity_x: f32, // Pixels moved during the last update
 
     // NEW: Vertical Scroll (Received from Parent)
     pub offset_y: f32,
-}
 
-impl RowList {
-    pub fn new(y_start: f32) -> Self {
-        let mut items = Vec::new();
+    // Visible width (for clamping the scroll at the end of the row)
+    pub viewport_width: f32,
 
-        for i in 0..10 {
-            let x_axis = 50.0 + (i as f32 * 320.00);
-            let y_axis = y_start;
-            let img_width = 300.0;
-            let img_height = 200.0;
+    // Brightness multiplier for the whole row (1.0 = full); `dim` eases
+    // towards `target_dim` (set by ColumnList) with anim.dim
+    pub dim: f32,
+    pub target_dim: f32,
+    dim_velocity: f32,
 
-            let img_src = if i % 2 == 0 {
-                "https://m.media-amazon.com/images/M/MV5BNGI0MDI4NjEtOWU3ZS00ODQyLWFhYTgtNGYxM2ZkM2Q2YjE3XkEyXkFqcGc@._V1_.jpg"
-            } else {
-                "https://m.media-amazon.com/images/M/MV5BNDE0MGFkYzktYTMyNS00Mjk1LWI3YzEtYWYxMzAxNTI2YmUyXkEyXkFqcGc@._V1_QL75_UX480_.jpg"
-            };
-            //create each item here
-            let item = PosterItem::new(x_axis, y_axis, img_width, img_height, img_src, true);
-            items.push(item);
-        }
+    // Items kept uploaded on each side of the visible ones
+    pub cull_margin: usize,
+    // Items actually drawn on each side, so they don't pop in at the edge
+    pub render_overscan: usize,
+
+    // Right on the last item -> first (and Left on the first -> last), same row
+    pub horizontal_wrap: bool,
+    pub selection_mode: SelectionMode,
+
+    // COVERFLOW 📀 selection is centered, the others tilt towards it
+    pub coverflow: bool,
+
+    // Mirrored, fading copy under every poster (None = off)
+    pub reflection: Option<Reflection>,
+
+    // CARD 🟦 Tinted, rounded band behind the whole row (None = off): a plain
+    // quad over the manager's white pixel, colored with its tint
+    background: Option<PosterItem>,
+
+    // SEE ALL ➡️ Called with the row index when the trailing tile is activated
+    pub on_see_all: Option<js_sys::Function>,
 
+    // KINETIC DRAG 👆 (pointer x in layout pixels)
+    dragging: bool,
+    coasting: bool, // Released, momentum still running
+    drag_last_x: f32,
+    drag_velocity: f32, // px per frame/move, smoothed
+
+    // Row-wide look changed (dim, reflection, coverflow, items removed);
+    // items track their own changes
+    dirty: bool,
+
+    // FILTER 🔍 Items that finished their exit animation, keyed by the index
+    // they had in the unfiltered row (sorted), so clear_filter() can put them back
+    hidden: Vec<(usize, PosterItem)>,
+}
+
+impl RowList {
+    // Empty row at `y_start` (see from_template for a filled one)
+    pub fn new(y_start: f32) -> Self {
         Self {
-            items,
+            id: String::new(),
+            kind: RowKind::Posters,
+            y: y_start,
+            items: Vec::new(),
             selected_index: 0,
             is_active: false,
 
             // Start at 0
             scroll_x: 0.0,
             target_scroll_x: 0.0,
+            scroll_velocity_x: 0.0,
             offset_y: 0.0, // Default 0
+            viewport_width: 1920.0,
+            dim: 1.0,
+            target_dim: 1.0,
+            dim_velocity: 0.0,
+            cull_margin: 2,
+            render_overscan: 2,
+            horizontal_wrap: false,
+            selection_mode: SelectionMode::Snap,
+            coverflow: false,
+            reflection: None,
+            on_see_all: None,
+            dragging: false,
+            coasting: false,
+            drag_last_x: 0.0,
+            drag_velocity: 0.0,
+            dirty: true,
+            hidden: Vec::new(),
+            background: None,
+        }
+    }
+
+    // SPRITE SHEET 🧮 `count` posters cut from one image laid out as a
+    // `cols` x `rows` grid (row-major). One src = one request, one shared texture.
+    pub fn from_sprite_sheet(y_start: f32, src: &str, cols: usize, rows: usize, count: usize, item_width: f32) -> Self {
+        let mut row = Self::new(y_start);
+        let (cols, rows) = (cols.max(1), rows.max(1));
+        let count = count.min(cols * rows);
+        row.items.reserve_exact(count);
+        for i in 0..count {
+            let (col, line) = ((i % cols) as f32, (i / cols) as f32);
+            let x_axis = ITEM_START_X + (i as f32 * ITEM_SPACING);
+            let mut item = PosterItem::new(x_axis, y_start, item_width, ITEM_HEIGHT, src, true);
+            item.uv_rect = [
+                col / cols as f32,
+                line / rows as f32,
+                (col + 1.0) / cols as f32,
+                (line + 1.0) / rows as f32,
+            ];
+            row.items.push(item);
+        }
+        row
+    }
+
+    // Whitespace between sections (no header, no items)
+    pub fn spacer(y_start: f32, height: f32) -> Self {
+        let mut row = Self::new(y_start);
+        row.kind = RowKind::Spacer(height.max(0.0));
+        row
+    }
+
+    // Up/Down can land here
+    pub fn is_focusable(&self) -> bool {
+        self.kind == RowKind::Posters
+    }
+
+    // Row number `row_index` of a templated grid. Items are allocated in one go.
+    pub fn from_template(y_start: f32, row_index: usize, template: &RowTemplate) -> Self {
+        let mut row = Self::new(y_start);
+        row.items.reserve_exact(template.item_count);
+        for i in 0..template.item_count {
+            let x_axis = ITEM_START_X + (i as f32 * ITEM_SPACING);
+            let src = (template.src)(row_index, i);
+            row.items.push(PosterItem::new(x_axis, y_start, template.item_width, template.item_height, &src, true));
         }
+        row
     }
 
     // 1. INPUT HANDLER
-    pub fn handle_input(&mut self, key_code: u32) {
+    pub fn handle_input(&mut self, action: Action) {
         if !self.is_active {
             return;
         }
+        if let SelectionMode::FreeScroll(step) = self.selection_mode {
+            // No selection to move: just the scroll, clamped to the content
+            match action {
+                Action::Left => self.scroll_by(step),
+                Action::Right => self.scroll_by(-step),
+                Action::Home => self.target_scroll_x = 0.0,
+                Action::End => self.target_scroll_x = self.clamp_scroll(f32::NEG_INFINITY),
+                _ => {}
+            }
+            return;
+        }
 
-        match key_code {
-            37 => {
+        let mut wrapped = false;
+        match action {
+            Action::Left => {
                 // LEFT
                 if self.selected_index > 0 {
                     self.selected_index -= 1;
+                } else if self.horizontal_wrap && self.items.len() > 1 {
+                    self.selected_index = self.items.len() - 1;
+                    wrapped = true;
                 }
             }
-            39 => {
+            Action::Right => {
                 // RIGHT
-                if self.selected_index < self.items.len() - 1 {
+                if self.selected_index + 1 < self.items.len() {
                     self.selected_index += 1;
+                } else if self.horizontal_wrap && self.items.len() > 1 {
+                    self.selected_index = 0;
+                    wrapped = true;
                 }
             }
+            Action::Home => {
+                // HOME -> first item
+                self.selected_index = 0;
+            }
+            Action::End => {
+                // END -> last item
+                self.selected_index = self.items.len().saturating_sub(1);
+            }
             _ => {}
         }
 
-        // --- SCROLL CALCULATION ---
-        // Rule: If we pass item 4, start shifting left!
-        // Item Width + Gap = 220.0
+        self.update_scroll_target();
+
+        // A wrap jumps straight to the other end instead of racing across the row
+        if wrapped {
+            self.scroll_x = self.target_scroll_x;
+            self.scroll_velocity_x = 0.0;
+        }
+    }
+
+    pub fn set_horizontal_wrap(&mut self, wrap: bool) {
+        self.horizontal_wrap = wrap;
+    }
+
+    // Switching to free scroll keeps the current scroll (the selection goes
+    // back to 0 and stays there); back to Snap scrolls to item 0
+    pub fn set_selection_mode(&mut self, mode: SelectionMode) {
+        self.selection_mode = mode;
+        if mode != SelectionMode::Snap {
+            self.selected_index = 0;
+        }
+        self.update_scroll_target();
+        self.dirty = true;
+    }
+
+    // 1b. POINTER DRAG: the row follows the finger 1:1 (no easing)...
+    pub fn begin_drag(&mut self, x: f32) {
+        self.dragging = true;
+        self.coasting = false;
+        self.drag_last_x = x;
+        self.drag_velocity = 0.0;
+    }
+
+    pub fn drag_to(&mut self, x: f32) {
+        if !self.dragging {
+            return;
+        }
+        let delta = x - self.drag_last_x;
+        self.drag_last_x = x;
+        self.drag_velocity = self.drag_velocity * 0.2 + delta * 0.8;
+        self.scroll_x = self.clamp_scroll(self.scroll_x + delta);
+        self.target_scroll_x = self.scroll_x;
+    }
+
+    // ...then coasts on with decaying momentum (see update_model)
+    pub fn end_drag(&mut self) {
+        if self.dragging {
+            self.dragging = false;
+            self.coasting = true;
+        }
+    }
+
+    fn clamp_scroll(&self, scroll_x: f32) -> f32 {
+        if self.coverflow {
+            let max = self.content_width();
+            return scroll_x.clamp(-max + self.viewport_width / 2.0, self.viewport_width / 2.0);
+        }
+        let max_scroll = (self.content_width() - self.viewport_width).max(0.0);
+        scroll_x.clamp(-max_scroll, 0.0)
+    }
+
+    // Shift the scroll target without touching the selection (see ColumnList::scroll_by)
+    pub fn scroll_by(&mut self, dx: f32) {
+        self.target_scroll_x = self.clamp_scroll(self.target_scroll_x + dx);
+    }
+
+    // Momentum ran out: pick the item the normal scroll rule would put here and
+    // let the usual easing settle on it
+    fn snap_after_coast(&mut self) {
+        if self.items.is_empty() || self.selection_mode != SelectionMode::Snap {
+            return; // Free scroll just stops where the momentum ran out
+        }
+        let last = self.items.len() - 1;
+        self.selected_index = if self.coverflow {
+            // Whatever's nearest to the middle
+            let middle = self.viewport_width / 2.0 - self.scroll_x;
+            ((middle - ITEM_START_X - ITEM_WIDTH / 2.0) / ITEM_SPACING).round().max(0.0) as usize
+        } else {
+            // update_scroll_target shifts (selected - lead) slots, so invert that
+            let lead = self.lead_index();
+            let shift = (-self.scroll_x / ITEM_SPACING).round().max(0.0) as usize;
+            if shift == 0 { self.selected_index.min(lead) } else { shift + lead }
+        }
+        .min(last);
+        self.update_scroll_target();
+    }
+
+    // Trailing "See All →" tile (None = remove it). It's focusable like any
+    // poster; activating it calls `on_activate(row_index)` instead of the
+    // engine's normal activate callback.
+    pub fn set_see_all(
+        &mut self,
+        context: Option<&WebGlRenderingContext>,
+        label: Option<&str>,
+        on_activate: Option<js_sys::Function>,
+    ) {
+        if let Some(index) = self.items.iter().position(|item| matches!(item.kind, ItemKind::SeeAll(_))) {
+            self.remove_item(context, index);
+        }
+        self.on_see_all = on_activate;
+        if let Some(label) = label {
+            let mut tile = PosterItem::see_all(label);
+            let x = ITEM_START_X + (self.items.len() as f32 * ITEM_SPACING);
+            tile.set_position(x, self.y);
+            tile.corner_radii = self.items.first().map_or([0.0; 4], |item| item.corner_radii);
+            tile.enter();
+            self.items.push(tile);
+            self.relayout();
+        }
+    }
+
+    // The focused item is the see-all tile
+    pub fn see_all_focused(&self) -> bool {
+        self.items
+            .get(self.selected_index)
+            .is_some_and(|item| matches!(item.kind, ItemKind::SeeAll(_)))
+    }
+
+    pub fn set_reflection(&mut self, enabled: bool, height_fraction: f32, opacity: f32) {
+        self.reflection = enabled.then_some(Reflection {
+            height_fraction: height_fraction.clamp(0.0, 1.0),
+            opacity: opacity.clamp(0.0, 1.0),
+        });
+        self.dirty = true;
+    }
+
+    // Card behind the row, `color` RGBA 0..1 (None removes it). Spans the
+    // viewport's width and the posters' height + BACKGROUND_PAD, moves with
+    // the row vertically but not with its horizontal scroll.
+    pub fn set_background(&mut self, color: Option<[f32; 4]>, radius: f32) {
+        self.background = color.map(|color| {
+            let mut card = self.background.take().unwrap_or_else(|| PosterItem::new(0.0, 0.0, 0.0, 0.0, "", false));
+            card.tint = color.map(|c| c.clamp(0.0, 1.0));
+            card.set_corner_radii([radius.max(0.0); 4]);
+            card
+        });
+        self.place_background();
+        self.dirty = true;
+    }
+
+    // Keep the card around the posters (they may have resized to their images)
+    fn place_background(&mut self) {
+        let Some(card) = &mut self.background else {
+            return;
+        };
+        let tallest = self.items.iter().map(|item| item.h).fold(0.0, f32::max);
+        card.set_size(self.viewport_width, tallest + 2.0 * BACKGROUND_PAD);
+        card.set_position(0.0, self.y - BACKGROUND_PAD);
+        card.offset_y = self.offset_y;
+    }
+
+    pub fn set_coverflow(&mut self, coverflow: bool) {
+        self.coverflow = coverflow;
+        self.dirty = true;
+        self.update_scroll_target();
+    }
+
+    // Anything in this row needs drawing again?
+    pub fn is_dirty(&self) -> bool {
+        self.dirty || self.items.iter().any(|item| item.is_dirty())
+    }
+
+    pub fn clear_dirty(&mut self) {
+        self.dirty = false;
+        for item in &mut self.items {
+            item.clear_dirty();
+        }
+    }
+
+    // Coverflow tilt for one item: flat within half a slot of the screen
+    // center, growing to COVERFLOW_MAX_ANGLE half a screen away
+    pub fn coverflow_angle(&self, item: &PosterItem) -> f32 {
+        let [x, _, w, _] = item.current_rect();
+        let half_view = self.viewport_width / 2.0;
+        let offset = (x + w / 2.0) - half_view;
+        let t = ((offset.abs() - ITEM_SPACING / 2.0).max(0.0) / half_view).min(1.0);
+        t * COVERFLOW_MAX_ANGLE * offset.signum()
+    }
+
+    // --- SCROLL CALCULATION ---
+    fn update_scroll_target(&mut self) {
+        // Free scroll has no selection to follow: keep the scroll, inside the content
+        if self.selection_mode != SelectionMode::Snap {
+            self.target_scroll_x = self.clamp_scroll(self.target_scroll_x);
+            return;
+        }
+
+        // Coverflow keeps the selection in the middle of the screen (no clamping)
+        if self.coverflow {
+            if let Some(item) = self.items.get(self.selected_index) {
+                self.target_scroll_x = self.viewport_width / 2.0 - (item.target_x() + item.w / 2.0);
+            }
+            return;
+        }
 
-        if self.selected_index > 4 {
-            // If we are at index 5, we want to shift 1 item to the left.
-            // If we are at index 6, shift 2 items...
-            let shift_count = (self.selected_index - 4) as f32;
-            self.target_scroll_x = -(shift_count * 320.0);
+        // Rule: once we pass the last item that fits on screen, start shifting left!
+        let lead = self.lead_index();
+        if self.selected_index > lead {
+            // One past it: shift 1 item to the left, two past: 2 items...
+            let shift_count = (self.selected_index - lead) as f32;
+            self.target_scroll_x = -(shift_count * ITEM_SPACING);
         } else {
-            // If we are at index 0-4, reset to start
+            // Everything up to it is on screen without scrolling
             self.target_scroll_x = 0.0;
         }
+
+        // Never scroll past the end: the last item stops at the right edge
+        let max_scroll = (self.content_width() - self.viewport_width).max(0.0);
+        self.target_scroll_x = self.target_scroll_x.max(-max_scroll);
+    }
+
+    // How many items fit on screen completely (at least 1); 5 on a 1920 wide screen
+    pub fn visible_count(&self) -> usize {
+        let item_width = self.items.first().map_or(ITEM_WIDTH, |item| item.w);
+        let room = self.viewport_width - ITEM_START_X - item_width;
+        if room < 0.0 {
+            return 1;
+        }
+        (room / ITEM_SPACING).floor() as usize + 1
+    }
+
+    // Last index reachable without scrolling
+    fn lead_index(&self) -> usize {
+        self.visible_count() - 1
+    }
+
+    // Recompute scroll math for a new screen width (resize)
+    pub fn set_screen_width(&mut self, width: f32) {
+        self.viewport_width = width;
+        self.update_scroll_target();
+    }
+
+    // Total row width: last item's right edge + the same margin as the left side
+    pub fn content_width(&self) -> f32 {
+        match self.items.last() {
+            Some(last) => last.target_x() + last.w + ITEM_START_X,
+            None => 0.0,
+        }
+    }
+
+    // Tallest item decides how much vertical space the row needs
+    pub fn height(&self) -> f32 {
+        match self.kind {
+            RowKind::Spacer(height) => height,
+            RowKind::Posters => self.items.iter().map(|item| item.h).fold(0.0, f32::max),
+        }
+    }
+
+    // RELAYOUT: send every item to its slot (they glide there), then re-aim
+    // the scroll so the focused item stays where the user expects it after
+    // adds/removes.
+    pub fn relayout(&mut self) {
+        self.layout(true);
+    }
+
+    // `animate` = false puts items in their slot immediately (fresh content)
+    fn layout(&mut self, animate: bool) {
+        for (i, item) in self.items.iter_mut().enumerate() {
+            let (x, y) = (ITEM_START_X + (i as f32 * ITEM_SPACING), self.y);
+            if animate {
+                item.glide_to(x, y);
+            } else {
+                item.set_position(x, y);
+            }
+        }
+        self.refocus();
+    }
+
+    // FILTER: items failing `predicate` shrink + fade out, then leave the row
+    // and the survivors glide together. Previously hidden items that match
+    // again are put back at their old place and fade in.
+    pub fn filter(&mut self, predicate: impl Fn(&PosterItem) -> bool) {
+        // A. Bring back hidden matches (ascending, so earlier inserts fix later indexes)
+        let mut i = 0;
+        while i < self.hidden.len() {
+            if !predicate(&self.hidden[i].1) {
+                i += 1;
+                continue;
+            }
+            let (original, mut item) = self.hidden.remove(i);
+            // Everything before it that's still hidden isn't in `items`
+            let index = original - self.hidden.iter().filter(|(o, _)| *o < original).count();
+            item.set_position(ITEM_START_X + (index as f32 * ITEM_SPACING), self.y);
+            item.enter();
+            if index <= self.selected_index && !self.items.is_empty() {
+                self.selected_index += 1;
+            }
+            self.items.insert(index, item);
+        }
+
+        // B. Start (or cancel) exits (the see-all tile always stays)
+        for item in &mut self.items {
+            let keep = item.kind != ItemKind::Poster || predicate(item);
+            if item.exiting == keep {
+                item.exiting = !keep;
+            }
+        }
+        self.relayout();
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter(|_| true);
+    }
+
+    // Index items[index] has in the unfiltered row
+    fn original_index(&self, index: usize) -> usize {
+        let mut original = index;
+        for (o, _) in &self.hidden {
+            if *o > original {
+                break;
+            }
+            original += 1;
+        }
+        original
+    }
+
+    // Move finished exits from `items` into `hidden`
+    fn collect_exited(&mut self) {
+        if !self.items.iter().any(|item| item.is_gone()) {
+            return;
+        }
+        let mut index = 0;
+        let mut original = 0;
+        let mut k = 0;
+        while index < self.items.len() {
+            // Original index of items[index]: skip slots owned by hidden items
+            while k < self.hidden.len() && self.hidden[k].0 == original {
+                original += 1;
+                k += 1;
+            }
+            if self.items[index].is_gone() {
+                let item = self.items.remove(index);
+                self.hidden.insert(k, (original, item));
+                k += 1;
+                if index < self.selected_index {
+                    self.selected_index -= 1;
+                }
+            } else {
+                index += 1;
+            }
+            original += 1;
+        }
+        self.relayout();
+    }
+
+    // Clamp the selection to what still exists and recompute the scroll target
+    pub fn refocus(&mut self) {
+        self.selected_index = self.selected_index.min(self.items.len().saturating_sub(1));
+        self.update_scroll_target();
+    }
+
+    // Swap the whole row's content. Old buffers are freed, the new items are
+    // slotted in and the selection is clamped (assets are loaded by the caller).
+    // A see-all tile stays at the end. No context = pure model (DOM / tests).
+    pub fn replace_items(&mut self, context: Option<&WebGlRenderingContext>, items: Vec<PosterItem>) {
+        let see_all = match self.items.last() {
+            Some(last) if matches!(last.kind, ItemKind::SeeAll(_)) => self.items.pop(),
+            _ => None,
+        };
+        let hidden = self.hidden.drain(..).map(|(_, item)| item);
+        for mut old in self.items.drain(..).chain(hidden) {
+            if let Some(context) = context {
+                Self::release_gl(context, &mut old);
+            }
+        }
+        self.items = items;
+        self.items.extend(see_all);
+        self.dirty = true;
+        self.layout(false);
+    }
+
+    // Free what the item owns on the GPU (shared poster textures belong to
+    // the manager, buffers to the GeometryCache: dropping them is enough)
+    fn release_gl(context: &WebGlRenderingContext, item: &mut PosterItem) {
+        item.buffer = None;
+        item.release_label(context);
+        if let (ItemKind::SeeAll(_), Some(texture)) = (&item.kind, item.texture.take()) {
+            context.delete_texture(Some(&texture));
+        }
+    }
+
+    // Remove one item (frees its GPU buffer). Removing something before the
+    // selection shifts the index so the same logical item stays focused.
+    pub fn remove_item(&mut self, context: Option<&WebGlRenderingContext>, index: usize) -> Option<PosterItem> {
+        if index >= self.items.len() {
+            return None;
+        }
+        // Hidden items after it move up one slot in the unfiltered order too
+        let original = self.original_index(index);
+        for (o, _) in &mut self.hidden {
+            if *o > original {
+                *o -= 1;
+            }
+        }
+        let mut item = self.items.remove(index);
+        if let Some(context) = context {
+            Self::release_gl(context, &mut item);
+        }
+        if index < self.selected_index {
+            self.selected_index -= 1;
+        }
+        self.dirty = true;
+        self.relayout();
+        Some(item)
     }
 
     // 2. LOAD ASSETS
@@ -90,32 +678,195 @@ impl RowList {
         &mut self,
         context: &WebGlRenderingContext,
         manager: &mut TextureManager,
+        geometry: &mut GeometryCache,
     ) -> Result<(), JsValue> {
-        for item in &mut self.items {
-            item.init_buffer(context).unwrap_or_else(|e| {
+        self.load_pending(context, manager, geometry, usize::MAX, usize::MAX, true)?;
+        Ok(())
+    }
+
+    // Set up at most `budget` items among the first `upto` that have no GPU
+    // buffer yet (in row order). Returns how many were done.
+    pub fn load_pending(
+        &mut self,
+        context: &WebGlRenderingContext,
+        manager: &mut TextureManager,
+        geometry: &mut GeometryCache,
+        budget: usize,
+        upto: usize,
+        on_screen: bool, // Row is visible: evicted items in view get reloaded
+    ) -> Result<usize, JsValue> {
+        if let Some(card) = self.background.as_mut().filter(|card| card.buffer.is_none()) {
+            card.init_buffer(context, geometry)?;
+            card.texture = Some(manager.white(context)?);
+        }
+        let pending = self.pending_items(budget, upto, on_screen);
+        for &i in &pending {
+            let item = &mut self.items[i];
+            item.init_buffer(context, geometry).unwrap_or_else(|e| {
                 web_sys::console::error_1(&format!("Buffer error: {}", e).into())
             });
-            let shared_assets = manager.get_texture(context, &item.src)?;
-            item.set_texture(shared_assets.texture, shared_assets.image);
+            let (w, h) = (item.w.ceil() as u32, item.h.ceil() as u32);
+            if let ItemKind::SeeAll(label) = &item.kind {
+                // Own texture, not shared: the label is drawn at the tile's size
+                match crate::text_texture::render_tile(context, &format!("{} →", label), w, h) {
+                    Ok(tile) => item.texture = Some(Rc::new(tile.texture)),
+                    Err(e) => web_sys::console::error_2(&"See all tile failed:".into(), &e),
+                }
+            } else if let ItemKind::Custom(_) = &item.kind {
+                // Nothing to fetch: JS draws it
+            } else {
+                let shared_assets = item.request_texture(context, manager)?;
+                item.set_texture(shared_assets.texture, shared_assets.image);
+            }
         }
-        Ok(())
+        Ok(pending.len())
     }
 
-    // 3. UPDATE LOOP
-    pub fn update(&mut self, context: &WebGlRenderingContext) {
-        // --- SCROLL ANIMATION (LERP) ---
-        let diff = self.target_scroll_x - self.scroll_x;
+    // Which items load_pending sets up: at most `budget` of the first `upto`
+    // without a GPU buffer, in row order. Evicted ones only once they're
+    // visible again (`on_screen` rows).
+    pub fn pending_items(&self, budget: usize, upto: usize, on_screen: bool) -> Vec<usize> {
+        let visible = if on_screen { self.visible_items() } else { None };
+        self.items
+            .iter()
+            .enumerate()
+            .take(upto)
+            .filter(|(i, item)| item.buffer.is_none() && (!item.evicted || visible.is_some_and(|(first, last)| (first..=last).contains(i))))
+            .map(|(i, _)| i)
+            .take(budget)
+            .collect()
+    }
+
+    // Textures the items on (or near) screen draw: pinned against eviction
+    pub fn visible_textures(&self, out: &mut Vec<Rc<WebGlTexture>>) {
+        let Some((first, last)) = self.visible_items() else {
+            return;
+        };
+        for item in &self.items[first..=last] {
+            out.extend(item.textures().cloned());
+        }
+    }
+
+    // Items (hidden ones too) showing one of `textures` give it up
+    pub fn release_textures(&mut self, textures: &[Rc<WebGlTexture>]) {
+        for item in self.items.iter_mut().chain(self.hidden.iter_mut().map(|(_, item)| item)) {
+            if matches!(item.kind, ItemKind::Poster) && item.texture.as_ref().is_some_and(|t| textures.iter().any(|e| Rc::ptr_eq(e, t))) {
+                item.evict_texture();
+            }
+        }
+    }
+
+    // Context lost: drop every GL handle (hidden items too, they may come back)
+    pub fn context_lost(&mut self) {
+        for item in self.items.iter_mut().chain(self.hidden.iter_mut().map(|(_, item)| item)) {
+            item.forget_gl();
+        }
+        if let Some(card) = &mut self.background {
+            card.forget_gl();
+        }
+        self.dirty = true;
+    }
+
+    // Ask for the visible posters again (the manager's texture scale
+    // changed); returns how many got a different texture
+    pub fn reload_textures(&mut self, context: &WebGlRenderingContext, manager: &mut TextureManager) -> Result<usize, JsValue> {
+        let Some((first, last)) = self.visible_items() else {
+            return Ok(0);
+        };
+        let mut reloaded = 0;
+        for item in &mut self.items[first..=last] {
+            let Some(old) = item.texture.clone().filter(|_| matches!(item.kind, ItemKind::Poster)) else {
+                continue;
+            };
+            let shared = item.request_texture(context, manager)?;
+            if Rc::ptr_eq(&old, &shared.texture) {
+                continue;
+            }
+            item.replace_texture(shared.texture, shared.image);
+            manager.retire(old);
+            reloaded += 1;
+        }
+        Ok(reloaded)
+    }
+
+    // Visible posters whose srcset has a sharper fit now (focus pop, new
+    // srcset) swap to it, the current texture staying up until it's loaded
+    pub fn sharpen_srcsets(&mut self, context: &WebGlRenderingContext, manager: &mut TextureManager) -> Result<usize, JsValue> {
+        let Some((first, last)) = self.visible_items() else {
+            return Ok(0);
+        };
+        let mut swapped = 0;
+        for item in &mut self.items[first..=last] {
+            if !matches!(item.kind, ItemKind::Poster) || !item.wants_sharper(manager) {
+                continue;
+            }
+            let Some(old) = item.texture.clone() else {
+                continue; // Not loaded yet: load_pending picks the right one anyway
+            };
+            let shared = item.request_texture(context, manager)?;
+            if !Rc::ptr_eq(&old, &shared.texture) {
+                item.replace_texture(shared.texture, shared.image);
+                manager.retire(old);
+                swapped += 1;
+            }
+        }
+        Ok(swapped)
+    }
+
+    // Skip to the end of every animation: scroll, dim and each item (the
+    // selection pop included, for whichever item is selected right now)
+    pub fn settle_now(&mut self) {
+        self.dragging = false;
+        self.coasting = false;
+        self.scroll_x = self.target_scroll_x;
+        self.scroll_velocity_x = 0.0;
+        self.dim = self.target_dim;
+        self.dim_velocity = 0.0;
+        let snap = self.selection_mode == SelectionMode::Snap;
+        for (i, item) in self.items.iter_mut().enumerate() {
+            item.is_selected = self.is_active && i == self.selected_index && snap;
+            item.settle_now();
+        }
+        self.dirty = true;
+    }
 
-        // Use a nice smooth speed (0.1)
-        if diff.abs() > 0.5 {
-            self.scroll_x += diff * 0.1;
+    // 3. UPDATE LOOP
+    // 3a. Scroll + selection + item animation (no GL)
+    pub fn update_model(&mut self, anim: &AnimationConfig) {
+        // --- SCROLL ANIMATION (anim.scroll_x, snaps when close) ---
+        // The velocity doubles as the spring state
+        if self.dragging {
+            self.scroll_velocity_x = 0.0; // drag_to() moves it directly
+        } else if self.coasting {
+            let prev_scroll_x = self.scroll_x;
+            self.scroll_x = self.clamp_scroll(self.scroll_x + self.drag_velocity);
+            self.target_scroll_x = self.scroll_x;
+            self.drag_velocity *= KINETIC_FRICTION;
+            self.scroll_velocity_x = self.scroll_x - prev_scroll_x;
+            if self.drag_velocity.abs() < KINETIC_STOP_SPEED || self.scroll_velocity_x == 0.0 {
+                self.coasting = false;
+                self.snap_after_coast();
+            }
         } else {
-            self.scroll_x = self.target_scroll_x; // Snap when close
+            self.scroll_x = anim.scroll_x.step(self.scroll_x, self.target_scroll_x, &mut self.scroll_velocity_x, anim.snap_distance());
+        }
+
+        // --- DIM FADE ---
+        let prev_dim = self.dim;
+        self.dim = anim.dim.step(self.dim, self.target_dim, &mut self.dim_velocity, 0.001).clamp(0.0, 1.0);
+        if self.dim != prev_dim {
+            self.dirty = true;
+        }
+
+        let card_before = self.background.as_ref().map(|card| card.current_rect());
+        self.place_background();
+        if self.background.as_ref().map(|card| card.current_rect()) != card_before {
+            self.dirty = true;
         }
 
         for (i, item) in self.items.iter_mut().enumerate() {
             // Update Selection
-            let should_be_selected = self.is_active && (i == self.selected_index);
+            let should_be_selected = self.is_active && (i == self.selected_index) && self.selection_mode == SelectionMode::Snap;
             if item.is_selected != should_be_selected {
                 item.is_selected = should_be_selected;
             }
@@ -126,36 +877,423 @@ impl RowList {
             item.offset_y = self.offset_y; // Vertical (From Parent)
 
             // Call Item Update
-            item.update(context);
+            item.update_model(anim);
+        }
+
+        self.collect_exited();
+    }
+
+    // CULLING ✂️ Inclusive range of items kept on the GPU: on screen + the
+    // wider of cull_margin / render_overscan each side (whatever gets drawn
+    // has to be uploaded). Clamped to the row, None if nothing is on screen.
+    pub fn visible_items(&self) -> Option<(usize, usize)> {
+        self.items_around_screen(self.cull_margin.max(self.render_overscan))
+    }
+
+    // Inclusive range of items drawn: on screen + render_overscan each side
+    pub fn drawn_items(&self) -> Option<(usize, usize)> {
+        self.items_around_screen(self.render_overscan)
+    }
+
+    fn items_around_screen(&self, margin: usize) -> Option<(usize, usize)> {
+        let on_screen = |item: &PosterItem| {
+            let [x, _, w, _] = item.current_rect();
+            x + w > 0.0 && x < self.viewport_width
+        };
+        let first = self.items.iter().position(on_screen)?;
+        let last = self.items.iter().rposition(on_screen).unwrap_or(first);
+        let max = self.items.len() - 1;
+        Some((first.saturating_sub(margin), (last + margin).min(max)))
+    }
+
+    // 3b. Push whatever moved to the GPU (offscreen items stay dirty until they show up)
+    pub fn upload(&mut self, context: &WebGlRenderingContext, geometry: &mut GeometryCache, pixel_ratio: f32) {
+        if let Some(card) = &mut self.background {
+            card.upload(context, geometry);
+        }
+        let Some((first, last)) = self.visible_items() else {
+            return;
+        };
+        for item in &mut self.items[first..=last] {
+            item.upload(context, geometry);
+            item.upload_label(context, geometry, pixel_ratio);
+        }
+    }
+
+    pub fn set_cover_anchor(&mut self, anchor: CoverAnchor) {
+        for item in self.items.iter_mut().chain(self.hidden.iter_mut().map(|(_, item)| item)) {
+            item.cover_anchor = anchor;
+            item.mark_dirty();
+        }
+    }
+
+    pub fn set_corner_radii(&mut self, radii: [f32; 4]) {
+        for item in self.items.iter_mut().chain(self.hidden.iter_mut().map(|(_, item)| item)) {
+            item.set_corner_radii(radii);
         }
     }
 
     // 4. DRAW LOOP
-    pub fn draw(&self, context: &WebGlRenderingContext) {
-        for item in &self.items {
-            if let (Some(texture), Some(buffer)) = (&item.texture, &item.buffer) {
-                context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(buffer));
-
-                context.vertex_attrib_pointer_with_i32(
-                    0,
-                    2,
-                    WebGlRenderingContext::FLOAT,
-                    false,
-                    16,
-                    0,
-                );
-                context.vertex_attrib_pointer_with_i32(
-                    1,
-                    2,
-                    WebGlRenderingContext::FLOAT,
-                    false,
-                    16,
-                    8,
-                );
-
-                context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(texture));
-                context.draw_arrays(WebGlRenderingContext::TRIANGLES, 0, 6);
+    pub fn draw(&self, context: &WebGlRenderingContext, painter: &Painter) {
+        let drawn = self.drawn_items().map_or(&[][..], |(first, last)| &self.items[first..=last]);
+        let visible = || drawn.iter();
+        let uniforms = painter.uniforms;
+
+        // 0. The row's card, under everything else
+        if let Some(card) = &self.background {
+            if let (Some(texture), Some(buffer)) = (card.draw_texture(), &card.buffer) {
+                context.uniform1f(uniforms.dim.as_ref(), self.dim);
+                card.apply_uniforms(context, uniforms);
+                Self::draw_quad(context, uniforms, &IDENTITY, texture, buffer);
+            }
+        }
+
+        // A. Shadows go underneath this row's posters
+        if let Some(shadow_pass) = painter.shadow {
+            let mut begun = false;
+            for shadow in visible().filter_map(|item| item.shadow(shadow_pass.selected_elevation)) {
+                if !begun {
+                    shadow_pass.begin(context);
+                    begun = true;
+                }
+                let (rect, softness, alpha) = shadow;
+                shadow_pass.draw(context, rect, softness, alpha * painter.fade);
+            }
+            if begun {
+                context.use_program(Some(painter.program));
+            }
+        }
+
+        // B. Posters
+        context.uniform1f(uniforms.dim.as_ref(), self.dim);
+        for item in visible() {
+            if let ItemKind::Custom(draw) = &item.kind {
+                if painter.draw_custom(context, draw, item.current_rect(), item.draw_alpha()) {
+                    context.uniform1f(uniforms.dim.as_ref(), self.dim); // Our program is fresh again
+                }
+                continue;
+            }
+            if let (Some(texture), Some(buffer)) = (item.draw_texture(), &item.buffer) {
+                item.apply_uniforms(context, uniforms);
+                Self::draw_quad(context, uniforms, &self.item_transform(item), texture, buffer);
+            }
+            if let Some((texture, buffer)) = item.apply_label_uniforms(context, uniforms) {
+                Self::draw_quad(context, uniforms, &self.item_transform(item), texture, buffer);
+            }
+        }
+
+        // C. Reflections: same quads mirrored under the posters, fading out
+        if let Some(reflection) = self.reflection {
+            for item in visible() {
+                if let (Some(texture), Some(buffer)) = (item.draw_texture(), &item.buffer) {
+                    item.apply_uniforms(context, uniforms);
+                    let fade = item.current_rect()[3] * reflection.height_fraction;
+                    context.uniform2f(uniforms.reflection.as_ref(), reflection.opacity, fade);
+                    let transform = mat4_mul(&self.item_transform(item), &item.mirror_transform(REFLECTION_GAP));
+                    Self::draw_quad(context, uniforms, &transform, texture, buffer);
+                }
+            }
+            context.uniform2f(uniforms.reflection.as_ref(), 0.0, 0.0);
+        }
+    }
+
+    // Coverflow tilt, or nothing
+    fn item_transform(&self, item: &PosterItem) -> [f32; 16] {
+        if self.coverflow {
+            item.rotation_y_transform(self.coverflow_angle(item))
+        } else {
+            IDENTITY
+        }
+    }
+
+    pub fn draw_quad(
+        context: &WebGlRenderingContext,
+        uniforms: &PosterUniforms,
+        transform: &[f32; 16],
+        texture: &WebGlTexture,
+        buffer: &WebGlBuffer,
+    ) {
+        context.uniform_matrix4fv_with_f32_array(uniforms.transform.as_ref(), false, transform);
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(buffer));
+
+        context.vertex_attrib_pointer_with_i32(
+            0,
+            2,
+            WebGlRenderingContext::FLOAT,
+            false,
+            16,
+            0,
+        );
+        context.vertex_attrib_pointer_with_i32(
+            1,
+            2,
+            WebGlRenderingContext::FLOAT,
+            false,
+            16,
+            8,
+        );
+
+        context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(texture));
+        context.draw_arrays(WebGlRenderingContext::TRIANGLES, 0, 6);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Easing;
+
+    // The demo row (10 posters, ids "0".."9") on a 1920 wide screen
+    fn row() -> RowList {
+        let mut row = RowList::from_template(ITEM_START_X, 0, &RowTemplate::demo());
+        for (i, item) in row.items.iter_mut().enumerate() {
+            item.id = i.to_string();
+        }
+        row.is_active = true;
+        row
+    }
+
+    fn slot_x(index: usize) -> f32 {
+        ITEM_START_X + index as f32 * ITEM_SPACING
+    }
+
+    #[test]
+    fn filtering_glides_survivors_into_contiguous_slots() {
+        let mut row = row();
+        let anim = AnimationConfig::new();
+        row.filter(|item| item.id.parse::<usize>().unwrap() % 2 == 0);
+        row.update_model(&anim);
+        assert_eq!(row.items.len(), 10); // Still fading out
+
+        for _ in 0..200 {
+            row.update_model(&anim);
+        }
+        let ids: Vec<&str> = row.items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, ["0", "2", "4", "6", "8"]);
+        for (i, item) in row.items.iter().enumerate() {
+            assert_eq!(item.x, slot_x(i));
+            assert!(item.is_settled());
+        }
+
+        row.clear_filter();
+        assert_eq!(row.items.len(), 10);
+        assert_eq!(row.items[1].id, "1");
+    }
+
+    #[test]
+    fn glide_and_fade_follow_the_animation_config() {
+        let mut row = row();
+        let mut anim = AnimationConfig::new();
+        anim.glide = Easing::Lerp(1.0);
+        anim.fade = Easing::Lerp(1.0);
+        anim.scale = Easing::Lerp(1.0);
+        row.filter(|item| item.id != "0");
+        row.update_model(&anim); // Faded out in one step, then removed
+        assert_eq!(row.items[0].id, "1");
+        row.update_model(&anim); // Glided over in one step
+        assert_eq!(row.items[0].x, slot_x(0));
+    }
+
+    #[test]
+    fn horizontal_wrap_jumps_back_to_the_start() {
+        let mut row = row();
+        row.selected_index = 9;
+        row.handle_input(Action::Right);
+        assert_eq!(row.selected_index, 9); // No wrap by default
+
+        row.set_horizontal_wrap(true);
+        row.handle_input(Action::End);
+        row.settle_now();
+        assert!(row.scroll_x < 0.0);
+        row.handle_input(Action::Right);
+        assert_eq!(row.selected_index, 0);
+        assert_eq!((row.target_scroll_x, row.scroll_x), (0.0, 0.0)); // Jumped, no race back
+    }
+
+    #[test]
+    fn coverflow_tilts_edge_items_and_keeps_the_center_flat() {
+        let mut row = row();
+        row.set_coverflow(true);
+        row.selected_index = 4;
+        row.refocus();
+        row.settle_now();
+        row.update_model(&AnimationConfig::new());
+        assert_eq!(row.coverflow_angle(&row.items[4]), 0.0);
+        assert!(row.coverflow_angle(&row.items[0]) < 0.0);
+        assert!(row.coverflow_angle(&row.items[9]) > 0.0);
+    }
+
+    #[test]
+    fn a_drag_moves_the_row_one_to_one_then_coasts_to_an_item() {
+        let mut row = row();
+        let anim = AnimationConfig::new();
+        row.begin_drag(900.0);
+        row.drag_to(850.0);
+        row.drag_to(800.0);
+        assert_eq!(row.scroll_x, -100.0);
+        assert_eq!(row.target_scroll_x, -100.0);
+
+        row.end_drag();
+        row.update_model(&anim);
+        assert!(row.scroll_x < -100.0); // Momentum carries on...
+        for _ in 0..500 {
+            row.update_model(&anim);
+        }
+        // ...dies down and settles on a whole item
+        assert_eq!(row.scroll_x % ITEM_SPACING, 0.0);
+        assert_eq!(row.scroll_x, row.target_scroll_x);
+    }
+
+    #[test]
+    fn the_see_all_tile_ends_the_row_and_takes_focus() {
+        let mut row = row();
+        row.set_see_all(None, Some("See all"), None);
+        row.set_see_all(None, Some("See all"), None); // Replaces, never stacks
+        assert_eq!(row.items.len(), 11);
+        assert!(!row.see_all_focused());
+
+        row.handle_input(Action::End);
+        assert!(row.see_all_focused());
+        assert_eq!(row.items[10].kind, ItemKind::SeeAll("See all".into()));
+        assert_eq!(row.target_scroll_x, -(row.content_width() - row.viewport_width));
+
+        row.set_see_all(None, None, None);
+        assert_eq!(row.items.len(), 10);
+        assert_eq!(row.selected_index, 9);
+    }
+
+    #[test]
+    fn input_and_update_model_scroll_without_a_context() {
+        let mut row = row();
+        for _ in 0..6 {
+            row.handle_input(Action::Right);
+        }
+        assert_eq!(row.selected_index, 6);
+        assert_eq!(row.target_scroll_x, -2.0 * ITEM_SPACING); // Item 4 is the last one that fits
+        row.update_model(&AnimationConfig::new());
+        assert!(row.scroll_x < 0.0 && row.scroll_x > row.target_scroll_x);
+        assert!(row.items[6].is_selected && row.items[6].offset_x == row.scroll_x);
+    }
+
+    #[test]
+    fn sprite_sheet_cells_are_distinct_and_share_one_image() {
+        let row = RowList::from_sprite_sheet(50.0, "sheet.jpg", 4, 4, 16, ITEM_WIDTH);
+        assert_eq!(row.items.len(), 16);
+        assert!(row.items.iter().all(|item| item.src == "sheet.jpg"));
+        assert_eq!(row.items[5].uv_rect, [0.25, 0.25, 0.5, 0.5]);
+
+        // Cells only touch at their edges
+        for (i, a) in row.items.iter().enumerate() {
+            for b in &row.items[i + 1..] {
+                let [a0, a1, a2, a3] = a.uv_rect;
+                let [b0, b1, b2, b3] = b.uv_rect;
+                assert!(a2 <= b0 || b2 <= a0 || a3 <= b1 || b3 <= a1);
+            }
+        }
+    }
+
+    #[test]
+    fn a_screen_fitting_three_items_scrolls_after_index_two() {
+        let mut row = row();
+        row.set_screen_width(ITEM_START_X + 2.0 * ITEM_SPACING + ITEM_WIDTH);
+        assert_eq!(row.visible_count(), 3);
+
+        row.handle_input(Action::Right);
+        row.handle_input(Action::Right);
+        assert_eq!(row.target_scroll_x, 0.0);
+        row.handle_input(Action::Right);
+        assert_eq!(row.target_scroll_x, -ITEM_SPACING);
+    }
+
+    #[test]
+    fn overscan_draws_two_items_past_each_visible_edge() {
+        let template = RowTemplate {
+            item_count: 20,
+            item_width: ITEM_WIDTH,
+            item_height: ITEM_HEIGHT,
+            src: Box::new(|_, item| format!("{}.jpg", item)),
+        };
+        let mut row = RowList::from_template(50.0, 0, &template);
+        row.is_active = true;
+        for _ in 0..10 {
+            row.handle_input(Action::Right);
+        }
+        row.settle_now();
+        row.update_model(&AnimationConfig::new());
+
+        row.render_overscan = 0;
+        let (first, last) = row.drawn_items().unwrap();
+        assert!(first >= 2 && last + 2 < row.items.len());
+        row.render_overscan = 2;
+        assert_eq!(row.drawn_items(), Some((first - 2, last + 2)));
+    }
+
+    #[test]
+    fn free_scroll_moves_the_content_by_the_step_without_selecting() {
+        let mut row = row();
+        row.set_selection_mode(SelectionMode::FreeScroll(120.0));
+        row.handle_input(Action::Right);
+        row.handle_input(Action::Right);
+        assert_eq!(row.target_scroll_x, -240.0);
+        assert_eq!(row.selected_index, 0);
+
+        row.handle_input(Action::Left);
+        assert_eq!(row.target_scroll_x, -120.0);
+        row.handle_input(Action::Left);
+        row.handle_input(Action::Left);
+        assert_eq!(row.target_scroll_x, 0.0); // Clamped to the start
+    }
+
+    #[test]
+    fn a_load_budget_of_ten_spreads_a_hundred_items_over_ten_updates() {
+        use wasm_bindgen::JsCast;
+        let template = RowTemplate {
+            item_count: 100,
+            item_width: ITEM_WIDTH,
+            item_height: ITEM_HEIGHT,
+            src: Box::new(|_, item| format!("{}.jpg", item)),
+        };
+        let mut row = RowList::from_template(50.0, 0, &template);
+        let mut updates = 0;
+        loop {
+            let pending = row.pending_items(10, usize::MAX, true);
+            if pending.is_empty() {
+                break;
+            }
+            assert!(pending.len() <= 10);
+            // Stand-in for init_buffer: never handed to GL
+            for i in pending {
+                row.items[i].buffer = Some(Rc::new(JsValue::UNDEFINED.unchecked_into()));
             }
+            updates += 1;
+        }
+        assert_eq!(updates, 10);
+    }
+
+    #[test]
+    fn background_card_spans_the_row_band() {
+        let mut row = RowList::from_template(530.0, 1, &RowTemplate::demo());
+        row.items[3].set_size(300.0, 450.0); // One poster taller than the rest
+        row.set_background(Some([0.1, 0.1, 0.1, 1.0]), 8.0);
+        row.update_model(&AnimationConfig::new());
+
+        let card = row.background.as_ref().unwrap();
+        assert_eq!(card.y, row.y - BACKGROUND_PAD);
+        assert_eq!(card.h, row.height() + 2.0 * BACKGROUND_PAD);
+        for item in &row.items {
+            assert!(card.y <= item.y && item.y + item.h <= card.y + card.h);
         }
     }
+
+    #[test]
+    fn nothing_on_screen_is_none_not_item_zero() {
+        assert_eq!(RowList::new(0.0).visible_items(), None);
+
+        let mut row = row();
+        row.viewport_width = 0.0; // No poster overlaps a zero-width screen
+        assert_eq!(row.visible_items(), None);
+        assert_eq!(row.drawn_items(), None);
+        row.viewport_width = 1920.0;
+        assert_eq!(row.visible_items().map(|(first, _)| first), Some(0));
+    }
 }
@@ -0,0 +1,107 @@
+use std::rc::Rc;
+use web_sys::{WebGlBuffer, WebGlProgram, WebGlRenderingContext, WebGlUniformLocation};
+
+// EDGE SCRIMS 🌫️
+// Full-width gradient bands at the top and/or bottom of the screen, drawn
+// over the grid so posters fade out under persistent chrome (top nav, bottom
+// bar) instead of sliding under it with a hard edge. Opaque at the screen
+// edge, transparent `height` pixels inward.
+pub struct ScrimPass {
+    pub top_height: f32,    // Pixels, 0 = no top scrim
+    pub bottom_height: f32, // Pixels, 0 = no bottom scrim
+    pub color: [f32; 3],
+
+    program: Rc<WebGlProgram>,
+    quad: WebGlBuffer,
+    band_loc: Option<WebGlUniformLocation>,
+    from_top_loc: Option<WebGlUniformLocation>,
+    color_loc: Option<WebGlUniformLocation>,
+}
+
+impl ScrimPass {
+    // `program` comes from the ShaderRegistry (ProgramKey::Scrim)
+    pub fn new(context: &WebGlRenderingContext, program: Rc<WebGlProgram>, width: f32, height: f32) -> Result<Self, String> {
+        // Unit square, stretched onto a band in the vertex shader
+        let quad = context.create_buffer().ok_or("Failed to create scrim quad")?;
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&quad));
+        let vertices: [f32; 12] = [
+            0.0, 0.0,
+            0.0, 1.0,
+            1.0, 0.0,
+            1.0, 0.0,
+            0.0, 1.0,
+            1.0, 1.0,
+        ];
+        let vert_array = js_sys::Float32Array::from(vertices.as_slice());
+        context.buffer_data_with_array_buffer_view(WebGlRenderingContext::ARRAY_BUFFER, &vert_array, WebGlRenderingContext::STATIC_DRAW);
+
+        context.use_program(Some(&program));
+        let resolution_loc = context.get_uniform_location(&program, "u_resolution");
+        context.uniform2f(resolution_loc.as_ref(), width, height);
+
+        Ok(Self {
+            top_height: 0.0,
+            bottom_height: 0.0,
+            color: [0.1, 0.1, 0.1], // The clear color, so it blends into the background
+            band_loc: context.get_uniform_location(&program, "u_band"),
+            from_top_loc: context.get_uniform_location(&program, "u_from_top"),
+            color_loc: context.get_uniform_location(&program, "u_color"),
+            program,
+            quad,
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.top_height > 0.0 || self.bottom_height > 0.0
+    }
+
+    // One draw per enabled edge
+    // `height`: bottom edge of the grid area (the viewport rect may end above the canvas')
+    pub fn draw(&self, context: &WebGlRenderingContext, height: f32) {
+        context.use_program(Some(&self.program));
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&self.quad));
+        let pos_loc = context.get_attrib_location(&self.program, "position") as u32;
+        context.vertex_attrib_pointer_with_i32(pos_loc, 2, WebGlRenderingContext::FLOAT, false, 8, 0);
+        context.uniform3f(self.color_loc.as_ref(), self.color[0], self.color[1], self.color[2]);
+
+        if self.top_height > 0.0 {
+            context.uniform2f(self.band_loc.as_ref(), 0.0, self.top_height);
+            context.uniform1f(self.from_top_loc.as_ref(), 1.0);
+            context.draw_arrays(WebGlRenderingContext::TRIANGLES, 0, 6);
+        }
+        if self.bottom_height > 0.0 {
+            context.uniform2f(self.band_loc.as_ref(), height - self.bottom_height, height);
+            context.uniform1f(self.from_top_loc.as_ref(), 0.0);
+            context.draw_arrays(WebGlRenderingContext::TRIANGLES, 0, 6);
+        }
+    }
+
+    pub fn get_vertex_shader() -> &'static str {
+        r#"
+            attribute vec2 position;
+            uniform vec2 u_resolution;
+            uniform vec2 u_band; // (top y, bottom y) in pixels, Y down
+            varying float v_t;   // 0 at the band's top, 1 at its bottom
+            void main() {
+                vec2 pixel = vec2(position.x * u_resolution.x, mix(u_band.x, u_band.y, position.y));
+                vec2 clip = (pixel / u_resolution) * 2.0 - 1.0;
+                gl_Position = vec4(clip.x, -clip.y, 0, 1);
+                v_t = position.y;
+            }
+        "#
+    }
+
+    pub fn get_fragment_shader() -> &'static str {
+        r#"
+            precision mediump float;
+            varying float v_t;
+            uniform float u_from_top; // 1 = opaque at the top edge, 0 = at the bottom
+            uniform vec3 u_color;
+            void main() {
+                float inward = mix(v_t, 1.0 - v_t, u_from_top); // 0 at the screen edge
+                float alpha = 1.0 - smoothstep(0.0, 1.0, inward);
+                gl_FragColor = vec4(u_color, alpha);
+            }
+        "#
+    }
+}
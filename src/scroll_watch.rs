@@ -0,0 +1,72 @@
+use wasm_bindgen::JsValue;
+
+// SCROLL START / END 📣
+// Fed once per frame with "is anything scrolling" and the grid's settled
+// signal (ColumnList::is_settled, which carries the hysteresis), turns those
+// into start/end callbacks for JS (hide overlays while moving, etc).
+pub struct ScrollWatch {
+    pub on_start: Option<js_sys::Function>,
+    pub on_end: Option<js_sys::Function>,
+
+    scrolling: bool,
+}
+
+impl ScrollWatch {
+    pub fn new() -> Self {
+        Self {
+            on_start: None,
+            on_end: None,
+            scrolling: false,
+        }
+    }
+
+    pub fn observe(&mut self, moving: bool, settled: bool) {
+        if moving {
+            if !self.scrolling {
+                self.scrolling = true;
+                fire(&self.on_start, "on_scroll_start");
+            }
+        } else if self.scrolling && settled {
+            self.scrolling = false;
+            fire(&self.on_end, "on_scroll_end");
+        }
+    }
+}
+
+// Call an optional no-argument listener, logging (not propagating) a throw
+pub fn fire(cb: &Option<js_sys::Function>, name: &str) {
+    if let Some(cb) = cb {
+        if let Err(e) = cb.call0(&JsValue::NULL) {
+            web_sys::console::error_2(&format!("{} failed:", name).into(), &e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::columnlist::ColumnList;
+    use crate::keymap::Action;
+
+    #[test]
+    fn a_navigation_starts_and_ends_scrolling_once() {
+        let mut list = ColumnList::new();
+        let mut watch = ScrollWatch::new();
+        let (mut starts, mut ends) = (0, 0);
+        list.handle_input(Action::Down);
+        list.handle_input(Action::Down);
+        for _ in 0..300 {
+            list.update_model();
+            let was = watch.scrolling;
+            watch.observe(list.is_scrolling(), list.is_settled());
+            match (was, watch.scrolling) {
+                (false, true) => starts += 1,
+                (true, false) => ends += 1,
+                _ => {}
+            }
+        }
+        assert_eq!((starts, ends), (1, 1));
+        assert!(list.is_settled());
+    }
+}
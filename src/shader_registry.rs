@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+use web_sys::{WebGlProgram, WebGlRenderingContext};
+
+use crate::blur::BlurPass;
+use crate::debug_overlay::DebugOverlay;
+use crate::posteritem::PosterItem;
+use crate::scrim::ScrimPass;
+use crate::shadow::ShadowPass;
+use crate::spotlight::SpotlightPass;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+}
+
+impl ShaderStage {
+    pub fn gl_type(self) -> u32 {
+        match self {
+            ShaderStage::Vertex => WebGlRenderingContext::VERTEX_SHADER,
+            ShaderStage::Fragment => WebGlRenderingContext::FRAGMENT_SHADER,
+        }
+    }
+}
+
+// What went wrong building a program, with the driver's info log
+#[derive(Clone, PartialEq, Debug)]
+pub enum ShaderError {
+    Compile { stage: ShaderStage, log: String },
+    Link { log: String },
+}
+
+impl fmt::Display for ShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShaderError::Compile { stage: ShaderStage::Vertex, log } => write!(f, "vertex shader failed to compile: {}", log),
+            ShaderError::Compile { stage: ShaderStage::Fragment, log } => write!(f, "fragment shader failed to compile: {}", log),
+            ShaderError::Link { log } => write!(f, "shader program failed to link: {}", log),
+        }
+    }
+}
+
+// JS boundary: readable message
+impl From<ShaderError> for JsValue {
+    fn from(e: ShaderError) -> Self {
+        JsValue::from_str(&e.to_string())
+    }
+}
+
+// Every GPU program the engine knows how to build
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ProgramKey {
+    Textured, // The poster program (always used)
+    Blur,     // Modal background blur
+    Shadow,   // Elevation drop shadows
+    Spotlight, // Vignette around the selected poster
+    Scrim,    // Edge gradients under top/bottom chrome
+    Debug,    // Outlines + labels of the debug overlay
+}
+
+impl ProgramKey {
+    fn sources(self) -> (&'static str, &'static str) {
+        match self {
+            ProgramKey::Textured => (PosterItem::get_vertex_shader(), PosterItem::get_fragment_shader()),
+            ProgramKey::Blur => (BlurPass::get_vertex_shader(), BlurPass::get_fragment_shader()),
+            ProgramKey::Shadow => (ShadowPass::get_vertex_shader(), ShadowPass::get_fragment_shader()),
+            ProgramKey::Spotlight => (SpotlightPass::get_vertex_shader(), SpotlightPass::get_fragment_shader()),
+            ProgramKey::Scrim => (ScrimPass::get_vertex_shader(), ScrimPass::get_fragment_shader()),
+            ProgramKey::Debug => (DebugOverlay::get_vertex_shader(), DebugOverlay::get_fragment_shader()),
+        }
+    }
+}
+
+// LAZY SHADER CACHE 🗃️
+// Programs are compiled + linked the first time somebody asks for them, so a
+// box that never shows a given effect never pays for compiling it.
+pub struct ShaderRegistry {
+    programs: HashMap<ProgramKey, Rc<WebGlProgram>>,
+}
+
+impl ShaderRegistry {
+    pub fn new() -> Self {
+        Self {
+            programs: HashMap::new(),
+        }
+    }
+
+    pub fn get(&mut self, context: &WebGlRenderingContext, key: ProgramKey) -> Result<Rc<WebGlProgram>, ShaderError> {
+        // 1. CHECK CACHE
+        if let Some(program) = self.programs.get(&key) {
+            return Ok(program.clone());
+        }
+
+        // 2. First use: compile + link
+        let (vert_src, frag_src) = key.sources();
+        let vert = crate::compile_shader(context, ShaderStage::Vertex, vert_src)?;
+        let frag = crate::compile_shader(context, ShaderStage::Fragment, frag_src)?;
+        let program = Rc::new(crate::link_program(context, &vert, &frag)?);
+
+        // The shader objects aren't needed once the program is linked
+        context.delete_shader(Some(&vert));
+        context.delete_shader(Some(&frag));
+
+        self.programs.insert(key, program.clone());
+        Ok(program)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The JsValue the JS side sees is this same text (From<ShaderError> goes
+    // through to_string), so checking Display covers both
+    #[test]
+    fn errors_name_the_stage_and_keep_the_log() {
+        let vertex = ShaderError::Compile { stage: ShaderStage::Vertex, log: "ERROR: 0:3: 'pos' : undeclared".into() };
+        assert_eq!(vertex.to_string(), "vertex shader failed to compile: ERROR: 0:3: 'pos' : undeclared");
+
+        let fragment = ShaderError::Compile { stage: ShaderStage::Fragment, log: "ERROR: 0:7: syntax error".into() };
+        assert_eq!(fragment.to_string(), "fragment shader failed to compile: ERROR: 0:7: syntax error");
+
+        let link = ShaderError::Link { log: "varying v_uv not written".into() };
+        assert_eq!(link.to_string(), "shader program failed to link: varying v_uv not written");
+    }
+}
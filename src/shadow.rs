@@ -0,0 +1,96 @@
+use std::rc::Rc;
+use web_sys::{WebGlBuffer, WebGlProgram, WebGlRenderingContext, WebGlUniformLocation};
+
+// DROP SHADOWS 🌑
+// One soft rounded-rect quad per elevated poster, drawn before the posters of
+// its row. The quad is a shared unit square stretched by `u_rect`.
+pub struct ShadowPass {
+    pub selected_elevation: f32, // Extra elevation a fully-selected item gets
+
+    program: Rc<WebGlProgram>,
+    quad: WebGlBuffer,
+    resolution_loc: Option<WebGlUniformLocation>,
+    rect_loc: Option<WebGlUniformLocation>,
+    softness_loc: Option<WebGlUniformLocation>,
+    alpha_loc: Option<WebGlUniformLocation>,
+}
+
+impl ShadowPass {
+    // `program` comes from the ShaderRegistry (ProgramKey::Shadow)
+    pub fn new(context: &WebGlRenderingContext, program: Rc<WebGlProgram>, width: f32, height: f32) -> Result<Self, String> {
+        let quad = context.create_buffer().ok_or("Failed to create shadow quad")?;
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&quad));
+        let vertices: [f32; 12] = [
+            0.0, 0.0,
+            0.0, 1.0,
+            1.0, 0.0,
+            1.0, 0.0,
+            0.0, 1.0,
+            1.0, 1.0,
+        ];
+        let vert_array = js_sys::Float32Array::from(vertices.as_slice());
+        context.buffer_data_with_array_buffer_view(WebGlRenderingContext::ARRAY_BUFFER, &vert_array, WebGlRenderingContext::STATIC_DRAW);
+
+        let pass = Self {
+            selected_elevation: 0.0,
+            resolution_loc: context.get_uniform_location(&program, "u_resolution"),
+            rect_loc: context.get_uniform_location(&program, "u_rect"),
+            softness_loc: context.get_uniform_location(&program, "u_softness"),
+            alpha_loc: context.get_uniform_location(&program, "u_alpha"),
+            program,
+            quad,
+        };
+        context.use_program(Some(&pass.program));
+        context.uniform2f(pass.resolution_loc.as_ref(), width, height);
+        Ok(pass)
+    }
+
+    // 1. Switch to the shadow program (call once per batch of shadows)
+    pub fn begin(&self, context: &WebGlRenderingContext) {
+        context.use_program(Some(&self.program));
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&self.quad));
+        let pos_loc = context.get_attrib_location(&self.program, "position") as u32;
+        context.vertex_attrib_pointer_with_i32(pos_loc, 2, WebGlRenderingContext::FLOAT, false, 8, 0);
+    }
+
+    // 2. One shadow: `rect` is the (already spread) quad in pixels
+    pub fn draw(&self, context: &WebGlRenderingContext, rect: [f32; 4], softness: f32, alpha: f32) {
+        context.uniform4f(self.rect_loc.as_ref(), rect[0], rect[1], rect[2], rect[3]);
+        context.uniform1f(self.softness_loc.as_ref(), softness);
+        context.uniform1f(self.alpha_loc.as_ref(), alpha);
+        context.draw_arrays(WebGlRenderingContext::TRIANGLES, 0, 6);
+    }
+
+    pub fn get_vertex_shader() -> &'static str {
+        r#"
+            attribute vec2 position;
+            uniform vec2 u_resolution;
+            uniform vec4 u_rect;
+            varying vec2 v_pos;
+            void main() {
+                vec2 pixel = u_rect.xy + position * u_rect.zw;
+                vec2 clipSpace = (pixel / u_resolution) * 2.0 - 1.0;
+                gl_Position = vec4(clipSpace.x, clipSpace.y * -1.0, 0, 1);
+                v_pos = pixel;
+            }
+        "#
+    }
+
+    // Rounded box whose edge fades out over `u_softness` pixels
+    pub fn get_fragment_shader() -> &'static str {
+        r#"
+            precision mediump float;
+            varying vec2 v_pos;
+            uniform vec4 u_rect;
+            uniform float u_softness;
+            uniform float u_alpha;
+            void main() {
+                vec2 halfSize = u_rect.zw * 0.5 - vec2(u_softness);
+                vec2 q = abs(v_pos - (u_rect.xy + u_rect.zw * 0.5)) - halfSize;
+                float dist = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0);
+                float a = u_alpha * (1.0 - smoothstep(-u_softness, u_softness, dist));
+                gl_FragColor = vec4(0.0, 0.0, 0.0, a);
+            }
+        "#
+    }
+}
@@ -0,0 +1,92 @@
+use std::rc::Rc;
+use web_sys::{WebGlBuffer, WebGlProgram, WebGlRenderingContext, WebGlUniformLocation};
+
+// FOCUS SPOTLIGHT 🔦
+// Full-screen darkening pass drawn after the grid. Everything but a soft
+// circle around the selected poster gets dimmed; the circle follows the
+// poster's rendered center, so it rides the same scroll/selection LERPs.
+pub struct SpotlightPass {
+    pub enabled: bool,
+    pub intensity: f32, // 0 = no darkening, 1 = black outside the circle
+    pub radius: f32,    // Fully lit radius in pixels (fades out over another radius)
+
+    program: Rc<WebGlProgram>,
+    quad: WebGlBuffer,
+    center_loc: Option<WebGlUniformLocation>,
+    radius_loc: Option<WebGlUniformLocation>,
+    intensity_loc: Option<WebGlUniformLocation>,
+}
+
+impl SpotlightPass {
+    // `program` comes from the ShaderRegistry (ProgramKey::Spotlight)
+    pub fn new(context: &WebGlRenderingContext, program: Rc<WebGlProgram>, width: f32, height: f32) -> Result<Self, String> {
+        let quad = context.create_buffer().ok_or("Failed to create spotlight quad")?;
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&quad));
+        let vertices: [f32; 12] = [
+            -1.0, -1.0,
+            -1.0,  1.0,
+             1.0, -1.0,
+             1.0, -1.0,
+            -1.0,  1.0,
+             1.0,  1.0,
+        ];
+        let vert_array = js_sys::Float32Array::from(vertices.as_slice());
+        context.buffer_data_with_array_buffer_view(WebGlRenderingContext::ARRAY_BUFFER, &vert_array, WebGlRenderingContext::STATIC_DRAW);
+
+        context.use_program(Some(&program));
+        let resolution_loc = context.get_uniform_location(&program, "u_resolution");
+        context.uniform2f(resolution_loc.as_ref(), width, height);
+
+        Ok(Self {
+            enabled: false,
+            intensity: 0.6,
+            radius: 300.0,
+            center_loc: context.get_uniform_location(&program, "u_center"),
+            radius_loc: context.get_uniform_location(&program, "u_radius"),
+            intensity_loc: context.get_uniform_location(&program, "u_intensity"),
+            program,
+            quad,
+        })
+    }
+
+    // `center` is the spotlight's middle in pixels (the selected poster's center)
+    pub fn draw(&self, context: &WebGlRenderingContext, center: [f32; 2]) {
+        context.use_program(Some(&self.program));
+        context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(&self.quad));
+        let pos_loc = context.get_attrib_location(&self.program, "position") as u32;
+        context.vertex_attrib_pointer_with_i32(pos_loc, 2, WebGlRenderingContext::FLOAT, false, 8, 0);
+
+        context.uniform2f(self.center_loc.as_ref(), center[0], center[1]);
+        context.uniform1f(self.radius_loc.as_ref(), self.radius);
+        context.uniform1f(self.intensity_loc.as_ref(), self.intensity);
+        context.draw_arrays(WebGlRenderingContext::TRIANGLES, 0, 6);
+    }
+
+    pub fn get_vertex_shader() -> &'static str {
+        r#"
+            attribute vec2 position;
+            uniform vec2 u_resolution;
+            varying vec2 v_pos;
+            void main() {
+                gl_Position = vec4(position, 0, 1);
+                // Back to pixels (Y down) so the center uses layout coordinates
+                v_pos = vec2(position.x * 0.5 + 0.5, 0.5 - position.y * 0.5) * u_resolution;
+            }
+        "#
+    }
+
+    pub fn get_fragment_shader() -> &'static str {
+        r#"
+            precision mediump float;
+            varying vec2 v_pos;
+            uniform vec2 u_center;
+            uniform float u_radius;
+            uniform float u_intensity;
+            void main() {
+                float dist = length(v_pos - u_center);
+                float dark = u_intensity * smoothstep(u_radius, u_radius * 2.0, dist);
+                gl_FragColor = vec4(0.0, 0.0, 0.0, dark);
+            }
+        "#
+    }
+}
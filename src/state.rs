@@ -0,0 +1,112 @@
+use crate::columnlist::ColumnList;
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+// SAVE / RESTORE 💾
+// Focus + scroll of the whole grid, so coming back to a screen looks exactly
+// like leaving it. Crosses to JS as a plain object:
+// { row, scroll_y, target_scroll_y, rows: [{ index, scroll_x, target_scroll_x }] }
+pub struct RowState {
+    pub selected_index: usize,
+    pub scroll_x: f32,
+    pub target_scroll_x: f32,
+}
+
+pub struct GridState {
+    pub selected_row_index: usize,
+    pub scroll_y: f32,
+    pub target_scroll_y: f32,
+    pub rows: Vec<RowState>,
+}
+
+impl GridState {
+    pub fn capture(list: &ColumnList) -> Self {
+        Self {
+            selected_row_index: list.selected_row_index,
+            scroll_y: list.scroll_y,
+            target_scroll_y: list.target_scroll_y,
+            rows: list
+                .rows
+                .iter()
+                .map(|row| RowState {
+                    selected_index: row.selected_index,
+                    scroll_x: row.scroll_x,
+                    target_scroll_x: row.target_scroll_x,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_js(&self) -> Result<JsValue, JsValue> {
+        let obj = Object::new();
+        set(&obj, "row", self.selected_row_index as f64)?;
+        set(&obj, "scroll_y", self.scroll_y as f64)?;
+        set(&obj, "target_scroll_y", self.target_scroll_y as f64)?;
+        let rows = Array::new();
+        for row in &self.rows {
+            let r = Object::new();
+            set(&r, "index", row.selected_index as f64)?;
+            set(&r, "scroll_x", row.scroll_x as f64)?;
+            set(&r, "target_scroll_x", row.target_scroll_x as f64)?;
+            rows.push(&r);
+        }
+        Reflect::set(&obj, &"rows".into(), &rows)?;
+        Ok(obj.into())
+    }
+
+    pub fn from_js(value: &JsValue) -> Result<Self, JsValue> {
+        let rows = Reflect::get(value, &"rows".into())?
+            .dyn_into::<Array>()
+            .map_err(|_| JsValue::from("state.rows must be an array"))?;
+        Ok(Self {
+            selected_row_index: get(value, "row")? as usize,
+            scroll_y: get(value, "scroll_y")? as f32,
+            target_scroll_y: get(value, "target_scroll_y")? as f32,
+            rows: rows
+                .iter()
+                .map(|r| {
+                    Ok(RowState {
+                        selected_index: get(&r, "index")? as usize,
+                        scroll_x: get(&r, "scroll_x")? as f32,
+                        target_scroll_x: get(&r, "target_scroll_x")? as f32,
+                    })
+                })
+                .collect::<Result<_, JsValue>>()?,
+        })
+    }
+}
+
+fn set(obj: &Object, key: &str, value: f64) -> Result<(), JsValue> {
+    Reflect::set(obj, &key.into(), &value.into())?;
+    Ok(())
+}
+
+fn get(obj: &JsValue, key: &str) -> Result<f64, JsValue> {
+    Reflect::get(obj, &key.into())?
+        .as_f64()
+        .ok_or_else(|| format!("state.{} must be a number", key).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_restores_row_index_and_exact_scroll() {
+        let mut list = ColumnList::new();
+        list.focus(3, 8);
+        for _ in 0..7 {
+            list.update_model(); // Mid-animation on purpose
+        }
+        let state = GridState::capture(&list);
+        let (scroll_x, scroll_y) = (list.rows[3].scroll_x, list.scroll_y);
+
+        let mut restored = ColumnList::new();
+        restored.restore_state(&state);
+        assert_eq!(restored.focused_item(), Some((3, 8)));
+        assert_eq!(restored.rows[3].scroll_x, scroll_x);
+        assert_eq!(restored.rows[3].target_scroll_x, list.rows[3].target_scroll_x);
+        assert_eq!(restored.scroll_y, scroll_y);
+        assert!(restored.rows[3].items[8].is_selected);
+    }
+}
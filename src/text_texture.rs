@@ -0,0 +1,130 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, WebGlRenderingContext, WebGlTexture};
+
+// TEXT -> TEXTURE 🔤
+// Draw a string into a throwaway 2D canvas and upload that as a texture.
+// White text on transparent, so the shader can tint it.
+pub struct TextTexture {
+    pub texture: WebGlTexture,
+    pub width: f32, // Layout size to draw it at
+    pub height: f32,
+    pub pixel_ratio: f32, // Canvas pixels per layout pixel (canvas = width * pixel_ratio)
+}
+
+// Canvas-sized label tile: translucent panel with `text` centered on it
+// (used for the "See All →" item, drawn at exactly width x height)
+pub fn render_tile(context: &WebGlRenderingContext, text: &str, width: u32, height: u32) -> Result<TextTexture, JsValue> {
+    let document = web_sys::window().and_then(|w| w.document()).ok_or("no document")?;
+    let canvas = document.create_element("canvas")?.dyn_into::<HtmlCanvasElement>()?;
+    canvas.set_width(width.max(1));
+    canvas.set_height(height.max(1));
+    let ctx = canvas
+        .get_context("2d")?
+        .ok_or("no 2d context")?
+        .dyn_into::<CanvasRenderingContext2d>()?;
+
+    // 1. Panel
+    ctx.set_fill_style(&JsValue::from_str("rgba(255, 255, 255, 0.15)"));
+    ctx.fill_rect(0.0, 0.0, width as f64, height as f64);
+
+    // 2. Label, centered
+    ctx.set_font(&format!("{}px sans-serif", (height as f32 * 0.14).round()));
+    ctx.set_text_align("center");
+    ctx.set_text_baseline("middle");
+    ctx.set_fill_style(&JsValue::from_str("white"));
+    ctx.fill_text(text, width as f64 / 2.0, height as f64 / 2.0)?;
+
+    // 3. Upload
+    let texture = upload_canvas(context, &canvas)?;
+    Ok(TextTexture {
+        texture,
+        width: width as f32,
+        height: height as f32,
+        pixel_ratio: 1.0,
+    })
+}
+
+// Full-width menu bar: translucent panel, `text` left-aligned `indent`
+// pixels in (room for an icon), vertically centered
+pub fn render_bar(context: &WebGlRenderingContext, text: &str, width: u32, height: u32, indent: f32) -> Result<TextTexture, JsValue> {
+    let document = web_sys::window().and_then(|w| w.document()).ok_or("no document")?;
+    let canvas = document.create_element("canvas")?.dyn_into::<HtmlCanvasElement>()?;
+    canvas.set_width(width.max(1));
+    canvas.set_height(height.max(1));
+    let ctx = canvas
+        .get_context("2d")?
+        .ok_or("no 2d context")?
+        .dyn_into::<CanvasRenderingContext2d>()?;
+
+    // 1. Panel
+    ctx.set_fill_style(&JsValue::from_str("rgba(255, 255, 255, 0.1)"));
+    ctx.fill_rect(0.0, 0.0, width as f64, height as f64);
+
+    // 2. Label
+    ctx.set_font(&format!("{}px sans-serif", (height as f32 * 0.4).round()));
+    ctx.set_text_align("left");
+    ctx.set_text_baseline("middle");
+    ctx.set_fill_style(&JsValue::from_str("white"));
+    ctx.fill_text(text, indent as f64, height as f64 / 2.0)?;
+
+    // 3. Upload
+    let texture = upload_canvas(context, &canvas)?;
+    Ok(TextTexture {
+        texture,
+        width: width as f32,
+        height: height as f32,
+        pixel_ratio: 1.0,
+    })
+}
+
+// `font_px` is in layout pixels; the canvas is rendered `pixel_ratio` times
+// bigger so the text stays crisp on high-DPR screens (drawn back at font size)
+pub fn render_text(context: &WebGlRenderingContext, text: &str, font_px: f32, pixel_ratio: f32) -> Result<TextTexture, JsValue> {
+    let pixel_ratio = if pixel_ratio.is_finite() && pixel_ratio > 0.0 { pixel_ratio } else { 1.0 };
+    let document = web_sys::window().and_then(|w| w.document()).ok_or("no document")?;
+    let canvas = document.create_element("canvas")?.dyn_into::<HtmlCanvasElement>()?;
+    let ctx = canvas
+        .get_context("2d")?
+        .ok_or("no 2d context")?
+        .dyn_into::<CanvasRenderingContext2d>()?;
+
+    // 1. Measure at backing resolution (the canvas resets its state when
+    // resized, so set the font twice)
+    let font = format!("{}px monospace", font_px * pixel_ratio);
+    ctx.set_font(&font);
+    let width = ctx.measure_text(text)?.width().ceil().max(1.0) as u32;
+    let height = (font_px * pixel_ratio * 1.25).ceil() as u32;
+    canvas.set_width(width);
+    canvas.set_height(height);
+
+    // 2. Draw
+    ctx.set_font(&font);
+    ctx.set_text_baseline("top");
+    ctx.set_fill_style(&JsValue::from_str("white"));
+    ctx.fill_text(text, 0.0, 0.0)?;
+
+    // 3. Upload
+    let texture = upload_canvas(context, &canvas)?;
+    Ok(TextTexture {
+        texture,
+        width: width as f32 / pixel_ratio,
+        height: height as f32 / pixel_ratio,
+        pixel_ratio,
+    })
+}
+
+fn upload_canvas(context: &WebGlRenderingContext, canvas: &HtmlCanvasElement) -> Result<WebGlTexture, JsValue> {
+    let texture = context.create_texture().ok_or("failed to create texture")?;
+    context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&texture));
+    context.tex_image_2d_with_u32_and_u32_and_canvas(
+        WebGlRenderingContext::TEXTURE_2D,
+        0,
+        WebGlRenderingContext::RGBA as i32,
+        WebGlRenderingContext::RGBA,
+        WebGlRenderingContext::UNSIGNED_BYTE,
+        canvas,
+    )?;
+    crate::texture_manager::apply_texture_params(context);
+    Ok(texture)
+}
@@ -1,111 +1,850 @@
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlImageElement, WebGlRenderingContext, WebGlTexture};
+use web_sys::{HtmlCanvasElement, HtmlImageElement, WebGlRenderingContext, WebGlTexture};
+
+// How long one image took from set_src to onload / onerror
+#[derive(Clone)]
+pub struct LoadTiming {
+    pub url: String,
+    pub ms: f64,
+    pub ok: bool, // false = onerror fired
+}
+
+// GPU pixel format for uploads. Rgb saves 25% VRAM on opaque JPEG posters;
+// Rgba is needed for anything with transparency.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TextureFormat {
+    Rgb,
+    Rgba,
+}
+
+impl TextureFormat {
+    pub fn gl_format(self) -> u32 {
+        match self {
+            TextureFormat::Rgb => WebGlRenderingContext::RGB,
+            TextureFormat::Rgba => WebGlRenderingContext::RGBA,
+        }
+    }
+
+    pub fn bytes_per_pixel(self) -> u32 {
+        match self {
+            TextureFormat::Rgb => 3,
+            TextureFormat::Rgba => 4,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct SharedTexture {
     pub texture: Rc<WebGlTexture>,
     pub image: Rc<HtmlImageElement>,
+    pub format: TextureFormat, // What it was created with
+}
+
+// A texture still showing a placeholder. Weak: waiting for an image must not
+// keep a cleared texture alive (strong_count = cache + items only).
+struct PendingTexture {
+    texture: Weak<WebGlTexture>,
+    image: Rc<HtmlImageElement>,
+    format: TextureFormat,
+    shaped: bool, // Has a provider placeholder: the branded image mustn't replace it
+}
+
+// A render scale / DPR change this big (relative) re-requests the visible
+// posters, once it has held for RESCALE_SETTLE_MS
+const RESCALE_STEP: f32 = 0.25;
+const RESCALE_SETTLE_MS: f64 = 1000.0;
+
+// Aspect buckets for shaped placeholders: ratios within 5% share one canvas
+// (log-spaced, so the bucket width is relative to the ratio)
+fn aspect_key(width: u32, height: u32) -> i32 {
+    let ratio = width.max(1) as f32 / height.max(1) as f32;
+    (ratio.ln() / 1.05f32.ln()).round() as i32
 }
 
 pub struct TextureManager {
     cache: HashMap<String, SharedTexture>,
+
+    // JS hook: (src, width, height) -> url actually loaded (e.g. CDN "?w=300")
+    url_transform: Option<js_sys::Function>,
+
+    // BRANDED PLACEHOLDER 🏷️ (blue pixel until this image itself arrives)
+    placeholder: Option<Rc<HtmlImageElement>>,
+    // Textures still waiting for their real image (get the placeholder late)
+    pending: Rc<RefCell<Vec<PendingTexture>>>,
+
+    // SHAPED PLACEHOLDERS 🃏 JS hook: (width, height) -> <canvas> drawn for a
+    // box of that size (grey rounded card...), made once per aspect bucket
+    placeholder_provider: Option<js_sys::Function>,
+    shaped_placeholders: HashMap<i32, Option<HtmlCanvasElement>>,
+
+    // STARTUP PROGRESS 📶 Images requested for the first screen (until
+    // end_initial_loads), for a "Loading 60%" indicator
+    initial_loads: Vec<Rc<HtmlImageElement>>,
+    initial_open: bool,
+
+    // Filled in by the onload/onerror closures (find the slow CDNs!)
+    timings: Rc<RefCell<Vec<LoadTiming>>>,
+
+    // Give up on a load that neither loads nor errors after this long (0 = never)
+    load_timeout_ms: u32,
+
+    // UNPACK_FLIP_Y_WEBGL for image uploads. <img> decodes top-down already so
+    // this stays off; it's for sources that arrive bottom-up (canvas/video paths).
+    flip_y: bool,
+
+    // Applies to textures created from now on
+    format: TextureFormat,
+
+    // RESOLUTION-AWARE SIZES 🔭 Requested sizes are box size * this (drawing
+    // buffer pixels per layout pixel), so a CDN transform can send what is
+    // really shown. None until the first observe_scale.
+    texture_scale: Option<f32>,
+    rescale_since: Option<f64>, // When the current big scale change was first seen
+    retired: Vec<Rc<WebGlTexture>>, // Replaced by a reload, deleted once no item shows them
+
+    // 1x1 white, for solid quads that get their color from u_tint (row cards)
+    white: Option<Rc<WebGlTexture>>,
+
+    // CONTEXT LOSS 💥 Downloaded images whose textures died with the old
+    // context: get_texture uploads them again instead of fetching
+    restorable: HashMap<String, Rc<HtmlImageElement>>,
+
+    // VRAM BUDGET 🧮 (0 = unlimited) and when each cache entry was last
+    // requested (a counter, not a clock), for least-recently-used eviction
+    pub budget_bytes: u64,
+    last_request: HashMap<String, u64>,
+    request_count: u64,
 }
 
 impl TextureManager {
     pub fn new() -> Self {
         Self {
             cache: HashMap::new(),
+            url_transform: None,
+            placeholder: None,
+            pending: Rc::new(RefCell::new(Vec::new())),
+            placeholder_provider: None,
+            shaped_placeholders: HashMap::new(),
+            initial_loads: Vec::new(),
+            initial_open: true,
+            timings: Rc::new(RefCell::new(Vec::new())),
+            load_timeout_ms: 10_000,
+            flip_y: false,
+            format: TextureFormat::Rgba,
+            texture_scale: None,
+            rescale_since: None,
+            retired: Vec::new(),
+            white: None,
+            restorable: HashMap::new(),
+            budget_bytes: 0,
+            last_request: HashMap::new(),
+            request_count: 0,
+        }
+    }
+
+    // Every texture is gone (the context was lost and restored). Keeps the
+    // images that had arrived; ones still loading start over when asked for.
+    pub fn context_lost(&mut self) {
+        for (url, shared) in self.cache.drain() {
+            if is_loaded(&shared.image) {
+                self.restorable.insert(url, shared.image);
+            }
         }
+        self.pending.borrow_mut().clear();
+        self.retired.clear();
+        self.white = None;
     }
 
+    // Shared white pixel (made on first use)
+    pub fn white(&mut self, context: &WebGlRenderingContext) -> Result<Rc<WebGlTexture>, JsValue> {
+        if let Some(white) = &self.white {
+            return Ok(white.clone());
+        }
+        let texture = Rc::new(context.create_texture().ok_or("failed to create texture")?);
+        context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&texture));
+        upload_pixel(context, [255, 255, 255, 255], TextureFormat::Rgba)?;
+        apply_texture_params(context);
+        self.white = Some(texture.clone());
+        Ok(texture)
+    }
+
+    // Fed every frame with the current pixel ratio. True once it has moved
+    // RESCALE_STEP away from the one the textures were sized for and stayed
+    // there RESCALE_SETTLE_MS (adaptive resolution hunting around a target
+    // doesn't reload anything); requests use the new scale from then on.
+    pub fn observe_scale(&mut self, ratio: f32, now: f64) -> bool {
+        if !ratio.is_finite() || ratio <= 0.0 {
+            return false;
+        }
+        let Some(current) = self.texture_scale else {
+            self.texture_scale = Some(ratio);
+            return false;
+        };
+        if (ratio / current - 1.0).abs() < RESCALE_STEP {
+            self.rescale_since = None;
+            return false;
+        }
+        let since = *self.rescale_since.get_or_insert(now);
+        if now - since < RESCALE_SETTLE_MS {
+            return false;
+        }
+        self.rescale_since = None;
+        self.texture_scale = Some(ratio);
+        self.url_transform.is_some() // Same URL at any size otherwise: nothing to reload
+    }
+
+    // `old` was swapped out by a reload; it goes once nothing draws it any more
+    pub fn retire(&mut self, old: Rc<WebGlTexture>) {
+        if !self.retired.iter().any(|t| Rc::ptr_eq(t, &old)) {
+            self.retired.push(old);
+        }
+    }
+
+    // Delete retired textures only the cache (and this list) still hold
+    pub fn sweep_retired(&mut self, context: &WebGlRenderingContext) {
+        if self.retired.is_empty() {
+            return;
+        }
+        let cache = &mut self.cache;
+        self.retired.retain(|texture| {
+            let in_cache = cache.values().any(|shared| Rc::ptr_eq(&shared.texture, texture));
+            if Rc::strong_count(texture) > 1 + in_cache as usize {
+                return true;
+            }
+            cache.retain(|_, shared| !Rc::ptr_eq(&shared.texture, texture));
+            context.delete_texture(Some(texture));
+            false
+        });
+    }
+
+    pub fn set_load_timeout_ms(&mut self, ms: u32) {
+        self.load_timeout_ms = ms;
+    }
+
+    // Applies to textures requested from now on (cached ones keep their orientation)
+    pub fn set_flip_y(&mut self, flip_y: bool) {
+        self.flip_y = flip_y;
+    }
+
+    pub fn set_upload_format(&mut self, format: TextureFormat) {
+        self.format = format;
+    }
+
+    // Rough VRAM use of every cached texture: decoded size x bytes per pixel
+    // (1x1 while still loading or failed)
+    pub fn estimated_bytes(&self) -> u64 {
+        self.cache.values().map(texture_bytes).sum()
+    }
+
+    pub fn is_over_budget(&self) -> bool {
+        self.budget_bytes > 0 && self.estimated_bytes() > self.budget_bytes
+    }
+
+    fn touch(&mut self, src: &str) {
+        self.request_count += 1;
+        self.last_request.insert(src.to_string(), self.request_count);
+    }
+
+    // Cached entries in eviction order: least recently requested first,
+    // never one in `pinned` (what visible posters show)
+    fn eviction_order(&self, pinned: &[Rc<WebGlTexture>]) -> Vec<(&String, &SharedTexture)> {
+        let mut order: Vec<_> = self
+            .cache
+            .iter()
+            .filter(|(_, shared)| !pinned.iter().any(|p| Rc::ptr_eq(p, &shared.texture)))
+            .collect();
+        order.sort_by_key(|(src, _)| self.last_request.get(*src).copied().unwrap_or(0));
+        order
+    }
+
+    // What evict_unused would need gone to get back under budget, oldest
+    // first: offscreen posters drop these (ColumnList::release_textures) so
+    // they become unused
+    pub fn eviction_candidates(&self, pinned: &[Rc<WebGlTexture>]) -> Vec<Rc<WebGlTexture>> {
+        let mut excess = self.estimated_bytes().saturating_sub(self.budget_bytes);
+        if self.budget_bytes == 0 || excess == 0 {
+            return Vec::new();
+        }
+        let mut candidates = Vec::new();
+        for (_, shared) in self.eviction_order(pinned) {
+            if excess == 0 {
+                break;
+            }
+            excess = excess.saturating_sub(texture_bytes(shared));
+            candidates.push(shared.texture.clone());
+        }
+        candidates
+    }
+
+    // EVICTION 🗑️ Over budget: delete least recently requested textures
+    // until it fits again. Pinned ones (visible posters) always survive, even
+    // if they were requested long ago, and so does anything something still
+    // holds. Returns how many were deleted.
+    pub fn evict_unused(&mut self, context: &WebGlRenderingContext, pinned: &[Rc<WebGlTexture>]) -> usize {
+        if !self.is_over_budget() {
+            return 0;
+        }
+        let mut bytes = self.estimated_bytes();
+        let mut evicted = Vec::new();
+        for (src, shared) in self.eviction_order(pinned) {
+            if bytes <= self.budget_bytes {
+                break;
+            }
+            if Rc::strong_count(&shared.texture) > 1 {
+                continue; // Still drawn (menu icon, hero art...)
+            }
+            bytes -= texture_bytes(shared);
+            evicted.push(src.clone());
+        }
+        for src in &evicted {
+            if let Some(shared) = self.cache.remove(src) {
+                context.delete_texture(Some(&shared.texture));
+                shared.image.set_src(""); // Stop a download that's still running
+            }
+            self.last_request.remove(src);
+        }
+        self.pending.borrow_mut().retain(|p| p.texture.strong_count() > 0);
+        evicted.len()
+    }
+
+    // The first screen is fully requested: later loads don't count towards it
+    pub fn end_initial_loads(&mut self) {
+        self.initial_open = false;
+    }
+
+    // Share of the first screen's images that have finished (failed and
+    // timed out ones count as finished, or it would never reach 1.0)
+    pub fn initial_load_progress(&self) -> f32 {
+        if self.initial_loads.is_empty() {
+            return if self.initial_open { 0.0 } else { 1.0 };
+        }
+        let done = self.initial_loads.iter().filter(|img| img.complete()).count();
+        done as f32 / self.initial_loads.len() as f32
+    }
+
+    // Every finished load so far (url, milliseconds, success), failures included
+    pub fn load_timings(&self) -> Vec<LoadTiming> {
+        self.timings.borrow().clone()
+    }
+
+    // Show `src` in every poster until its own image has loaded
+    pub fn set_placeholder_image(&mut self, context: &WebGlRenderingContext, src: &str) {
+        let img = Rc::new(HtmlImageElement::new().unwrap());
+        if needs_cors(src) {
+            img.set_cross_origin(Some("anonymous"));
+        }
+
+        // When the placeholder arrives, swap it into everything still loading
+        let img_clone = img.clone();
+        let pending = self.pending.clone();
+        let context_clone = context.clone();
+        let flip_y = self.flip_y;
+        let closure = Closure::wrap(Box::new(move || {
+            for waiting in pending.borrow().iter().filter(|p| !p.shaped && !is_loaded(&p.image)) {
+                let Some(texture) = waiting.texture.upgrade() else {
+                    continue;
+                };
+                context_clone.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&texture));
+                let _ = upload_image(&context_clone, &img_clone, flip_y, waiting.format);
+            }
+        }) as Box<dyn FnMut()>);
+
+        img.set_onload(Some(closure.as_ref().unchecked_ref()));
+        closure.forget();
+        img.set_src(src);
+        self.placeholder = Some(img);
+    }
+
+    // Install (or clear with None) the shaped placeholder hook. Affects
+    // textures created from now on; canvases made so far are dropped.
+    pub fn set_placeholder_provider(&mut self, cb: Option<js_sys::Function>) {
+        self.placeholder_provider = cb;
+        self.shaped_placeholders.clear();
+    }
+
+    // The provider's canvas for a width x height box (None = no provider, or
+    // it didn't return a canvas)
+    fn shaped_placeholder(&mut self, width: u32, height: u32) -> Option<&HtmlCanvasElement> {
+        let cb = self.placeholder_provider.as_ref()?;
+        // Misses are remembered too, or a broken provider runs for every texture
+        self.shaped_placeholders
+            .entry(aspect_key(width, height))
+            .or_insert_with(|| match cb.call2(&JsValue::NULL, &width.into(), &height.into()) {
+                Ok(result) => {
+                    let canvas = result.dyn_into::<HtmlCanvasElement>().ok();
+                    if canvas.is_none() {
+                        web_sys::console::warn_1(&"placeholder_provider didn't return a canvas, using the default".into());
+                    }
+                    canvas
+                }
+                Err(e) => {
+                    web_sys::console::error_2(&"placeholder_provider failed:".into(), &e);
+                    None
+                }
+            })
+            .as_ref()
+    }
+
+    // FLUSH 🧹 Delete every cached texture nothing else holds (strong_count 1
+    // = only the cache). Ones still used by items stay cached. Returns how
+    // many were deleted.
+    pub fn clear(&mut self, context: &WebGlRenderingContext) -> usize {
+        let before = self.cache.len();
+        self.cache.retain(|_, shared| {
+            if Rc::strong_count(&shared.texture) > 1 {
+                return true;
+            }
+            context.delete_texture(Some(&shared.texture));
+            shared.image.set_src(""); // Stop a download that's still running
+            false
+        });
+        let cache = &self.cache;
+        self.last_request.retain(|src, _| cache.contains_key(src));
+        self.pending.borrow_mut().retain(|p| p.texture.strong_count() > 0);
+        before - self.cache.len()
+    }
+
+    // PRE-WARM 🔥 Start loading `srcs` (sized for width x height boxes) so
+    // items created later find them in the cache
+    pub fn warm(&mut self, context: &WebGlRenderingContext, srcs: &[&str], width: u32, height: u32) -> Result<(), JsValue> {
+        for src in srcs {
+            self.get_texture(context, src, width, height)?;
+        }
+        Ok(())
+    }
+
+    // Install (or clear with None) the URL transformer. Default is identity.
+    pub fn set_url_transform(&mut self, cb: Option<js_sys::Function>) {
+        self.url_transform = cb;
+    }
+
+    // SRCSET 🖼️ Of several (url, width px) versions of one image, the smallest
+    // at least `target_px` layout pixels wide once the texture scale (DPR x
+    // render scale) is applied; the widest one if none is that big
+    pub fn pick_srcset<'a>(&self, candidates: &'a [(String, u32)], target_px: u32) -> Option<&'a (String, u32)> {
+        let needed = (target_px as f32 * self.texture_scale.unwrap_or(1.0)).ceil() as u32;
+        candidates
+            .iter()
+            .filter(|(_, width)| *width >= needed)
+            .min_by_key(|(_, width)| *width)
+            .or_else(|| candidates.iter().max_by_key(|(_, width)| *width))
+    }
+
+    // get_texture for the pick_srcset choice (box `target_px` x `height`);
+    // also returns the chosen candidate's width
+    pub fn get_texture_srcset(
+        &mut self,
+        context: &WebGlRenderingContext,
+        candidates: &[(String, u32)],
+        target_px: u32,
+        height: u32,
+    ) -> Result<(SharedTexture, u32), JsValue> {
+        let (url, width) = self.pick_srcset(candidates, target_px).ok_or("empty srcset")?.clone();
+        Ok((self.get_texture(context, &url, target_px, height)?, width))
+    }
+
+    // Map an item's src to the URL we really fetch for a box of width x height
+    pub fn resolve_url(&self, src: &str, width: u32, height: u32) -> String {
+        let Some(cb) = &self.url_transform else {
+            return src.to_string();
+        };
+        match cb.call3(&JsValue::NULL, &src.into(), &width.into(), &height.into()) {
+            Ok(url) => url.as_string().unwrap_or_else(|| src.to_string()),
+            Err(e) => {
+                web_sys::console::error_2(&"url_transform failed:".into(), &e);
+                src.to_string()
+            }
+        }
+    }
+
+    // `width`/`height` are the target pixel size of the box the image fills
     pub fn get_texture(
         &mut self,
         context: &WebGlRenderingContext,
         src: &str,
+        width: u32,
+        height: u32,
     ) -> Result<SharedTexture, JsValue> {
+        let scale = self.texture_scale.unwrap_or(1.0);
+        let (width, height) = ((width as f32 * scale).ceil() as u32, (height as f32 * scale).ceil() as u32);
+        let src = &self.resolve_url(src, width, height);
+
         // 1. CHECK CACHE: If we already loaded this URL, return the saved one!
-        if let Some(shared) = self.cache.get(src) {
-            return Ok(shared.clone());
+        if let Some(shared) = self.cache.get(src).cloned() {
+            self.touch(src);
+            return Ok(shared);
+        }
+
+        // 1b. RESTORE: image survived a context loss, only the upload is needed
+        if let Some(image) = self.restorable.remove(src) {
+            let texture = Rc::new(context.create_texture().ok_or("failed to create texture")?);
+            context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&texture));
+            upload_image(context, &image, self.flip_y, self.format)?;
+            apply_texture_params(context);
+            let shared = SharedTexture { texture, image, format: self.format };
+            self.cache.insert(src.to_string(), shared.clone());
+            self.touch(src);
+            return Ok(shared);
         }
 
         //if not
         let texture = context.create_texture().ok_or("failed to create texture")?;
         let texture_rc = Rc::new(texture); // Wrap in Shared Pointer
 
-        // B. Bind & Set Blue Placeholder
+        // B. Bind & Set Placeholder (shaped one for this box, else branded
+        // image if it's ready, else blue)
         context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&texture_rc));
-        let blue_pixel: [u8; 4] = [0, 0, 255, 255];
-        context.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
-            WebGlRenderingContext::TEXTURE_2D,
-            0,
-            WebGlRenderingContext::RGBA as i32,
-            1,
-            1,
-            0,
-            WebGlRenderingContext::RGBA,
-            WebGlRenderingContext::UNSIGNED_BYTE,
-            Some(&blue_pixel),
-        )?;
+        let (flip_y, format) = (self.flip_y, self.format);
+        let shaped = match self.shaped_placeholder(width, height) {
+            Some(canvas) => {
+                upload_canvas(context, canvas, flip_y, format)?;
+                true
+            }
+            None => false,
+        };
+        if !shaped {
+            match self.placeholder.as_ref().filter(|img| is_loaded(img)) {
+                Some(placeholder) => upload_image(context, placeholder, flip_y, format)?,
+                None => upload_pixel(context, BLUE_PIXEL, format)?,
+            }
+        }
+        // NPOT-safe from the start (a placeholder image is rarely a power of two)
+        apply_texture_params(context);
 
         // C. Create Image Element
         let img = HtmlImageElement::new().unwrap();
-        img.set_cross_origin(Some("anonymous"));
+        // data:/blob: URLs are same-origin already; forcing CORS on them can
+        // make the load fail outright (blob: especially), so leave it unset.
+        if needs_cors(src) {
+            img.set_cross_origin(Some("anonymous"));
+        }
         let img_rc = Rc::new(img); // Wrap in Shared Pointer
 
         // D. Setup Async Loading (Closure)
-        let texture_clone = texture_rc.clone();
+        // Weak, so a clear() can free the texture while its image is in flight
+        let texture_weak = Rc::downgrade(&texture_rc);
         let img_clone = img_rc.clone();
         let context_clone = context.clone();
+        let started_at = crate::clock::now_ms();
+        let timings = self.timings.clone();
+        let url = src.to_string();
+        let flip_y = self.flip_y;
+        let format = self.format;
+        let settled = Rc::new(Cell::new(false)); // onload, onerror or timeout: first one wins
+        let timeout_handle = Rc::new(Cell::new(None::<i32>));
 
+        let settled_clone = settled.clone();
+        let timeout_clone = timeout_handle.clone();
         let closure = Closure::wrap(Box::new(move || {
-            context_clone.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&texture_clone));
-            let _ = context_clone.tex_image_2d_with_u32_and_u32_and_image(
-                WebGlRenderingContext::TEXTURE_2D,
-                0,
-                WebGlRenderingContext::RGBA as i32,
-                WebGlRenderingContext::RGBA,
-                WebGlRenderingContext::UNSIGNED_BYTE,
-                &img_clone,
-            );
+            if settled_clone.replace(true) {
+                return;
+            }
+            let Some(texture) = texture_weak.upgrade() else {
+                return; // Cleared meanwhile
+            };
+            if let (Some(window), Some(handle)) = (web_sys::window(), timeout_clone.get()) {
+                window.clear_timeout_with_handle(handle);
+            }
+            timings.borrow_mut().push(LoadTiming {
+                url: url.clone(),
+                ms: crate::clock::now_ms() - started_at,
+                ok: true,
+            });
+
+            context_clone.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&texture));
+            let _ = upload_image(&context_clone, &img_clone, flip_y, format);
 
             // Safe parameters for any size
-            context_clone.tex_parameteri(
-                WebGlRenderingContext::TEXTURE_2D,
-                WebGlRenderingContext::TEXTURE_WRAP_S,
-                WebGlRenderingContext::CLAMP_TO_EDGE as i32,
-            );
-            context_clone.tex_parameteri(
-                WebGlRenderingContext::TEXTURE_2D,
-                WebGlRenderingContext::TEXTURE_WRAP_T,
-                WebGlRenderingContext::CLAMP_TO_EDGE as i32,
-            );
-            context_clone.tex_parameteri(
-                WebGlRenderingContext::TEXTURE_2D,
-                WebGlRenderingContext::TEXTURE_MIN_FILTER,
-                WebGlRenderingContext::LINEAR as i32,
-            );
-            context_clone.tex_parameteri(
-                WebGlRenderingContext::TEXTURE_2D,
-                WebGlRenderingContext::TEXTURE_MAG_FILTER,
-                WebGlRenderingContext::LINEAR as i32,
-            );
+            apply_texture_params(&context_clone);
         }) as Box<dyn FnMut()>);
 
+        // E. Failure path (onerror OR timeout): timed too, then the error texture
+        let fail: Rc<dyn Fn()> = {
+            let settled = settled.clone();
+            let timings = self.timings.clone();
+            let url = src.to_string();
+            let texture = Rc::downgrade(&texture_rc);
+            let context = context.clone();
+            Rc::new(move || {
+                if settled.replace(true) {
+                    return;
+                }
+                let Some(texture) = texture.upgrade() else {
+                    return; // Cleared meanwhile (clear() empties src, which lands here)
+                };
+                timings.borrow_mut().push(LoadTiming {
+                    url: url.clone(),
+                    ms: crate::clock::now_ms() - started_at,
+                    ok: false,
+                });
+                context.bind_texture(WebGlRenderingContext::TEXTURE_2D, Some(&texture));
+                let _ = upload_pixel(&context, ERROR_PIXEL, format);
+            })
+        };
+
+        let fail_clone = fail.clone();
+        let on_error = Closure::wrap(Box::new(move || fail_clone()) as Box<dyn FnMut()>);
+
         img_rc.set_onload(Some(closure.as_ref().unchecked_ref()));
+        img_rc.set_onerror(Some(on_error.as_ref().unchecked_ref()));
         closure.forget();
+        on_error.forget();
         img_rc.set_src(src);
 
+        // F. Hung connection guard: neither onload nor onerror in time = failure
+        if self.load_timeout_ms > 0 {
+            if let Some(window) = web_sys::window() {
+                let img_clone = img_rc.clone();
+                let on_timeout = Closure::once_into_js(move || {
+                    fail();
+                    img_clone.set_src(""); // Abort the request (its onerror is ignored now)
+                });
+                let handle = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                    on_timeout.unchecked_ref(),
+                    self.load_timeout_ms as i32,
+                )?;
+                timeout_handle.set(Some(handle));
+            }
+        }
+
         // 3. STORE IN CACHE
         let shared = SharedTexture {
             texture: texture_rc,
             image: img_rc,
+            format,
         };
 
         self.cache.insert(src.to_string(), shared.clone());
+        self.touch(src);
+        if self.initial_open {
+            self.initial_loads.push(shared.image.clone());
+        }
+
+        // Remember it until its image arrives (drop the ones that already did)
+        let mut pending = self.pending.borrow_mut();
+        pending.retain(|p| !is_loaded(&p.image) && p.texture.strong_count() > 0);
+        pending.push(PendingTexture {
+            texture: Rc::downgrade(&shared.texture),
+            image: shared.image.clone(),
+            format,
+            shaped,
+        });
 
         Ok(shared)
     }
 }
+
+// 1x1 fallbacks: still loading / gave up
+const BLUE_PIXEL: [u8; 4] = [0, 0, 255, 255];
+const ERROR_PIXEL: [u8; 4] = [60, 60, 60, 255];
+
+// Fill the currently bound texture with a single solid pixel (alpha dropped for Rgb)
+fn upload_pixel(context: &WebGlRenderingContext, pixel: [u8; 4], format: TextureFormat) -> Result<(), JsValue> {
+    let bytes = &pixel[..format.bytes_per_pixel() as usize];
+    // A 3-byte row isn't 4-aligned: the default UNPACK_ALIGNMENT would reject it
+    context.pixel_storei(WebGlRenderingContext::UNPACK_ALIGNMENT, 1);
+    let result = context.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+        WebGlRenderingContext::TEXTURE_2D,
+        0,
+        format.gl_format() as i32,
+        1,
+        1,
+        0,
+        format.gl_format(),
+        WebGlRenderingContext::UNSIGNED_BYTE,
+        Some(bytes),
+    );
+    context.pixel_storei(WebGlRenderingContext::UNPACK_ALIGNMENT, 4);
+    result
+}
+
+// Upload an <img> into the bound texture. The flip flag is pixel-store state
+// shared by every upload, so it's set right before and reset right after.
+fn upload_image(
+    context: &WebGlRenderingContext,
+    img: &HtmlImageElement,
+    flip_y: bool,
+    format: TextureFormat,
+) -> Result<(), JsValue> {
+    if flip_y {
+        context.pixel_storei(WebGlRenderingContext::UNPACK_FLIP_Y_WEBGL, 1);
+    }
+    let result = context.tex_image_2d_with_u32_and_u32_and_image(
+        WebGlRenderingContext::TEXTURE_2D,
+        0,
+        format.gl_format() as i32,
+        format.gl_format(),
+        WebGlRenderingContext::UNSIGNED_BYTE,
+        img,
+    );
+    if flip_y {
+        context.pixel_storei(WebGlRenderingContext::UNPACK_FLIP_Y_WEBGL, 0);
+    }
+    result
+}
+
+// Same for a <canvas> (shaped placeholders)
+fn upload_canvas(
+    context: &WebGlRenderingContext,
+    canvas: &HtmlCanvasElement,
+    flip_y: bool,
+    format: TextureFormat,
+) -> Result<(), JsValue> {
+    if flip_y {
+        context.pixel_storei(WebGlRenderingContext::UNPACK_FLIP_Y_WEBGL, 1);
+    }
+    let result = context.tex_image_2d_with_u32_and_u32_and_canvas(
+        WebGlRenderingContext::TEXTURE_2D,
+        0,
+        format.gl_format() as i32,
+        format.gl_format(),
+        WebGlRenderingContext::UNSIGNED_BYTE,
+        canvas,
+    );
+    if flip_y {
+        context.pixel_storei(WebGlRenderingContext::UNPACK_FLIP_Y_WEBGL, 0);
+    }
+    result
+}
+
+// One cache entry's share of estimated_bytes
+fn texture_bytes(shared: &SharedTexture) -> u64 {
+    let pixels = if is_loaded(&shared.image) {
+        shared.image.natural_width() as u64 * shared.image.natural_height() as u64
+    } else {
+        1
+    };
+    pixels * shared.format.bytes_per_pixel() as u64
+}
+
+// Safe parameters for any size (NPOT: no mipmaps, no repeat)
+pub fn apply_texture_params(context: &WebGlRenderingContext) {
+    for (param, value) in [
+        (WebGlRenderingContext::TEXTURE_WRAP_S, WebGlRenderingContext::CLAMP_TO_EDGE),
+        (WebGlRenderingContext::TEXTURE_WRAP_T, WebGlRenderingContext::CLAMP_TO_EDGE),
+        (WebGlRenderingContext::TEXTURE_MIN_FILTER, WebGlRenderingContext::LINEAR),
+        (WebGlRenderingContext::TEXTURE_MAG_FILTER, WebGlRenderingContext::LINEAR),
+    ] {
+        context.tex_parameteri(WebGlRenderingContext::TEXTURE_2D, param, value as i32);
+    }
+}
+
+// An <img> is usable once it finished decoding with a real size
+pub fn is_loaded(img: &HtmlImageElement) -> bool {
+    img.complete() && img.natural_width() > 0
+}
+
+// Only network URLs need crossOrigin="anonymous" to stay untainted for WebGL
+fn needs_cors(src: &str) -> bool {
+    let src = src.trim_start().to_ascii_lowercase();
+    !(src.starts_with("data:") || src.starts_with("blob:"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_and_blob_urls_skip_cross_origin() {
+        assert!(!needs_cors("data:image/png;base64,iVBORw0KGgo="));
+        assert!(!needs_cors("  BLOB:https://app.example/5f1c"));
+        assert!(needs_cors("https://cdn.example/poster.jpg"));
+        assert!(needs_cors("/posters/local.jpg"));
+    }
+
+    #[test]
+    fn rgb_uploads_use_rgb_at_three_bytes_a_pixel() {
+        assert_eq!(TextureFormat::Rgb.gl_format(), WebGlRenderingContext::RGB);
+        assert_eq!(TextureFormat::Rgb.bytes_per_pixel(), 3);
+        assert_eq!(TextureFormat::Rgba.gl_format(), WebGlRenderingContext::RGBA);
+        assert_eq!(TextureFormat::Rgba.bytes_per_pixel(), 4);
+        let mut manager = TextureManager::new();
+        manager.set_upload_format(TextureFormat::Rgb);
+        assert_eq!(manager.format, TextureFormat::Rgb);
+    }
+
+    // Stand-in GL / DOM objects: never handed to JS, only compared by Rc
+    fn fake_texture() -> SharedTexture {
+        use wasm_bindgen::JsCast;
+        SharedTexture {
+            texture: Rc::new(JsValue::UNDEFINED.unchecked_into()),
+            image: Rc::new(JsValue::UNDEFINED.unchecked_into()),
+            format: TextureFormat::Rgba,
+        }
+    }
+
+    #[test]
+    fn pinned_textures_are_never_eviction_candidates() {
+        let mut manager = TextureManager::new();
+        for src in ["old.jpg", "visible.jpg", "recent.jpg"] {
+            manager.cache.insert(src.to_string(), fake_texture());
+            manager.touch(src);
+        }
+        let visible = manager.cache["visible.jpg"].texture.clone();
+
+        // The visible one is the oldest request of all, and still survives
+        manager.touch("old.jpg");
+        manager.touch("recent.jpg");
+        let order: Vec<_> = manager.eviction_order(&[visible]).into_iter().map(|(src, _)| src.as_str()).collect();
+        assert_eq!(order, ["old.jpg", "recent.jpg"]);
+    }
+
+    #[test]
+    fn a_300px_box_picks_the_320px_candidate() {
+        let mut manager = TextureManager::new();
+        let candidates = [("big.jpg".to_string(), 1280), ("small.jpg".to_string(), 320), ("tiny.jpg".to_string(), 160)];
+        assert_eq!(manager.pick_srcset(&candidates, 300).unwrap().1, 320);
+        assert_eq!(manager.pick_srcset(&candidates, 2000).unwrap().1, 1280); // Widest if none suffices
+        assert!(manager.pick_srcset(&[], 300).is_none());
+
+        // At a 2x texture scale the same box needs 600px
+        manager.texture_scale = Some(2.0);
+        assert_eq!(manager.pick_srcset(&candidates, 300).unwrap().1, 1280);
+    }
+
+    #[test]
+    fn only_a_big_lasting_scale_change_resizes_textures() {
+        let mut manager = TextureManager::new();
+        assert!(!manager.observe_scale(1.0, 0.0));
+        assert_eq!(manager.texture_scale, Some(1.0));
+
+        // Under 25%: ignored however long it lasts
+        assert!(!manager.observe_scale(1.2, 100.0));
+        assert!(!manager.observe_scale(1.2, 100.0 + 2.0 * RESCALE_SETTLE_MS));
+        assert_eq!(manager.texture_scale, Some(1.0));
+
+        // 25% or more: only once it has held for the settle time
+        assert!(!manager.observe_scale(1.25, 5000.0));
+        assert!(!manager.observe_scale(1.25, 5000.0 + RESCALE_SETTLE_MS - 1.0));
+        assert_eq!(manager.texture_scale, Some(1.0));
+        manager.observe_scale(1.25, 5000.0 + RESCALE_SETTLE_MS);
+        assert_eq!(manager.texture_scale, Some(1.25));
+    }
+
+    #[test]
+    fn aspect_key_buckets_by_relative_ratio() {
+        // Portrait posters and landscape stills never share a placeholder
+        assert_ne!(aspect_key(200, 300), aspect_key(1920, 1080));
+        assert_ne!(aspect_key(200, 300), aspect_key(300, 300));
+        // A couple of percent off the same shape lands in the same bucket
+        assert_eq!(aspect_key(200, 300), aspect_key(204, 300));
+        assert_eq!(aspect_key(1920, 1080), aspect_key(1900, 1080));
+        assert_eq!(aspect_key(300, 300), aspect_key(300, 306));
+        // Degenerate sizes don't panic
+        assert_eq!(aspect_key(0, 0), aspect_key(1, 1));
+    }
+
+    #[test]
+    fn initial_load_progress_with_nothing_requested() {
+        let mut manager = TextureManager::new();
+        // Still collecting the first screen: not done, even with nothing asked for
+        assert_eq!(manager.initial_load_progress(), 0.0);
+        // Closed with nothing to wait on: done
+        manager.end_initial_loads();
+        assert_eq!(manager.initial_load_progress(), 1.0);
+    }
+}